@@ -0,0 +1,97 @@
+use crate::ai::mcts::get_best_move_mcts;
+use crate::ai::minimax::{default_engine, default_move_db, get_best_move_minimax};
+use crate::ai::simple::choose_highest_power;
+use crate::core::battle::{determine_winner, is_battle_over, legal_actions, step_battle, BattleOptions};
+use crate::core::rng::SplitMix64Rng;
+use crate::core::state::{Action, BattleState};
+
+const MAX_TURNS: u32 = 100;
+/// 倒れた後の交代だけの手番はターンに数えないので、手番の数にも別に上限を置く
+const MAX_STEPS: u32 = MAX_TURNS * 2;
+
+/// 対戦させる AI の種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AiKind {
+    Minimax { depth: usize },
+    Mcts { iterations: usize },
+    HighestPower,
+}
+
+impl AiKind {
+    /// 行動を選ぶ。AI が何も返さなければ合法手の先頭で代用する
+    pub fn choose(self, state: &BattleState, player_id: &str) -> Option<Action> {
+        let action = match self {
+            AiKind::Minimax { depth } => get_best_move_minimax(state, player_id, depth),
+            AiKind::Mcts { iterations } => get_best_move_mcts(state, player_id, iterations),
+            AiKind::HighestPower => choose_highest_power(default_engine(), state, player_id),
+        };
+        action.or_else(|| legal_actions(state, player_id, default_move_db()).into_iter().next())
+    }
+}
+
+/// `run_many_battles` の集計結果。`[0]` が先頭のプレイヤー、`[1]` が2番目
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BattleStats {
+    pub games: usize,
+    pub wins: [usize; 2],
+    /// 決着がつかなかった (ターン上限・同時全滅の判定不能) 試合数
+    pub draws: usize,
+    pub average_turns: f64,
+    /// 全試合で倒れたポケモンの合計
+    pub faints: [usize; 2],
+}
+
+/// `state` から `n` 試合を行って集計する。`i` 試合目は `seeds[i]` (足りなければ `i`) で乱数を初期化するので、
+/// 同じ引数なら結果は毎回同じになる。
+pub fn run_many_battles(state: &BattleState, n: usize, seeds: &[u64], p1: AiKind, p2: AiKind) -> BattleStats {
+    let mut stats = BattleStats {
+        games: n,
+        ..Default::default()
+    };
+    let mut total_turns = 0u64;
+    for game in 0..n {
+        let seed = seeds.get(game).copied().unwrap_or(game as u64);
+        let mut rng = SplitMix64Rng::new(seed);
+        let mut rng_fn = rng.as_fn();
+        let (finished, turns) = play_battle(state, &mut rng_fn, [p1, p2]);
+        total_turns += turns as u64;
+
+        let winner = determine_winner(&finished, &default_engine().rules);
+        match finished.players.iter().position(|p| Some(&p.id) == winner.as_ref()) {
+            Some(side @ 0..=1) => stats.wins[side] += 1,
+            _ => stats.draws += 1,
+        }
+        for (side, player) in finished.players.iter().take(2).enumerate() {
+            stats.faints[side] += player.team.iter().filter(|c| c.hp <= 0).count();
+        }
+    }
+    if n > 0 {
+        stats.average_turns = total_turns as f64 / n as f64;
+    }
+    stats
+}
+
+fn play_battle(state: &BattleState, rng: &mut dyn FnMut() -> f64, kinds: [AiKind; 2]) -> (BattleState, u32) {
+    let mut next = state.clone();
+    let mut turns = 0;
+    for _ in 0..MAX_STEPS {
+        if is_battle_over(&next) || turns >= MAX_TURNS {
+            break;
+        }
+        let actions: Vec<Action> = next
+            .players
+            .iter()
+            .zip(kinds)
+            .filter_map(|(player, kind)| kind.choose(&next, &player.id))
+            .collect();
+        if actions.is_empty() {
+            break;
+        }
+        let turn_before = next.turn;
+        next = step_battle(&next, &actions, rng, BattleOptions::default());
+        if next.turn > turn_before {
+            turns += 1;
+        }
+    }
+    (next, turns)
+}
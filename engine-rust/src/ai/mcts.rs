@@ -1,8 +1,8 @@
 use crate::ai::eval::evaluate_state;
-use crate::core::battle::{is_battle_over, step_battle, BattleOptions};
-use crate::core::state::{Action, ActionType, BattleState};
-use crate::core::utils::get_active_creature;
-use crate::data::moves::MoveDatabase;
+use crate::ai::minimax::{default_engine, default_move_db};
+use crate::ai::simple::choose_highest_power;
+use crate::core::battle::{is_battle_over, legal_actions, step_battle, BattleOptions};
+use crate::core::state::{Action, BattleState};
 
 struct LcgRng {
     state: u64,
@@ -18,24 +18,6 @@ impl LcgRng {
         let value = (self.state >> 11) as u64;
         (value as f64) / ((1u64 << 53) as f64)
     }
-
-    fn choose_index(&mut self, len: usize) -> usize {
-        if len <= 1 {
-            return 0;
-        }
-        let idx = (self.next_f64() * len as f64) as usize;
-        idx.min(len - 1)
-    }
-}
-
-fn needs_switch(state: &BattleState, player_id: &str) -> bool {
-    let Some(active) = get_active_creature(state, player_id) else {
-        return true;
-    };
-    if active.hp <= 0 {
-        return true;
-    }
-    active.statuses.iter().any(|s| s.id == "pending_switch")
 }
 
 fn opponent_id(state: &BattleState, player_id: &str) -> Option<String> {
@@ -46,75 +28,61 @@ fn opponent_id(state: &BattleState, player_id: &str) -> Option<String> {
         .map(|p| p.id.clone())
 }
 
-fn move_has_pp(active: &crate::core::state::CreatureState, move_id: &str, move_db: &MoveDatabase) -> bool {
-    let Some(move_data) = move_db.get(move_id) else {
-        return false;
-    };
-    let Some(pp) = move_data.pp else {
-        return true;
-    };
-    match active.move_pp.get(move_id) {
-        Some(remaining) => *remaining > 0,
-        None => pp > 0,
-    }
-}
-
 fn available_actions(state: &BattleState, player_id: &str) -> Vec<Action> {
-    let player = state.players.iter().find(|p| p.id == player_id);
-    let Some(player) = player else {
-        return Vec::new();
-    };
-
-    let switch_actions: Vec<Action> = player
-        .team
-        .iter()
-        .enumerate()
-        .filter(|(idx, mon)| *idx != player.active_slot && mon.hp > 0)
-        .map(|(idx, _)| Action {
-            player_id: player.id.clone(),
-            action_type: ActionType::Switch,
-            move_id: None,
-            target_id: None,
-            slot: Some(idx),
-            priority: None,
-        })
-        .collect();
+    legal_actions(state, player_id, default_move_db())
+}
 
-    if needs_switch(state, player_id) {
-        return switch_actions;
-    }
+/// プレイアウト中の行動の選び方
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RolloutPolicy {
+    /// 合法手から一様ランダム
+    #[default]
+    Random,
+    /// `choose_highest_power` の期待ダメージ最大の技 (交代が必要な場面などはランダム)
+    HighestDamage,
+    /// 4回に3回は期待ダメージ最大の技、残りはランダム
+    Heuristic,
+}
 
-    let Some(active) = get_active_creature(state, player_id) else {
-        return switch_actions;
+fn choose_rollout_action(
+    state: &BattleState,
+    player_id: &str,
+    policy: RolloutPolicy,
+    rng: &mut dyn FnMut() -> f64,
+) -> Option<Action> {
+    let greedy = match policy {
+        RolloutPolicy::Random => false,
+        RolloutPolicy::HighestDamage => true,
+        RolloutPolicy::Heuristic => rng() < 0.75,
     };
-    if active.hp <= 0 {
-        return switch_actions;
-    }
-    let target_id = opponent_id(state, player_id);
-    let move_db = MoveDatabase::default();
-    let mut actions = Vec::new();
-    for move_id in &active.moves {
-        if !move_has_pp(active, move_id, &move_db) {
-            continue;
+    if greedy {
+        if let Some(action) = choose_highest_power(default_engine(), state, player_id) {
+            return Some(action);
         }
-        actions.push(Action {
-            player_id: player.id.clone(),
-            action_type: ActionType::Move,
-            move_id: Some(move_id.clone()),
-            target_id: target_id.clone(),
-            slot: None,
-            priority: None,
-        });
     }
-    if actions.is_empty() {
-        switch_actions
-    } else {
-        actions.extend(switch_actions);
-        actions
+    let actions = available_actions(state, player_id);
+    if actions.len() <= 1 {
+        return actions.into_iter().next();
     }
+    let idx = ((rng() * actions.len() as f64) as usize).min(actions.len() - 1);
+    actions.into_iter().nth(idx)
 }
 
-pub fn get_best_move_mcts(state: &BattleState, player_id: &str, _iterations: usize) -> Option<Action> {
+/// 局面の手番数から決まる乱数で ランダムプレイアウトする MCTS
+pub fn get_best_move_mcts(state: &BattleState, player_id: &str, iterations: usize) -> Option<Action> {
+    let mut rng = LcgRng::new(0x9e3779b97f4a7c15 ^ state.turn as u64);
+    let mut rng_fn = || rng.next_f64();
+    get_best_move_mcts_with_policy(state, player_id, iterations, RolloutPolicy::Random, &mut rng_fn)
+}
+
+/// プレイアウトの方針と乱数を指定する MCTS。同じ乱数列・回数・方針なら同じ行動を返す
+pub fn get_best_move_mcts_with_policy(
+    state: &BattleState,
+    player_id: &str,
+    iterations: usize,
+    policy: RolloutPolicy,
+    rng: &mut dyn FnMut() -> f64,
+) -> Option<Action> {
     let actions = available_actions(state, player_id);
     if actions.is_empty() {
         return None;
@@ -123,9 +91,8 @@ pub fn get_best_move_mcts(state: &BattleState, player_id: &str, _iterations: usi
         return actions.first().cloned();
     };
 
-    let iterations = _iterations.max(1);
+    let iterations = iterations.max(1);
     let rollout_depth = 3usize;
-    let mut rng = LcgRng::new(0x9e3779b97f4a7c15 ^ state.turn as u64);
 
     let mut best_action = None;
     let mut best_score = f32::NEG_INFINITY;
@@ -133,37 +100,32 @@ pub fn get_best_move_mcts(state: &BattleState, player_id: &str, _iterations: usi
         let mut total_score = 0.0;
         for _ in 0..iterations {
             let mut sim_state = state.clone();
-            let opp_actions = available_actions(&sim_state, &opp_id);
-            if opp_actions.is_empty() {
+            let Some(opp_action) = choose_rollout_action(&sim_state, &opp_id, policy, rng) else {
                 total_score += evaluate_state(&sim_state, player_id);
                 continue;
-            }
-            let opp_action = opp_actions[rng.choose_index(opp_actions.len())].clone();
-            let mut step_rng = || rng.next_f64();
+            };
             sim_state = step_battle(
                 &sim_state,
                 &[action.clone(), opp_action],
-                &mut step_rng,
-                BattleOptions { record_history: false },
+                rng,
+                BattleOptions { record_history: false, ..Default::default() },
             );
 
             for _ in 0..rollout_depth {
                 if is_battle_over(&sim_state) {
                     break;
                 }
-                let my_actions = available_actions(&sim_state, player_id);
-                let opp_actions = available_actions(&sim_state, &opp_id);
-                if my_actions.is_empty() || opp_actions.is_empty() {
+                let (Some(my_action), Some(opp_action)) = (
+                    choose_rollout_action(&sim_state, player_id, policy, rng),
+                    choose_rollout_action(&sim_state, &opp_id, policy, rng),
+                ) else {
                     break;
-                }
-                let my_action = my_actions[rng.choose_index(my_actions.len())].clone();
-                let opp_action = opp_actions[rng.choose_index(opp_actions.len())].clone();
-                let mut step_rng = || rng.next_f64();
+                };
                 sim_state = step_battle(
                     &sim_state,
                     &[my_action, opp_action],
-                    &mut step_rng,
-                    BattleOptions { record_history: false },
+                    rng,
+                    BattleOptions { record_history: false, ..Default::default() },
                 );
             }
             total_score += evaluate_state(&sim_state, player_id);
@@ -1,18 +1,8 @@
 use crate::ai::eval::evaluate_state;
-use crate::core::battle::{is_battle_over, step_battle, BattleOptions};
-use crate::core::state::{Action, ActionType, BattleState};
-use crate::core::utils::get_active_creature;
+use crate::core::battle::{is_battle_over, legal_actions, BattleEngine, BattleOptions};
+use crate::core::state::{Action, BattleState};
 use crate::data::moves::MoveDatabase;
-
-fn needs_switch(state: &BattleState, player_id: &str) -> bool {
-    let Some(active) = get_active_creature(state, player_id) else {
-        return true;
-    };
-    if active.hp <= 0 {
-        return true;
-    }
-    active.statuses.iter().any(|s| s.id == "pending_switch")
-}
+use std::sync::OnceLock;
 
 fn opponent_id(state: &BattleState, player_id: &str) -> Option<String> {
     state
@@ -22,77 +12,24 @@ fn opponent_id(state: &BattleState, player_id: &str) -> Option<String> {
         .map(|p| p.id.clone())
 }
 
-fn move_has_pp(active: &crate::core::state::CreatureState, move_id: &str, move_db: &MoveDatabase) -> bool {
-    let Some(move_data) = move_db.get(move_id) else {
-        return false;
-    };
-    let Some(pp) = move_data.pp else {
-        return true;
-    };
-    match active.move_pp.get(move_id) {
-        Some(remaining) => *remaining > 0,
-        None => pp > 0,
-    }
+// 探索中に何度も呼ばれるので 技データは一度だけ読み込む
+pub(crate) fn default_engine() -> &'static BattleEngine {
+    static ENGINE: OnceLock<BattleEngine> = OnceLock::new();
+    ENGINE.get_or_init(BattleEngine::default)
 }
 
-fn available_actions(state: &BattleState, player_id: &str) -> Vec<Action> {
-    let player = state.players.iter().find(|p| p.id == player_id);
-    let Some(player) = player else {
-        return Vec::new();
-    };
-
-    let mut actions = Vec::new();
-    let switch_actions: Vec<Action> = player
-        .team
-        .iter()
-        .enumerate()
-        .filter(|(idx, mon)| *idx != player.active_slot && mon.hp > 0)
-        .map(|(idx, _)| Action {
-            player_id: player.id.clone(),
-            action_type: ActionType::Switch,
-            move_id: None,
-            target_id: None,
-            slot: Some(idx),
-            priority: None,
-        })
-        .collect();
-
-    if needs_switch(state, player_id) {
-        return switch_actions;
-    }
-
-    let Some(active) = get_active_creature(state, player_id) else {
-        return switch_actions;
-    };
-    if active.hp <= 0 {
-        return switch_actions;
-    }
-    let target_id = opponent_id(state, player_id);
-    let move_db = MoveDatabase::default();
-    for move_id in &active.moves {
-        if !move_has_pp(active, move_id, &move_db) {
-            continue;
-        }
-        actions.push(Action {
-            player_id: player.id.clone(),
-            action_type: ActionType::Move,
-            move_id: Some(move_id.clone()),
-            target_id: target_id.clone(),
-            slot: None,
-            priority: None,
-        });
-    }
+pub(crate) fn default_move_db() -> &'static MoveDatabase {
+    &default_engine().move_db
+}
 
-    if actions.is_empty() {
-        switch_actions
-    } else {
-        actions.extend(switch_actions);
-        actions
-    }
+/// 使える技に加えて 控えへの交代も候補に含める。
+fn available_actions(state: &BattleState, player_id: &str) -> Vec<Action> {
+    legal_actions(state, player_id, default_move_db())
 }
 
 fn evaluate_after_turn(
-    state: &BattleState,
+    engine: &BattleEngine,
+    state: &mut BattleState,
     max_player_id: &str,
     depth: usize,
 ) -> f32 {
@@ -118,8 +55,9 @@ fn evaluate_after_turn(
         for opp_action in &opp_actions {
             let actions = vec![action.clone(), opp_action.clone()];
             let mut rng = || 0.42;
-            let next = step_battle(state, &actions, &mut rng, BattleOptions { record_history: false });
-            let score = evaluate_after_turn(&next, max_player_id, depth - 1);
+            let token = engine.apply_turn(state, &actions, &mut rng, BattleOptions { record_history: false, ..Default::default() });
+            let score = evaluate_after_turn(engine, state, max_player_id, depth - 1);
+            state.restore(token);
             if score < worst {
                 worst = score;
             }
@@ -147,13 +85,16 @@ pub fn get_best_move_minimax(state: &BattleState, player_id: &str, depth: usize)
     let mut best_action = None;
     let mut best_score = f32::NEG_INFINITY;
     let search_depth = depth.max(1);
+    let engine = default_engine();
+    let mut scratch = state.clone();
     for action in &max_actions {
         let mut worst = f32::INFINITY;
         for opp_action in &opp_actions {
             let actions = vec![action.clone(), opp_action.clone()];
             let mut rng = || 0.42;
-            let next = step_battle(state, &actions, &mut rng, BattleOptions { record_history: false });
-            let score = evaluate_after_turn(&next, player_id, search_depth - 1);
+            let token = engine.apply_turn(&mut scratch, &actions, &mut rng, BattleOptions { record_history: false, ..Default::default() });
+            let score = evaluate_after_turn(engine, &mut scratch, player_id, search_depth - 1);
+            scratch.restore(token);
             if score < worst {
                 worst = score;
             }
@@ -165,3 +106,75 @@ pub fn get_best_move_minimax(state: &BattleState, player_id: &str, depth: usize)
     }
     best_action
 }
+
+/// AI の強さ。`skill` 0.0 で完全ランダム、1.0 で常に最善手。
+#[derive(Clone, Copy, Debug)]
+pub struct AiConfig {
+    pub skill: f32,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self { skill: 1.0 }
+    }
+}
+
+/// 確率 `1 - skill` で合法手からランダムに選び、それ以外はミニマックスの最善手を返す。
+pub fn get_best_move_with_config(
+    state: &BattleState,
+    player_id: &str,
+    depth: usize,
+    config: AiConfig,
+    rng: &mut dyn FnMut() -> f64,
+) -> Option<Action> {
+    let actions = available_actions(state, player_id);
+    if actions.is_empty() {
+        return None;
+    }
+    if rng() >= config.skill as f64 {
+        let idx = ((rng() * actions.len() as f64) as usize).min(actions.len() - 1);
+        return actions.into_iter().nth(idx);
+    }
+    get_best_move_minimax(state, player_id, depth)
+}
+
+/// Stand-in attack assumed for an unrevealed move slot, picked from the creature's own type.
+fn placeholder_move(types: &[String]) -> &'static str {
+    match types.first().map(String::as_str) {
+        Some("fire") => "flamethrower",
+        Some("water") => "surf",
+        Some("grass") => "vine_whip",
+        Some("electric") => "thunder_shock",
+        Some("psychic") => "psychic",
+        _ => "tackle",
+    }
+}
+
+/// Rewrites the opponent's movesets so they only contain what `player_id` could know about:
+/// revealed moves, plus a same-type placeholder standing in for any slots still hidden.
+fn fog_state(state: &BattleState, player_id: &str) -> BattleState {
+    let mut view = state.clone();
+    for player in view.players.iter_mut().filter(|p| p.id != player_id) {
+        for creature in &mut player.team {
+            let mut moves: Vec<String> = creature
+                .moves
+                .iter()
+                .filter(|m| creature.revealed_moves.contains(m))
+                .cloned()
+                .collect();
+            if moves.len() < creature.moves.len() {
+                let filler = placeholder_move(&creature.types).to_string();
+                if !moves.contains(&filler) {
+                    moves.push(filler);
+                }
+            }
+            creature.moves = moves;
+        }
+    }
+    view
+}
+
+/// Minimax that only models the opponent with information revealed so far in the battle.
+pub fn get_best_move_minimax_fog(state: &BattleState, player_id: &str, depth: usize) -> Option<Action> {
+    get_best_move_minimax(&fog_state(state, player_id), player_id, depth)
+}
@@ -1,9 +1,11 @@
+pub mod batch;
 pub mod eval;
 pub mod mcts;
 pub mod minimax;
 pub mod simple;
 
+pub use batch::{run_many_battles, AiKind, BattleStats};
 pub use eval::evaluate_state;
-pub use mcts::get_best_move_mcts;
-pub use minimax::get_best_move_minimax;
+pub use mcts::{get_best_move_mcts, get_best_move_mcts_with_policy, RolloutPolicy};
+pub use minimax::{get_best_move_minimax, get_best_move_minimax_fog, get_best_move_with_config, AiConfig};
 pub use simple::{choose_highest_power, run_auto_battle};
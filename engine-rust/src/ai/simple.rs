@@ -1,9 +1,11 @@
-use crate::core::battle::{is_battle_over, step_battle, BattleOptions};
+use crate::core::battle::{is_battle_over, step_battle, BattleEngine, BattleOptions};
 use crate::core::state::{Action, ActionType, BattleState};
+use crate::core::damage::calc_damage_range;
 use crate::core::utils::get_active_creature;
-use crate::data::moves::MoveDatabase;
 
-pub fn choose_highest_power(state: &BattleState, player_id: &str) -> Option<Action> {
+/// 期待ダメージ (タイプ相性・タイプ一致・命中込み) が最大の技を選ぶ。同値なら技IDの若い順。
+/// 技データとタイプ相性は `engine` のものを使う
+pub fn choose_highest_power(engine: &BattleEngine, state: &BattleState, player_id: &str) -> Option<Action> {
     let player = state.players.iter().find(|p| p.id == player_id)?;
     let active = get_active_creature(state, player_id)?;
     if active.hp <= 0 {
@@ -15,20 +17,31 @@ pub fn choose_highest_power(state: &BattleState, player_id: &str) -> Option<Acti
         .iter()
         .find(|p| p.id != player_id)
         .map(|p| p.id.clone())?;
+    get_active_creature(state, &target_id)?;
 
-    let move_db = MoveDatabase::minimal();
-    let mut best_move_id = active.moves.first()?.clone();
-    let mut best_power = -1;
+    let mut best: Option<(f32, &String)> = None;
 
     for move_id in &active.moves {
-        if let Some(move_data) = move_db.get(move_id) {
-            let power = move_data.power.unwrap_or(0);
-            if power > best_power {
-                best_power = power;
-                best_move_id = move_id.clone();
+        let Some(move_data) = engine.move_db.get(move_id) else {
+            continue;
+        };
+        let rolls = calc_damage_range(state, player_id, &target_id, move_id, engine).rolls;
+        let accuracy = move_data.accuracy.unwrap_or(1.0);
+        let expected = rolls.iter().sum::<i32>() as f32 / rolls.len() as f32 * accuracy;
+        let better = match best {
+            None => true,
+            Some((best_expected, best_id)) => {
+                expected > best_expected || (expected == best_expected && move_id < best_id)
             }
+        };
+        if better {
+            best = Some((expected, move_id));
         }
     }
+    let best_move_id = match best {
+        Some((_, move_id)) => move_id.clone(),
+        None => active.moves.first()?.clone(),
+    };
 
     Some(Action {
         player_id: player.id.clone(),
@@ -5,7 +5,7 @@ use engine_rust::core::factory::{create_creature, CreateCreatureOptions};
 use engine_rust::core::state::{create_battle_state, Action, ActionType, BattleState, PlayerState};
 use engine_rust::core::utils::get_active_creature;
 use engine_rust::data::learnsets::LearnsetDatabase;
-use engine_rust::data::moves::MoveDatabase;
+use engine_rust::data::moves::{MoveCategory, MoveDatabase};
 use engine_rust::data::species::SpeciesDatabase;
 
 use std::io::{self, Write};
@@ -105,7 +105,7 @@ fn main() {
                     let name = move_data.name.as_ref().map(|s| s.as_str()).unwrap_or(move_id);
                     let move_type = move_data.move_type.as_ref().map(|s| s.as_str()).unwrap_or("???");
                     let power = move_data.power.map(|p| p.to_string()).unwrap_or("-".to_string());
-                    let category = match move_data.category.as_deref() {
+                    let category = match move_data.category.map(MoveCategory::as_str) {
                         Some("physical") => "物理",
                         Some("special") => "特殊",
                         Some("status") => "変化",
@@ -256,20 +256,8 @@ fn main() {
     println!();
 
     // バトル状態作成
-    let player_state = PlayerState {
-        id: "player".to_string(),
-        name: "あなた".to_string(),
-        team: player_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let ai_state = PlayerState {
-        id: "ai".to_string(),
-        name: "相手".to_string(),
-        team: ai_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let player_state = PlayerState::new("player", "あなた", player_team);
+    let ai_state = PlayerState::new("ai", "相手", ai_team);
 
     let mut state = create_battle_state(vec![player_state, ai_state]);
     let mut rng = || rand_f64();
@@ -532,7 +520,7 @@ fn print_enriched_logs(state: &BattleState, move_db: &MoveDatabase, last_idx: &m
                 // データベースから技を検索
                 if let Some(move_data) = find_move_by_name(move_db, move_name) {
                     let move_type = move_data.move_type.as_deref().unwrap_or("???");
-                    let category = match move_data.category.as_deref() {
+                    let category = match move_data.category.map(MoveCategory::as_str) {
                         Some("physical") => "物理",
                         Some("special") => "特殊",
                         Some("status") => "変化",
@@ -549,17 +537,7 @@ fn print_enriched_logs(state: &BattleState, move_db: &MoveDatabase, last_idx: &m
 }
 
 fn find_move_by_name<'a>(move_db: &'a MoveDatabase, name: &str) -> Option<&'a engine_rust::data::moves::MoveData> {
-    for m in move_db.as_map().values() {
-        if let Some(n) = &m.name {
-            if n == name {
-                return Some(m);
-            }
-        }
-        if m.id == name {
-            return Some(m);
-        }
-    }
-    None
+    move_db.by_name(name).or_else(|| move_db.get(name))
 }
 
 fn prompt_action(state: &BattleState, move_db: &MoveDatabase) -> Option<Action> {
@@ -626,7 +604,7 @@ fn handle_command(cmd: &str, state: &BattleState, move_db: &MoveDatabase) {
                         let power = move_data.power.map(|p| p.to_string()).unwrap_or("-".to_string());
                         let pp = move_data.pp.unwrap_or(0);
                         let current_pp = active.move_pp.get(move_id).copied().unwrap_or(pp);
-                        let category = move_data.category.as_ref().map(|s| s.as_str()).unwrap_or("???");
+                        let category = move_data.category.map(MoveCategory::as_str).unwrap_or("???");
                         let priority = move_data.priority.unwrap_or(0);
                         println!("  {}. {} [{}] - {} | 威力: {} | PP: {}/{} | 優先度: {:+}",
                             i + 1, name, move_type, category, power, current_pp, pp, priority);
@@ -4,10 +4,11 @@
 //! abilities, and battle mechanics.
 
 use engine_rust::core::battle::{is_battle_over, BattleEngine, BattleOptions};
+use engine_rust::core::damage::calc_damage_range;
 use engine_rust::core::factory::{calc_stat, create_creature, CreateCreatureOptions};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState};
 use engine_rust::data::learnsets::LearnsetDatabase;
-use engine_rust::data::moves::MoveDatabase;
+use engine_rust::data::moves::{MoveCategory, MoveDatabase};
 use engine_rust::data::species::SpeciesDatabase;
 use engine_rust::data::type_chart::TypeChart;
 use inquire::{Select, Text};
@@ -121,7 +122,7 @@ fn print_species_info(species_id: &str, species_db: &SpeciesDatabase, move_db: &
             if let Some(m) = move_db.get(move_id) {
                 let name = m.name.as_deref().unwrap_or(move_id);
                 let mtype = m.move_type.as_deref().unwrap_or("???");
-                let cat = m.category.as_deref().unwrap_or("???");
+                let cat = m.category.map(MoveCategory::as_str).unwrap_or("???");
                 let power = m.power.map(|p| p.to_string()).unwrap_or("-".to_string());
                 println!("│   {} {} {} 威力:{}          ", 
                     format!("{:<12}", name),
@@ -370,7 +371,7 @@ fn print_creature_details(creature: &CreatureState, move_db: &MoveDatabase) {
         if let Some(m) = move_db.get(move_id) {
             let name = m.name.as_deref().unwrap_or(move_id);
             let mtype = m.move_type.as_deref().unwrap_or("???");
-            let cat = m.category.as_deref().unwrap_or("???");
+            let cat = m.category.map(MoveCategory::as_str).unwrap_or("???");
             let power = m.power.map(|p| p.to_string()).unwrap_or("-".to_string());
             let pp = m.pp.unwrap_or(0);
             println!("║    {} | {} | {} | 威力:{} | PP:{}  ", name, format_type(mtype), format_category(cat), power, pp);
@@ -594,28 +595,18 @@ fn run_battle_simulation(species_db: &SpeciesDatabase, move_db: &MoveDatabase, l
 }
 
 fn create_battle(p1_team: Vec<CreatureState>, p2_team: Vec<CreatureState>) -> BattleState {
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "あなた".to_string(),
-        team: p1_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "相手".to_string(),
-        team: p2_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let p1 = PlayerState::new("p1", "あなた", p1_team);
+    let p2 = PlayerState::new("p2", "相手", p2_team);
     BattleState {
         players: vec![p1, p2],
         turn: 1,
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -688,7 +679,7 @@ fn rand_f64() -> f64 {
 // Damage Calculator
 // ============================================================================
 
-fn predict_damage(state: &BattleState, move_db: &MoveDatabase, _engine: &BattleEngine) {
+fn predict_damage(state: &BattleState, move_db: &MoveDatabase, engine: &BattleEngine) {
     let player = &state.players[0];
     let active = &player.team[player.active_slot];
     let opponent = &state.players[1];
@@ -702,19 +693,19 @@ fn predict_damage(state: &BattleState, move_db: &MoveDatabase, _engine: &BattleE
             let name = m.name.as_deref().unwrap_or(move_id);
             
             // Skip status moves
-            if m.category.as_deref() == Some("status") || m.power.unwrap_or(0) == 0 {
+            if m.category == Some(MoveCategory::Status) || m.power.unwrap_or(0) == 0 {
                 println!("  {} (変化技 - ダメージなし)", name);
                 continue;
             }
 
-            let damage_info = calc_damage_breakdown(active, opp_active, m);
+            let damage_info = calc_damage_breakdown(state, m, engine);
             println!("  【{}】", name);
             println!("    タイプ: {} | カテゴリ: {} | 威力: {}", 
                 format_type(m.move_type.as_deref().unwrap_or("???")),
-                format_category(m.category.as_deref().unwrap_or("???")),
+                format_category(m.category.map(MoveCategory::as_str).unwrap_or("???")),
                 m.power.unwrap_or(0)
             );
-            println!("    タイプ相性: {}x", damage_info.type_effectiveness);
+            println!("    タイプ相性: {}", format_effectiveness(&damage_info));
             println!("    攻撃実数値: {} → 防御実数値: {}", damage_info.atk_stat, damage_info.def_stat);
             println!("    ダメージ範囲: {} ~ {} (HP {}% ~ {}%)", 
                 damage_info.min_damage, 
@@ -738,114 +729,54 @@ struct DamageBreakdown {
     atk_stat: i32,
     def_stat: i32,
     type_effectiveness: f32,
+    type_breakdown: Vec<(String, f32)>,
     min_damage: i32,
     max_damage: i32,
     ohko_chance: f32,
 }
 
-fn calc_damage_breakdown(attacker: &CreatureState, defender: &CreatureState, move_data: &engine_rust::data::moves::MoveData) -> DamageBreakdown {
-    let level = attacker.level as i32;
-    let power = move_data.power.unwrap_or(0);
-    
-    let is_special = move_data.category.as_deref() == Some("special");
+fn calc_damage_breakdown(
+    state: &BattleState,
+    move_data: &engine_rust::data::moves::MoveData,
+    engine: &BattleEngine,
+) -> DamageBreakdown {
+    let attacker = &state.players[0].team[state.players[0].active_slot];
+    let defender = &state.players[1].team[state.players[1].active_slot];
+
+    let is_special = move_data.category == Some(MoveCategory::Special);
     let atk_stat = if is_special { attacker.sp_attack } else { attacker.attack };
     let def_stat = if is_special { defender.sp_defense } else { defender.defense };
 
-    // Type effectiveness (simplified - would need full type chart)
     let move_type = move_data.move_type.as_deref().unwrap_or("normal");
-    let type_effectiveness = calc_type_effectiveness(move_type, &defender.types);
-
-    // STAB
-    let stab = if attacker.types.iter().any(|t| t.to_lowercase() == move_type.to_lowercase()) {
-        1.5
-    } else {
-        1.0
-    };
-
-    // Base damage formula
-    let base_damage = ((2 * level / 5 + 2) * power * atk_stat / def_stat / 50 + 2) as f32;
-    let modified_damage = base_damage * stab * type_effectiveness;
+    let type_breakdown = engine.type_chart.effectiveness_breakdown(move_type, &defender.types);
+    let type_effectiveness = type_breakdown.iter().map(|(_, multiplier)| multiplier).product();
 
-    let min_damage = (modified_damage * 0.85) as i32;
-    let max_damage = modified_damage as i32;
-
-    // OHKO chance (simplified)
-    let ohko_chance = if min_damage >= defender.hp {
-        1.0
-    } else if max_damage < defender.hp {
-        0.0
-    } else {
-        let range = max_damage - min_damage + 1;
-        let ohko_rolls = max_damage - defender.hp + 1;
-        (ohko_rolls as f32 / range as f32).max(0.0).min(1.0)
-    };
+    let range = calc_damage_range(state, &state.players[0].id, &state.players[1].id, &move_data.id, engine);
+    let ohko_rolls = range.rolls.iter().filter(|&&damage| damage >= defender.hp).count();
 
     DamageBreakdown {
         atk_stat,
         def_stat,
         type_effectiveness,
-        min_damage: min_damage.max(1),
-        max_damage: max_damage.max(1),
-        ohko_chance,
+        type_breakdown,
+        min_damage: range.min,
+        max_damage: range.max,
+        ohko_chance: ohko_rolls as f32 / range.rolls.len() as f32,
     }
 }
 
-fn calc_type_effectiveness(move_type: &str, defender_types: &[String]) -> f32 {
-    // Simplified type chart - would use actual TypeChart in real implementation
-    let mut effectiveness = 1.0;
-    
-    for def_type in defender_types {
-        let mult = match (move_type.to_lowercase().as_str(), def_type.to_lowercase().as_str()) {
-            // Super effective
-            ("fire", "grass") | ("fire", "ice") | ("fire", "bug") | ("fire", "steel") => 2.0,
-            ("water", "fire") | ("water", "ground") | ("water", "rock") => 2.0,
-            ("grass", "water") | ("grass", "ground") | ("grass", "rock") => 2.0,
-            ("electric", "water") | ("electric", "flying") => 2.0,
-            ("ice", "grass") | ("ice", "ground") | ("ice", "flying") | ("ice", "dragon") => 2.0,
-            ("fighting", "normal") | ("fighting", "ice") | ("fighting", "rock") | ("fighting", "dark") | ("fighting", "steel") => 2.0,
-            ("ground", "fire") | ("ground", "electric") | ("ground", "poison") | ("ground", "rock") | ("ground", "steel") => 2.0,
-            ("flying", "grass") | ("flying", "fighting") | ("flying", "bug") => 2.0,
-            ("psychic", "fighting") | ("psychic", "poison") => 2.0,
-            ("bug", "grass") | ("bug", "psychic") | ("bug", "dark") => 2.0,
-            ("rock", "fire") | ("rock", "ice") | ("rock", "flying") | ("rock", "bug") => 2.0,
-            ("ghost", "psychic") | ("ghost", "ghost") => 2.0,
-            ("dragon", "dragon") => 2.0,
-            ("dark", "psychic") | ("dark", "ghost") => 2.0,
-            ("steel", "ice") | ("steel", "rock") | ("steel", "fairy") => 2.0,
-            ("fairy", "fighting") | ("fairy", "dragon") | ("fairy", "dark") => 2.0,
-            
-            // Not very effective
-            ("fire", "fire") | ("fire", "water") | ("fire", "rock") | ("fire", "dragon") => 0.5,
-            ("water", "water") | ("water", "grass") | ("water", "dragon") => 0.5,
-            ("grass", "fire") | ("grass", "grass") | ("grass", "poison") | ("grass", "flying") | ("grass", "bug") | ("grass", "dragon") | ("grass", "steel") => 0.5,
-            ("electric", "electric") | ("electric", "grass") | ("electric", "dragon") => 0.5,
-            ("fighting", "poison") | ("fighting", "flying") | ("fighting", "psychic") | ("fighting", "bug") | ("fighting", "fairy") => 0.5,
-            ("poison", "poison") | ("poison", "ground") | ("poison", "rock") | ("poison", "ghost") => 0.5,
-            ("ground", "grass") | ("ground", "bug") => 0.5,
-            ("flying", "electric") | ("flying", "rock") | ("flying", "steel") => 0.5,
-            ("psychic", "psychic") | ("psychic", "steel") => 0.5,
-            ("bug", "fire") | ("bug", "fighting") | ("bug", "poison") | ("bug", "flying") | ("bug", "ghost") | ("bug", "steel") | ("bug", "fairy") => 0.5,
-            ("rock", "fighting") | ("rock", "ground") | ("rock", "steel") => 0.5,
-            ("ghost", "dark") => 0.5,
-            ("dragon", "steel") => 0.5,
-            ("dark", "fighting") | ("dark", "dark") | ("dark", "fairy") => 0.5,
-            ("steel", "fire") | ("steel", "water") | ("steel", "electric") | ("steel", "steel") => 0.5,
-            ("fairy", "fire") | ("fairy", "poison") | ("fairy", "steel") => 0.5,
-
-            // Immunities
-            ("normal", "ghost") | ("fighting", "ghost") | ("ghost", "normal") => 0.0,
-            ("electric", "ground") => 0.0,
-            ("poison", "steel") => 0.0,
-            ("ground", "flying") => 0.0,
-            ("psychic", "dark") => 0.0,
-            ("dragon", "fairy") => 0.0,
-            
-            _ => 1.0,
-        };
-        effectiveness *= mult;
+/// "2x (fire) × 0.5x (water) = 1x" のように防御タイプごとの倍率を並べる
+fn format_effectiveness(info: &DamageBreakdown) -> String {
+    let parts: Vec<String> = info
+        .type_breakdown
+        .iter()
+        .map(|(defending, multiplier)| format!("{}x ({})", multiplier, defending))
+        .collect();
+    if parts.len() > 1 {
+        format!("{} = {}x", parts.join(" × "), info.type_effectiveness)
+    } else {
+        format!("{}x", info.type_effectiveness)
     }
-    
-    effectiveness
 }
 
 fn damage_calculator(species_db: &SpeciesDatabase, move_db: &MoveDatabase, learnset_db: &LearnsetDatabase, engine: &BattleEngine) {
@@ -916,7 +847,7 @@ fn inspect_move(move_db: &MoveDatabase) {
             println!("│ 📖 {} (ID: {})                           ", name, m.id);
             println!("├─────────────────────────────────────────────────────────┤");
             println!("│ タイプ: {}                                          ", format_type(m.move_type.as_deref().unwrap_or("???")));
-            println!("│ 分類: {}                                            ", format_category(m.category.as_deref().unwrap_or("???")));
+            println!("│ 分類: {}                                            ", format_category(m.category.map(MoveCategory::as_str).unwrap_or("???")));
             println!("│ 威力: {}                                             ", m.power.map(|p| p.to_string()).unwrap_or("-".to_string()));
             println!("│ 命中: {}                                             ", m.accuracy.map(|a| format!("{:.0}%", a * 100.0)).unwrap_or("-".to_string()));
             println!("│ PP: {}                                               ", m.pp.map(|p| p.to_string()).unwrap_or("-".to_string()));
@@ -938,3 +869,100 @@ fn inspect_move(move_db: &MoveDatabase) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine_rust::data::moves::MoveData;
+
+    fn mono(id: &str, types: &[&str]) -> CreatureState {
+        CreatureState {
+            id: id.to_string(),
+            species_id: id.to_string(),
+            name: id.to_string(),
+            level: 50,
+            types: types.iter().map(|t| t.to_string()).collect(),
+            moves: Vec::new(),
+            ability: None,
+            item: None,
+            hp: 100,
+            max_hp: 100,
+            stages: Default::default(),
+            statuses: Vec::new(),
+            move_pp: HashMap::new(),
+            ability_data: HashMap::new(),
+            volatile_data: HashMap::new(),
+            revealed_moves: Vec::new(),
+            species_revealed: false,
+            weight: None,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
+            attack: 100,
+            defense: 100,
+            sp_attack: 100,
+            sp_defense: 100,
+            speed: 100,
+        }
+    }
+
+    fn breakdown_for(attacker: &CreatureState, defender: &CreatureState, move_data: MoveData) -> DamageBreakdown {
+        let mut engine = BattleEngine::default();
+        engine.move_db.insert(move_data.clone());
+        let state = create_battle(vec![attacker.clone()], vec![defender.clone()]);
+        calc_damage_breakdown(&state, &move_data, &engine)
+    }
+
+    #[test]
+    fn breakdown_effectiveness_matches_type_chart() {
+        let attacker = mono("attacker", &["normal"]);
+        let matchups: [(&str, &[&str], f32); 6] = [
+            ("water", &["fire"], 2.0),
+            ("electric", &["ground"], 0.0),
+            ("ground", &["flying"], 0.0),
+            ("fire", &["grass", "steel"], 4.0),
+            ("fighting", &["normal", "flying"], 1.0),
+            ("ice", &["dragon", "ground"], 4.0),
+        ];
+        for (move_type, defender_types, expected) in matchups {
+            let defender = mono("defender", defender_types);
+            let move_data = MoveData {
+                id: "test".to_string(),
+                name: None,
+                move_type: Some(move_type.to_string()),
+                category: Some(MoveCategory::Physical),
+                pp: None,
+                power: Some(80),
+                accuracy: Some(1.0),
+                priority: Some(0),
+                description: None,
+                steps: Vec::new(),
+                tags: Vec::new(),
+                crit_rate: None,
+            };
+            let breakdown = breakdown_for(&attacker, &defender, move_data);
+            assert_eq!(breakdown.type_effectiveness, expected, "{} vs {:?}", move_type, defender_types);
+        }
+    }
+
+    #[test]
+    fn effectiveness_line_lists_each_defending_type() {
+        let attacker = mono("attacker", &["normal"]);
+        let defender = mono("defender", &["grass", "water"]);
+        let move_data = MoveData {
+            id: "test".to_string(),
+            name: None,
+            move_type: Some("fire".to_string()),
+            category: Some(MoveCategory::Special),
+            pp: None,
+            power: Some(80),
+            accuracy: Some(1.0),
+            priority: Some(0),
+            description: None,
+            steps: Vec::new(),
+            tags: Vec::new(),
+            crit_rate: None,
+        };
+        let breakdown = breakdown_for(&attacker, &defender, move_data);
+        assert_eq!(format_effectiveness(&breakdown), "2x (grass) × 0.5x (water) = 1x");
+    }
+}
@@ -1,7 +1,14 @@
-use crate::core::events::{meta_get_bool, meta_with_move_source, BattleEvent};
-use crate::core::state::{Action, BattleState, CreatureState};
-use crate::core::utils::{get_active_creature, is_status_move};
-use crate::data::moves::MoveData;
+use crate::core::effects::get_item_id;
+use crate::core::events::{
+    has_major_status, is_status_immune, meta_get_bool, meta_get_string, meta_with_move_source, BattleEvent,
+};
+use crate::core::rules::GUARANTEED_CRIT_STAGE;
+use crate::core::state::{Action, BattleState, CreatureState, LogCategory};
+use crate::core::utils::{
+    active_battler_ids, find_battler, find_battler_mut, get_active_creature, get_active_creature_mut, is_status_move,
+    split_battler_id,
+};
+use crate::data::moves::{MoveCategory, MoveData};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 
@@ -9,11 +16,35 @@ use std::collections::HashMap;
 pub enum WeatherKind {
     Sun,
     Rain,
+    Sand,
+    Hail,
+}
+
+impl WeatherKind {
+    /// 場の状態としての ID
+    pub fn id(&self) -> &'static str {
+        match self {
+            WeatherKind::Sun => "sun",
+            WeatherKind::Rain => "rain",
+            WeatherKind::Sand => "sandstorm",
+            WeatherKind::Hail => "hail",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "sun" => Some(WeatherKind::Sun),
+            "rain" => Some(WeatherKind::Rain),
+            "sandstorm" => Some(WeatherKind::Sand),
+            "hail" => Some(WeatherKind::Hail),
+            _ => None,
+        }
+    }
 }
 
 pub struct AbilityValueContext<'a> {
     pub move_data: Option<&'a MoveData>,
-    pub category: Option<&'a str>,
+    pub category: Option<MoveCategory>,
     pub target: Option<&'a CreatureState>,
     pub weather: Option<&'a str>,
     pub turn: u32,
@@ -35,6 +66,32 @@ pub struct AbilityHookResult {
     pub override_action: Option<Action>,
 }
 
+/// かがくへんかガス: 場にいる自分以外のポケモンの特性を打ち消す
+pub fn ability_suppressed(state: &BattleState, player_id: &str) -> bool {
+    active_battler_ids(state).iter().filter(|id| *id != player_id).any(|id| {
+        get_active_creature(state, id).is_some_and(|c| c.hp > 0 && c.ability.as_deref() == Some("neutralizing_gas"))
+    })
+}
+
+fn active_ability<'a>(state: &BattleState, player_id: &str, active: &'a CreatureState) -> Option<&'a str> {
+    if ability_suppressed(state, player_id) {
+        return None;
+    }
+    active.ability.as_deref()
+}
+
+/// 場に出ているポケモンのいずれかが有効な `ability` を持っているか
+pub fn field_ability_active(state: &BattleState, ability: &str) -> bool {
+    active_battler_ids(state).iter().any(|id| {
+        get_active_creature(state, id).is_some_and(|c| c.hp > 0 && active_ability(state, id, c) == Some(ability))
+    })
+}
+
+/// ノーてんき・エアロック: 天気の影響をなくす
+pub fn weather_suppressed(state: &BattleState) -> bool {
+    field_ability_active(state, "cloud_nine") || field_ability_active(state, "air_lock")
+}
+
 pub fn run_ability_value_hook(
     state: &BattleState,
     player_id: &str,
@@ -45,7 +102,7 @@ pub fn run_ability_value_hook(
     let Some(active) = get_active_creature(state, player_id) else {
         return value;
     };
-    let Some(ability) = active.ability.as_deref() else {
+    let Some(ability) = active_ability(state, player_id, active) else {
         return value;
     };
 
@@ -55,14 +112,14 @@ pub fn run_ability_value_hook(
             _ => value,
         },
         ("fur_coat", "onModifyDefense") => {
-            if ctx.category == Some("physical") {
+            if ctx.category == Some(MoveCategory::Physical) {
                 value * 2.0
             } else {
                 value
             }
         }
         ("slow_start", "onModifyOffense") => {
-            if ctx.category == Some("physical") && ctx.turn <= 5 {
+            if ctx.category == Some(MoveCategory::Physical) && ctx.turn <= 5 {
                 value * 0.5
             } else {
                 value
@@ -94,18 +151,22 @@ pub fn run_ability_value_hook(
                 value
             }
         }
-        ("hustle", "onModifyPower") => if ctx.category == Some("physical") { value * 1.5 } else { value },
-        ("hustle", "onModifyAccuracy") => if ctx.category == Some("physical") { value * 0.8 } else { value },
-        ("pure_power", "onModifyPower") => if ctx.category == Some("physical") { value * 2.0 } else { value },
+        ("hustle", "onModifyPower") => if ctx.category == Some(MoveCategory::Physical) { value * 1.5 } else { value },
+        ("hustle", "onModifyAccuracy") => if ctx.category == Some(MoveCategory::Physical) { value * 0.8 } else { value },
+        ("pure_power", "onModifyPower") => if ctx.category == Some(MoveCategory::Physical) { value * 2.0 } else { value },
         ("guts", "onModifyPower") => {
-            if ctx.category == Some("physical") {
-                if let Some(active) = get_active_creature(state, player_id) {
-                    if !active.statuses.is_empty() {
-                        return value * 1.5;
-                    }
-                }
+            if ctx.category == Some(MoveCategory::Physical) && has_major_status(active) {
+                value * 1.5
+            } else {
+                value
+            }
+        }
+        ("marvel_scale", "onModifyDefense") => {
+            if ctx.category == Some(MoveCategory::Physical) && has_major_status(active) {
+                value * 1.5
+            } else {
+                value
             }
-            value
         }
         ("merciless", "onModifyCritChance") => {
             if let Some(target) = ctx.target {
@@ -114,7 +175,7 @@ pub fn run_ability_value_hook(
                     .iter()
                     .any(|s| s.id == "poison" || s.id == "toxic")
                 {
-                    return 999.0;
+                    return GUARANTEED_CRIT_STAGE;
                 }
             }
             value
@@ -122,12 +183,26 @@ pub fn run_ability_value_hook(
         ("super_luck", "onModifyCritChance") => value + 1.0,
         ("compound_eyes", "onModifyAccuracy") => value * 1.3,
         ("quick_feet", "onModifySpeed") => {
-            if let Some(active) = get_active_creature(state, player_id) {
-                if !active.statuses.is_empty() {
-                    return value * 1.5;
-                }
+            if has_major_status(active) {
+                value * 1.5
+            } else {
+                value
+            }
+        }
+        ("protosynthesis" | "quark_drive", "onModifyOffense") => {
+            match (paradox_boost(active), ctx.category) {
+                (Some("atk"), Some(MoveCategory::Physical)) | (Some("spa"), Some(MoveCategory::Special)) => value * 1.3,
+                _ => value,
+            }
+        }
+        ("protosynthesis" | "quark_drive", "onModifyDefense") => {
+            match (paradox_boost(active), ctx.category) {
+                (Some("def"), Some(MoveCategory::Physical)) | (Some("spd"), Some(MoveCategory::Special)) => value * 1.3,
+                _ => value,
             }
-            value
+        }
+        ("protosynthesis" | "quark_drive", "onModifySpeed") => {
+            if paradox_boost(active) == Some("spe") { value * 1.5 } else { value }
         }
         ("swift_swim", "onModifySpeed") => if ctx.weather == Some("rain") { value * 2.0 } else { value },
         ("chlorophyll", "onModifySpeed") => if ctx.weather == Some("sun") { value * 2.0 } else { value },
@@ -152,7 +227,7 @@ pub fn run_ability_check_hook(
     let Some(active) = get_active_creature(state, player_id) else {
         return default_value;
     };
-    let Some(ability) = active.ability.as_deref() else {
+    let Some(ability) = active_ability(state, player_id, active) else {
         return default_value;
     };
 
@@ -160,6 +235,19 @@ pub fn run_ability_check_hook(
         ("immunity", "onCheckStatusImmunity") => matches!(ctx.status_id, Some("poison") | Some("toxic")),
         ("insomnia", "onCheckStatusImmunity") => ctx.status_id == Some("sleep"),
         ("own_tempo", "onCheckStatusImmunity") => ctx.status_id == Some("confusion"),
+        ("corrosion", "onIgnoreStatusTypeImmunity") => matches!(ctx.status_id, Some("poison") | Some("toxic")),
+        ("overcoat", "onCheckPowderImmunity") => true,
+        ("sticky_hold", "onCheckItemRemoval") => true,
+        ("sturdy", "onSurviveLethalHit") => true,
+        ("stance_change", "onCheckStanceChange") => true,
+        ("water_veil", "onCheckStatusImmunity") => ctx.status_id == Some("burn"),
+        ("limber", "onCheckStatusImmunity") => ctx.status_id == Some("paralysis"),
+        ("magma_armor", "onCheckStatusImmunity") => ctx.status_id == Some("freeze"),
+        ("vital_spirit", "onCheckStatusImmunity") => ctx.status_id == Some("sleep"),
+        ("comatose", "onCheckStatusImmunity") => matches!(
+            ctx.status_id,
+            Some("burn") | Some("poison") | Some("toxic") | Some("paralysis") | Some("freeze") | Some("sleep")
+        ),
         ("own_tempo", "onImmunity") => ctx.r#type == Some("intimidate"),
         ("clear_body", "onImmunity") => ctx.r#type == Some("intimidate"),
         ("white_smoke", "onImmunity") => ctx.r#type == Some("intimidate"),
@@ -180,6 +268,9 @@ pub fn run_ability_check_hook(
             false
         }
         ("skill_link", "onSkillLink") => true,
+        ("magic_guard", "onCheckResidualImmunity") => true,
+        ("rock_head" | "magic_guard", "onRecoilImmunity") => true,
+        ("overcoat", "onCheckResidualImmunity") => matches!(ctx.status_id, Some("sandstorm") | Some("hail")),
         _ => default_value,
     }
 }
@@ -192,7 +283,7 @@ pub fn modify_stages_with_ability(
     let Some(active) = get_active_creature(state, target_id) else {
         return stages.clone();
     };
-    let Some(ability) = active.ability.as_deref() else {
+    let Some(ability) = active_ability(state, target_id, active) else {
         return stages.clone();
     };
 
@@ -207,22 +298,26 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
     let Some(active) = get_active_creature(state, player_id) else {
         return AbilityHookResult::default();
     };
-    let Some(ability) = active.ability.as_deref() else {
+    let Some(ability) = active_ability(state, player_id, active) else {
         return AbilityHookResult::default();
     };
 
     match (ability, hook) {
+        ("neutralizing_gas", "onSwitchIn") => AbilityHookResult {
+            events: vec![BattleEvent::log_with(LogCategory::Ability, "あたりに かがくへんかガスが 充満した！".to_string())],
+            ..Default::default()
+        },
         ("intimidate", "onSwitchIn") => {
             if active.ability_data.get("intimidateUsed").and_then(|v| v.as_bool()).unwrap_or(false) {
                 return AbilityHookResult::default();
             }
             let next = mark_ability_used(state, player_id, "intimidateUsed");
             let mut events = Vec::new();
-            for other in &next.players {
-                if other.id == player_id {
+            for other_id in active_battler_ids(&next) {
+                if split_battler_id(&other_id).0 == split_battler_id(player_id).0 {
                     continue;
                 }
-                if run_ability_check_hook(&next, &other.id, "onImmunity", AbilityCheckContext {
+                if run_ability_check_hook(&next, &other_id, "onImmunity", AbilityCheckContext {
                     status_id: None,
                     r#type: Some("intimidate"),
                     target_id: None,
@@ -233,7 +328,7 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
                 let mut stages = HashMap::new();
                 stages.insert("atk".to_string(), -1);
                 events.push(BattleEvent::ModifyStage {
-                    target_id: other.id.clone(),
+                    target_id: other_id,
                     stages,
                     clamp: true,
                     fail_if_no_change: false,
@@ -247,7 +342,7 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
             if active.ability_data.get("downloadUsed").and_then(|v| v.as_bool()).unwrap_or(false) {
                 return AbilityHookResult::default();
             }
-            let target_player = state.players.iter().find(|p| p.id != player_id);
+            let target_player = state.players.iter().find(|p| p.id != split_battler_id(player_id).0);
             let Some(target_player) = target_player else { return AbilityHookResult::default(); };
             let Some(target) = get_active_creature(state, &target_player.id) else { return AbilityHookResult::default(); };
             let raise = if target.defense < target.sp_defense { "atk" } else { "spa" };
@@ -276,14 +371,12 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
             next = set_weather(&next, WeatherKind::Sun, Some(5));
             AbilityHookResult {
                 state: Some(next),
-                events: vec![BattleEvent::Log {
-                    message: "日差しが 強く なった！".to_string(),
-                    meta: Map::new(),
-                }],
+                events: vec![BattleEvent::log_with(LogCategory::Ability, "日差しが 強く なった！".to_string())],
                 prevent_action: false,
                 override_action: None,
             }
         }
+        ("protosynthesis" | "quark_drive", "onSwitchIn" | "onTurnStart") => update_paradox_boost(state, player_id, active, ability),
         ("moody", "onTurnEnd") => {
             let stats = ["atk", "def", "spa", "spd", "spe"];
             let up_index = (ctx.rng)().mul_add(stats.len() as f64, 0.0).floor() as usize % stats.len();
@@ -308,6 +401,21 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
                 override_action: None,
             }
         }
+        // バトルスイッチ: 攻撃技を使う前に ブレードフォルムになる (キングシールドは技側で戻す)
+        ("stance_change", "onBeforeAction") => {
+            let Some(move_data) = ctx.move_data else { return AbilityHookResult::default(); };
+            if is_status_move(move_data) {
+                return AbilityHookResult::default();
+            }
+            AbilityHookResult {
+                events: vec![BattleEvent::StanceChange {
+                    target_id: player_id.to_string(),
+                    forme: "blade".to_string(),
+                    meta: meta_with_move_source(Some(&move_data.id), Some(player_id)),
+                }],
+                ..Default::default()
+            }
+        }
         ("libero", "onBeforeAction") => {
             let Some(action) = ctx.action else { return AbilityHookResult::default(); };
             let move_id = action.move_id.as_deref();
@@ -324,8 +432,8 @@ pub fn run_ability_hooks(state: &BattleState, player_id: &str, hook: &str, ctx:
             let Some(move_data) = ctx.move_data else { return AbilityHookResult::default(); };
             let Some(move_type) = move_data.move_type.as_deref() else { return AbilityHookResult::default(); };
             let mut next = state.clone();
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == player_id) {
-                if let Some(creature) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, player_id) {
+                if let Some(creature) = player.team.get_mut(slot) {
                     creature.types = vec![move_type.to_string()];
                     creature.ability_data.insert("liberoUsed".to_string(), Value::Bool(true));
                 }
@@ -355,8 +463,8 @@ pub struct AbilityHookContext<'a> {
 pub fn run_all_ability_hooks(state: &BattleState, hook: &str, ctx: AbilityHookContext<'_>) -> AbilityHookResult {
     let mut working_state = state.clone();
     let mut events = Vec::new();
-    for player in &working_state.players.clone() {
-        let result = run_ability_hooks(&working_state, &player.id, hook, AbilityHookContext {
+    for battler_id in active_battler_ids(&working_state) {
+        let result = run_ability_hooks(&working_state, &battler_id, hook, AbilityHookContext {
             rng: ctx.rng,
             action: ctx.action,
             move_data: ctx.move_data,
@@ -373,13 +481,14 @@ pub fn apply_ability_event_modifiers(
     state: &BattleState,
     events: &[BattleEvent],
     move_db: &std::collections::HashMap<String, MoveData>,
+    rng: &mut dyn FnMut() -> f64,
 ) -> Vec<BattleEvent> {
     let mut output = Vec::new();
     for event in events {
         let mut current_events = vec![event.clone()];
         if let Some(target_id) = event_target_id(event) {
             if let Some(target) = get_active_creature(state, &target_id) {
-                if let Some(ability) = target.ability.as_deref() {
+                if let Some(ability) = active_ability(state, &target_id, target) {
                     if ability == "magic_bounce" {
                         if let Some(replacement) = try_magic_bounce(event, state, move_db) {
                             current_events = replacement;
@@ -397,30 +506,33 @@ pub fn apply_ability_event_modifiers(
         for processed in current_events {
             if let Some(target_id) = event_target_id(&processed) {
                 if let Some(target) = get_active_creature(state, &target_id) {
-                    if target.ability.as_deref() == Some("soundproof") {
-                        let is_sound = event_meta_ref(&processed)
-                            .and_then(|meta| meta_get_bool(meta, "sound"))
-                            .unwrap_or(false);
-                        if is_sound {
-                            output.push(BattleEvent::Log {
-                                message: format!("{}は 音の技を 受けない！", target.name),
-                                meta: Map::new(),
-                            });
+                    if active_ability(state, &target_id, target) == Some("soundproof") {
+                        let meta = event_meta_ref(&processed);
+                        let is_sound = meta.and_then(|meta| meta_get_bool(meta, "sound")).unwrap_or(false);
+                        // 自分の音の技 (いやしのすず等) は防がない
+                        let is_own = meta.and_then(|meta| meta_get_string(meta, "source")).as_deref()
+                            == Some(target_id.as_str());
+                        if is_sound && !is_own {
+                            output.push(BattleEvent::log_with(LogCategory::Ability, format!("{}は 音の技を 受けない！", target.name)));
                             continue;
                         }
                     }
                 }
             }
             output.push(processed.clone());
-            for player in &state.players {
-                if let Some(active) = get_active_creature(state, &player.id) {
-                    if let Some(ability) = active.ability.as_deref() {
+            for battler_id in active_battler_ids(state) {
+                if let Some(active) = get_active_creature(state, &battler_id) {
+                    if let Some(ability) = active_ability(state, &battler_id, active) {
                         let reactions = match ability {
-                            "stamina" => after_stamina(&processed, &player.id),
-                            "cotton_down" => after_cotton_down(state, &processed, &player.id),
-                            "berserk" => after_berserk(state, &processed, &player.id),
-                            "competitive" => after_competitive(&processed, &player.id),
-                            "opportunist" => after_opportunist(&processed, &player.id),
+                            "stamina" => after_stamina(&processed, &battler_id),
+                            "cotton_down" => after_cotton_down(state, &processed, &battler_id),
+                            "berserk" => after_berserk(state, &processed, &battler_id),
+                            "competitive" => after_competitive(&processed, &battler_id),
+                            "opportunist" => after_opportunist(&processed, &battler_id),
+                            "synchronize" => after_synchronize(state, &processed, &battler_id),
+                            "static" => after_contact_status(state, &processed, &battler_id, move_db, rng, "paralysis", "せいでんき"),
+                            "flame_body" => after_contact_status(state, &processed, &battler_id, move_db, rng, "burn", "ほのおのからだ"),
+                            "rough_skin" => after_rough_skin(state, &processed, &battler_id, move_db),
                             _ => Vec::new(),
                         };
                         output.extend(reactions);
@@ -433,36 +545,88 @@ pub fn apply_ability_event_modifiers(
 }
 
 pub fn get_weather(state: &BattleState) -> Option<WeatherKind> {
+    if weather_suppressed(state) {
+        return None;
+    }
     state
         .field
         .global
         .iter()
-        .find_map(|e| match e.id.as_str() {
-            "sun" => Some(WeatherKind::Sun),
-            "rain" => Some(WeatherKind::Rain),
-            _ => None,
-        })
+        .find_map(|e| WeatherKind::from_id(&e.id))
 }
 
 fn set_weather(state: &BattleState, weather: WeatherKind, turns: Option<i32>) -> BattleState {
     let mut next = state.clone();
-    next.field.global.retain(|e| e.id != "sun" && e.id != "rain");
-    let id = match weather {
-        WeatherKind::Sun => "sun",
-        WeatherKind::Rain => "rain",
-    };
+    // 天気は一つだけ
+    next.field.global.retain(|e| WeatherKind::from_id(&e.id).is_none());
     next.field.global.push(crate::core::state::FieldEffect {
-        id: id.to_string(),
+        id: weather.id().to_string(),
         remaining_turns: turns,
         data: HashMap::new(),
     });
     next
 }
 
+/// こだいかっせい・クォークチャージで 高まっている能力 ("atk" など)
+fn paradox_boost(creature: &CreatureState) -> Option<&str> {
+    creature.ability_data.get("paradoxBoost").and_then(|v| v.as_str())
+}
+
+// 晴れ (こだいかっせい) / エレキフィールド (クォークチャージ) の間、一番高い能力を高める。
+// ブーストエナジーを使った場合は 場にいる間ずっと続く
+fn update_paradox_boost(state: &BattleState, player_id: &str, active: &CreatureState, ability: &str) -> AbilityHookResult {
+    let (ability_name, condition) = match ability {
+        "protosynthesis" => ("こだいかっせい", matches!(get_weather(state), Some(WeatherKind::Sun))),
+        _ => ("クォークチャージ", state.field.global.iter().any(|e| e.id == "electric_terrain")),
+    };
+    let booster_used = active.ability_data.get("boosterEnergy").and_then(|v| v.as_bool()).unwrap_or(false);
+    let use_booster = !condition && !booster_used && get_item_id(active).as_deref() == Some("booster_energy");
+    let should_boost = condition || booster_used || use_booster;
+
+    let mut events = Vec::new();
+    let mut next = state.clone();
+    let Some(creature) = get_active_creature_mut(&mut next, player_id) else {
+        return AbilityHookResult::default();
+    };
+    match (should_boost, paradox_boost(active)) {
+        (true, None) => {
+            let stats = [
+                ("atk", "攻撃", active.attack),
+                ("def", "防御", active.defense),
+                ("spa", "特攻", active.sp_attack),
+                ("spd", "特防", active.sp_defense),
+                ("spe", "素早さ", active.speed),
+            ];
+            // 同値なら 攻撃→防御→特攻→特防→素早さ の順で優先
+            let (key, label, _) = stats
+                .iter()
+                .fold(stats[0], |best, stat| if stat.2 > best.2 { *stat } else { best });
+            if use_booster {
+                creature.item = None;
+                creature.statuses.retain(|s| s.id != "item");
+                creature.ability_data.insert("boosterEnergy".to_string(), Value::Bool(true));
+                events.push(BattleEvent::log_with(LogCategory::Ability, format!("{}は ブーストエナジーを 使った！", active.name)));
+            }
+            creature.ability_data.insert("paradoxBoost".to_string(), Value::String(key.to_string()));
+            events.push(BattleEvent::log_with(LogCategory::Ability, format!("{}の {}で {}が 高まった！", active.name, ability_name, label)));
+        }
+        (false, Some(_)) => {
+            creature.ability_data.remove("paradoxBoost");
+            events.push(BattleEvent::log_with(LogCategory::Ability, format!("{}の {}の 効果が 切れた！", active.name, ability_name)));
+        }
+        _ => return AbilityHookResult::default(),
+    }
+    AbilityHookResult {
+        state: Some(next),
+        events,
+        ..Default::default()
+    }
+}
+
 fn mark_ability_used(state: &BattleState, player_id: &str, key: &str) -> BattleState {
     let mut next = state.clone();
-    if let Some(player) = next.players.iter_mut().find(|p| p.id == player_id) {
-        if let Some(creature) = player.team.get_mut(player.active_slot) {
+    if let Some((player, slot)) = find_battler_mut(&mut next.players, player_id) {
+        if let Some(creature) = player.team.get_mut(slot) {
             creature.ability_data.insert(key.to_string(), Value::Bool(true));
         }
     }
@@ -495,7 +659,7 @@ fn copy_fainted_ability(state: &BattleState, player_id: &str, ability_id: &str)
         "quark_drive",
         "protosynthesis",
     ];
-    let Some(player) = state.players.iter().find(|p| p.id == player_id) else {
+    let Some((player, _)) = find_battler(&state.players, player_id) else {
         return AbilityHookResult::default();
     };
     let Some(last) = player.last_fainted_ability.as_deref() else {
@@ -506,8 +670,8 @@ fn copy_fainted_ability(state: &BattleState, player_id: &str, ability_id: &str)
     }
 
     let mut next = state.clone();
-    if let Some(player) = next.players.iter_mut().find(|p| p.id == player_id) {
-        if let Some(creature) = player.team.get_mut(player.active_slot) {
+    if let Some((player, slot)) = find_battler_mut(&mut next.players, player_id) {
+        if let Some(creature) = player.team.get_mut(slot) {
             if creature.ability.as_deref() != Some(ability_id) {
                 return AbilityHookResult::default();
             }
@@ -526,10 +690,7 @@ fn copy_fainted_ability(state: &BattleState, player_id: &str, ability_id: &str)
 
     AbilityHookResult {
         state: Some(next),
-        events: vec![BattleEvent::Log {
-            message: format!("{}は {}を コピーした！", player.name, last),
-            meta: Map::new(),
-        }],
+        events: vec![BattleEvent::log_with(LogCategory::Ability, format!("{}は {}を コピーした！", player.name, last))],
         prevent_action: false,
         override_action: None,
     }
@@ -542,8 +703,14 @@ fn event_target_id(event: &BattleEvent) -> Option<String> {
         | BattleEvent::RemoveStatus { target_id, .. }
         | BattleEvent::ReplaceStatus { target_id, .. }
         | BattleEvent::ModifyStage { target_id, .. }
+        | BattleEvent::SetStages { target_id, .. }
         | BattleEvent::ClearStages { target_id, .. }
         | BattleEvent::ResetStages { target_id, .. }
+        | BattleEvent::SetAbility { target_id, .. }
+        | BattleEvent::Revive { target_id, .. }
+        | BattleEvent::StanceChange { target_id, .. }
+        | BattleEvent::SetType { target_id, .. }
+        | BattleEvent::CureTeamStatus { target_id, .. }
         | BattleEvent::CureAllStatus { target_id, .. } => Some(target_id.clone()),
         _ => None,
     }
@@ -556,8 +723,14 @@ fn event_meta_ref(event: &BattleEvent) -> Option<&Map<String, Value>> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. } => Some(meta),
         _ => None,
     }
@@ -588,10 +761,7 @@ fn try_magic_bounce(
     set_event_meta(&mut bounced_event, "bounced", Value::Bool(true));
 
     Some(vec![
-        BattleEvent::Log {
-            message: format!("{}は 技を 跳ね返した！", target_id),
-            meta: Map::new(),
-        },
+        BattleEvent::log_with(LogCategory::Ability, format!("{}は 技を 跳ね返した！", target_id)),
         bounced_event,
     ])
 }
@@ -618,10 +788,7 @@ fn try_lightning_rod(
             show_event: true,
             meta: Map::new(),
         },
-        BattleEvent::Log {
-            message: format!("{}が 電気の技を 吸い取った！", target_id),
-            meta: Map::new(),
-        },
+        BattleEvent::log_with(LogCategory::Ability, format!("{}が 電気の技を 吸い取った！", target_id)),
     ])
 }
 
@@ -647,14 +814,14 @@ fn after_cotton_down(state: &BattleState, event: &BattleEvent, player_id: &str)
     match event {
         BattleEvent::Damage { target_id, .. } if target_id == player_id => {
             let mut events = Vec::new();
-            for other in &state.players {
-                if other.id == player_id {
+            for other_id in active_battler_ids(state) {
+                if other_id == player_id {
                     continue;
                 }
                 let mut stages = HashMap::new();
                 stages.insert("spe".to_string(), -1);
                 events.push(BattleEvent::ModifyStage {
-                    target_id: other.id.clone(),
+                    target_id: other_id,
                     stages,
                     clamp: true,
                     fail_if_no_change: false,
@@ -717,6 +884,144 @@ fn after_competitive(event: &BattleEvent, player_id: &str) -> Vec<BattleEvent> {
     }
 }
 
+/// シンクロ: 相手に付けられた どく・まひ・やけど を 相手にも 返す。返した状態には `synchronized` を付けて往復させない
+fn after_synchronize(state: &BattleState, event: &BattleEvent, player_id: &str) -> Vec<BattleEvent> {
+    let BattleEvent::ApplyStatus {
+        target_id,
+        status_id,
+        duration,
+        meta,
+        ..
+    } = event
+    else {
+        return Vec::new();
+    };
+    if target_id != player_id
+        || event_meta_flag_raw(meta, "synchronized")
+        || !matches!(status_id.as_str(), "burn" | "poison" | "toxic" | "paralysis")
+    {
+        return Vec::new();
+    }
+    let Some(source_id) = meta_get_string(meta, "source").filter(|source| source != player_id) else {
+        return Vec::new();
+    };
+    let Some(holder) = get_active_creature(state, player_id) else {
+        return Vec::new();
+    };
+    // 自分に状態異常が付かなければ 返さない
+    if holder.statuses.iter().any(|s| s.id == *status_id)
+        || is_status_immune(state, player_id, status_id, Some(&source_id))
+    {
+        return Vec::new();
+    }
+    let mut reflected_meta = Map::new();
+    reflected_meta.insert("source".to_string(), Value::String(player_id.to_string()));
+    reflected_meta.insert("synchronized".to_string(), Value::Bool(true));
+    vec![
+        BattleEvent::log_with(LogCategory::Ability, format!("{}の シンクロ！", holder.name)),
+        BattleEvent::ApplyStatus {
+            target_id: source_id,
+            status_id: status_id.clone(),
+            duration: *duration,
+            stack: false,
+            data: HashMap::new(),
+            meta: reflected_meta,
+        },
+    ]
+}
+
+/// 持ち主に接触技のダメージが入ったとき、攻撃してきた相手のバトラーIDを返す。
+/// まもる・みがわりで防がれる攻撃は 触れていないので数えない
+fn contact_attacker(
+    state: &BattleState,
+    event: &BattleEvent,
+    player_id: &str,
+    move_db: &HashMap<String, MoveData>,
+) -> Option<String> {
+    let BattleEvent::Damage { target_id, amount, meta } = event else {
+        return None;
+    };
+    if target_id != player_id || *amount <= 0 {
+        return None;
+    }
+    let source_id = meta_get_string(meta, "source").filter(|source| source != player_id)?;
+    let move_data = meta_get_string(meta, "moveId").and_then(|move_id| move_db.get(&move_id))?;
+    if !move_data.tags.iter().any(|tag| tag == "contact") {
+        return None;
+    }
+    let holder = get_active_creature(state, player_id)?;
+    let blocked_by = |status_id: &str, bypass: &str| {
+        holder.statuses.iter().any(|s| s.id == status_id) && !event_meta_flag_raw(meta, bypass)
+    };
+    if blocked_by("protect", "bypassProtect") || blocked_by("substitute", "bypassSubstitute") {
+        return None;
+    }
+    get_active_creature(state, &source_id)?;
+    Some(source_id)
+}
+
+/// せいでんき・ほのおのからだ: 接触技を受けると 30% で相手を状態異常にする
+fn after_contact_status(
+    state: &BattleState,
+    event: &BattleEvent,
+    player_id: &str,
+    move_db: &HashMap<String, MoveData>,
+    rng: &mut dyn FnMut() -> f64,
+    status_id: &str,
+    ability_name: &str,
+) -> Vec<BattleEvent> {
+    let Some(attacker_id) = contact_attacker(state, event, player_id, move_db) else {
+        return Vec::new();
+    };
+    let Some(attacker) = get_active_creature(state, &attacker_id) else {
+        return Vec::new();
+    };
+    if has_major_status(attacker) || is_status_immune(state, &attacker_id, status_id, Some(player_id)) {
+        return Vec::new();
+    }
+    if rng() >= 0.3 {
+        return Vec::new();
+    }
+    let holder_name = get_active_creature(state, player_id).map(|c| c.name.clone()).unwrap_or_default();
+    vec![
+        BattleEvent::log_with(LogCategory::Ability, format!("{}の {}！", holder_name, ability_name)),
+        BattleEvent::ApplyStatus {
+            target_id: attacker_id,
+            status_id: status_id.to_string(),
+            duration: None,
+            stack: false,
+            data: HashMap::new(),
+            meta: meta_with_move_source(None, Some(player_id)),
+        },
+    ]
+}
+
+/// さめはだ: 接触技を受けると 相手に最大HPの 1/8 のダメージ
+fn after_rough_skin(
+    state: &BattleState,
+    event: &BattleEvent,
+    player_id: &str,
+    move_db: &HashMap<String, MoveData>,
+) -> Vec<BattleEvent> {
+    let Some(attacker_id) = contact_attacker(state, event, player_id, move_db) else {
+        return Vec::new();
+    };
+    let Some(attacker) = get_active_creature(state, &attacker_id) else {
+        return Vec::new();
+    };
+    let holder_name = get_active_creature(state, player_id).map(|c| c.name.clone()).unwrap_or_default();
+    let mut meta = meta_with_move_source(None, Some(player_id));
+    meta.insert("bypassSubstitute".to_string(), Value::Bool(true));
+    vec![
+        BattleEvent::log_with(LogCategory::Ability, format!("{}の さめはだ！", holder_name)),
+        BattleEvent::Damage {
+            target_id: attacker_id,
+            amount: (attacker.max_hp / 8).max(1),
+            meta,
+        },
+    ]
+}
+
 fn after_opportunist(event: &BattleEvent, player_id: &str) -> Vec<BattleEvent> {
     match event {
         BattleEvent::ModifyStage { target_id, stages, meta, .. } if target_id != player_id => {
@@ -753,10 +1058,18 @@ fn event_meta_move_id(event: &BattleEvent) -> Option<String> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. }
         | BattleEvent::Log { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. } => meta.get("moveId").and_then(|v| v.as_str()).map(|s| s.to_string()),
@@ -771,10 +1084,18 @@ fn event_meta_source(event: &BattleEvent) -> Option<String> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. }
         | BattleEvent::Log { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. } => meta.get("source").and_then(|v| v.as_str()).map(|s| s.to_string()),
@@ -789,10 +1110,18 @@ fn event_meta_flag(event: &BattleEvent, key: &str) -> bool {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. }
         | BattleEvent::Log { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. } => event_meta_flag_raw(meta, key),
@@ -811,6 +1140,7 @@ fn is_reflectable_status_event(event: &BattleEvent) -> bool {
             | BattleEvent::RemoveStatus { .. }
             | BattleEvent::ReplaceStatus { .. }
             | BattleEvent::ModifyStage { .. }
+            | BattleEvent::SetStages { .. }
             | BattleEvent::ClearStages { .. }
             | BattleEvent::ResetStages { .. }
             | BattleEvent::CureAllStatus { .. }
@@ -824,8 +1154,14 @@ fn set_event_target(event: &mut BattleEvent, target_id: &str) {
         | BattleEvent::RemoveStatus { target_id: t, .. }
         | BattleEvent::ReplaceStatus { target_id: t, .. }
         | BattleEvent::ModifyStage { target_id: t, .. }
+        | BattleEvent::SetStages { target_id: t, .. }
         | BattleEvent::ClearStages { target_id: t, .. }
         | BattleEvent::ResetStages { target_id: t, .. }
+        | BattleEvent::SetAbility { target_id: t, .. }
+        | BattleEvent::Revive { target_id: t, .. }
+        | BattleEvent::StanceChange { target_id: t, .. }
+        | BattleEvent::SetType { target_id: t, .. }
+        | BattleEvent::CureTeamStatus { target_id: t, .. }
         | BattleEvent::CureAllStatus { target_id: t, .. } => {
             *t = target_id.to_string();
         }
@@ -840,10 +1176,18 @@ fn set_event_meta(event: &mut BattleEvent, key: &str, value: Value) {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. }
         | BattleEvent::Log { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. } => meta,
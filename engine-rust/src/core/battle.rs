@@ -1,32 +1,146 @@
 use crate::core::abilities::{
-    apply_ability_event_modifiers, get_weather, run_ability_check_hook, run_ability_hooks,
+    apply_ability_event_modifiers, run_ability_check_hook, run_ability_hooks,
     run_ability_value_hook, AbilityCheckContext, AbilityHookContext, AbilityValueContext,
 };
-use crate::core::effects::{apply_effects, apply_events, has_item, EffectContext};
+use crate::core::effects::{apply_effects, apply_events, effective_speed, get_item_id, has_item, EffectContext};
 use crate::core::events::{apply_event, event_type, BattleEvent, EventTransform};
-use crate::core::state::{Action, ActionType, BattleHistory, BattleState, BattleTurn};
-use crate::core::statuses::{run_field_hooks, run_status_hooks, tick_field_effects, tick_statuses, StatusHookContext};
-use crate::core::utils::{get_active_creature, get_active_creature_mut, stage_multiplier};
-use crate::data::moves::{MoveData, MoveDatabase};
+use crate::core::items::{run_item_check_hook, use_bag_item};
+use crate::core::rules::BattleRules;
+use crate::core::state::{
+    Action, ActionType, BattleHistory, BattleState, BattleTurn, LogCategory, MoveOutcome, MoveRecord,
+};
+use crate::core::statuses::{
+    run_field_hooks, run_side_field_hooks, run_status_hooks, tick_field_effects, tick_statuses, StatusHookContext,
+};
+use crate::core::undo::UndoToken;
+use crate::core::utils::{
+    active_battler_ids, battler_id, find_battler, get_active_creature, get_active_creature_mut, is_doubles,
+    is_status_move, split_battler_id,
+};
+use crate::data::moves::{MoveCategory, MoveData, MoveDatabase};
 use crate::data::type_chart::TypeChart;
 use serde_json::{Map, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug)]
 pub struct BattleOptions {
     pub record_history: bool,
+    pub log_retention: LogRetention,
+    /// ダブルバトル: 各プレイヤーが2体を場に出す。2体目の行動・対象は "p1:1" のようなバトラーIDで指定する
+    pub doubles: bool,
+    /// 見せ合い: 対戦の始めから 控えも含めた全員の種族を公開する
+    pub team_preview: bool,
 }
 
 impl Default for BattleOptions {
     fn default() -> Self {
-        Self { record_history: true }
+        Self {
+            record_history: true,
+            log_retention: LogRetention::All,
+            doubles: false,
+            team_preview: false,
+        }
+    }
+}
+
+/// `state.log` に残すログの範囲。`record_history` のターン別ログには影響しない。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogRetention {
+    #[default]
+    All,
+    LastNTurns(usize),
+    None,
+}
+
+const TURN_HEADER_PREFIX: &str = "--- Turn ";
+
+fn retain_log(state: &mut BattleState, retention: LogRetention) {
+    let log = &mut state.log;
+    match retention {
+        LogRetention::All => {}
+        LogRetention::None | LogRetention::LastNTurns(0) => log.clear(),
+        LogRetention::LastNTurns(n) => {
+            let start = log
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, line)| line.starts_with(TURN_HEADER_PREFIX))
+                .nth(n - 1)
+                .map(|(idx, _)| idx);
+            if let Some(start) = start {
+                log.drain(..start);
+            }
+        }
     }
+    state.trim_log_categories();
+}
+
+/// ターン終了時処理の各段階。`BattleEngine::end_turn_phases` の順に発動する。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndTurnPhase {
+    AbilityTurnEnd,
+    Weather,
+    Wish,
+    GrassyTerrain,
+    Items,
+    LeechSeed,
+    StatusDamage,
+    Bind,
+    StatusTurnEnd,
+    FieldTurnEnd,
+}
+
+impl EndTurnPhase {
+    fn hook(self) -> &'static str {
+        match self {
+            EndTurnPhase::AbilityTurnEnd | EndTurnPhase::StatusTurnEnd | EndTurnPhase::FieldTurnEnd => "onTurnEnd",
+            EndTurnPhase::Weather => "onWeatherEnd",
+            EndTurnPhase::Wish => "onWishResolve",
+            EndTurnPhase::GrassyTerrain => "onGrassyTerrainHeal",
+            EndTurnPhase::Items => "onItemEndTurn",
+            EndTurnPhase::LeechSeed => "onLeechSeed",
+            EndTurnPhase::StatusDamage => "onStatusDamage",
+            EndTurnPhase::Bind => "onBindDamage",
+        }
+    }
+
+    fn is_field_phase(self) -> bool {
+        matches!(
+            self,
+            EndTurnPhase::Weather | EndTurnPhase::GrassyTerrain | EndTurnPhase::FieldTurnEnd
+        )
+    }
+}
+
+pub fn default_end_turn_phases() -> Vec<EndTurnPhase> {
+    vec![
+        EndTurnPhase::AbilityTurnEnd,
+        // 1. 天気ダメージ
+        EndTurnPhase::Weather,
+        // 2. ねがいごと
+        EndTurnPhase::Wish,
+        // 3. グラスフィールド回復
+        EndTurnPhase::GrassyTerrain,
+        // 4. 道具効果（たべのこし、くろいヘドロ）
+        EndTurnPhase::Items,
+        // 5. やどりぎのタネ
+        EndTurnPhase::LeechSeed,
+        // 6. 状態異常ダメージ（どく、やけど）
+        EndTurnPhase::StatusDamage,
+        // 7. バインドダメージ
+        EndTurnPhase::Bind,
+        // その他のターン終了時効果（混乱解除など）
+        EndTurnPhase::StatusTurnEnd,
+        EndTurnPhase::FieldTurnEnd,
+    ]
 }
 
 #[derive(Clone, Debug)]
 pub struct BattleEngine {
     pub move_db: MoveDatabase,
     pub type_chart: TypeChart,
+    pub rules: BattleRules,
+    pub end_turn_phases: Vec<EndTurnPhase>,
 }
 
 impl Default for BattleEngine {
@@ -34,13 +148,101 @@ impl Default for BattleEngine {
         Self {
             move_db: MoveDatabase::default(),
             type_chart: TypeChart::new(),
+            rules: BattleRules::default(),
+            end_turn_phases: default_end_turn_phases(),
         }
     }
 }
 
 impl BattleEngine {
     pub fn new(move_db: MoveDatabase, type_chart: TypeChart) -> Self {
-        Self { move_db, type_chart }
+        Self {
+            move_db,
+            type_chart,
+            rules: BattleRules::default(),
+            end_turn_phases: default_end_turn_phases(),
+        }
+    }
+
+    /// 世代ごとのプリセットや独自の `BattleRules` で戦う
+    pub fn with_rules(mut self, rules: BattleRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// さかさバトル: タイプ相性を反転した表で戦う
+    pub fn inverse_battle(mut self) -> Self {
+        self.type_chart = self.type_chart.inverted();
+        self
+    }
+
+    /// `step_battle` の厳密版。同じバトラーからの行動が2つ以上ある・場にいないプレイヤーの行動がある場合はエラーを返す
+    /// (`step_battle` は 2つ目以降をログに残して捨てる)
+    pub fn try_step_battle(
+        &self,
+        state: &BattleState,
+        actions: &[Action],
+        rng: &mut dyn FnMut() -> f64,
+        options: BattleOptions,
+    ) -> Result<BattleState, String> {
+        let mut seen = HashSet::new();
+        for action in actions {
+            if find_battler(&state.players, &action.player_id).is_none() {
+                return Err(format!("{} is not a player in this battle", action.player_id));
+            }
+            if !seen.insert(action.player_id.as_str()) {
+                return Err(format!("{} submitted more than one action", action.player_id));
+            }
+        }
+        let prepared;
+        let state = if options.doubles && state.players.iter().any(|p| p.partner_slot.is_none()) {
+            prepared = with_partner_slots(state);
+            &prepared
+        } else {
+            state
+        };
+        if let Some(result) = self.forced_switch_step(state, actions, rng, &options) {
+            return result;
+        }
+        Ok(self.step_battle(state, actions, rng, options))
+    }
+
+    /// 交代待ちがあり 交代の行動だけが渡されたときの処理。通常のターンとして進めるなら None
+    fn forced_switch_step(
+        &self,
+        state: &BattleState,
+        actions: &[Action],
+        rng: &mut dyn FnMut() -> f64,
+        options: &BattleOptions,
+    ) -> Option<Result<BattleState, String>> {
+        let forced = !actions.is_empty()
+            && !pending_switch_players(state).is_empty()
+            && actions.iter().all(|a| a.action_type == ActionType::Switch);
+        if !forced {
+            return None;
+        }
+        let mut rng_log = Vec::new();
+        let mut rng_recorder = || {
+            let v = rng();
+            rng_log.push(v);
+            v
+        };
+        let result = self.resolve_forced_switches(state, actions, &mut rng_recorder).map(|mut next| {
+            if options.record_history {
+                let turn_log = next.log[state.log.len()..].to_vec();
+                let history = next.history.get_or_insert(BattleHistory { turns: Vec::new() });
+                history.turns.push(BattleTurn {
+                    turn: next.turn,
+                    actions: actions.to_vec(),
+                    log: turn_log,
+                    rng: rng_log,
+                    outcomes: Vec::new(),
+                });
+            }
+            retain_log(&mut next, options.log_retention);
+            next
+        });
+        Some(result)
     }
 
     pub fn step_battle(
@@ -50,17 +252,46 @@ impl BattleEngine {
         rng: &mut dyn FnMut() -> f64,
         options: BattleOptions,
     ) -> BattleState {
+        let prepared;
+        let state = if options.doubles && state.players.iter().any(|p| p.partner_slot.is_none()) {
+            prepared = with_partner_slots(state);
+            &prepared
+        } else {
+            state
+        };
+
+        // 倒れたポケモンの交代だけが渡された場合は ターンを進めずに処理する。
+        // 不正な交代はターンを進めず、理由をログに残して そのままの状態を返す
+        if let Some(result) = self.forced_switch_step(state, actions, rng, &options) {
+            return result.unwrap_or_else(|err| {
+                let mut next = state.clone();
+                next.push_log(LogCategory::System, err);
+                next
+            });
+        }
+
         let mut next = state.clone();
         next.turn += 1;
         let log_start = next.log.len();
         let mut rng_log = Vec::new();
+        let mut outcomes = Vec::new();
         let mut rng_recorder = || {
             let v = rng();
             rng_log.push(v);
             v
         };
 
-        next.log.push(format!("--- Turn {} ---", next.turn));
+        next.push_log(LogCategory::System, format!("{}{} ---", TURN_HEADER_PREFIX, next.turn));
+        for player in next.players.iter_mut() {
+            let active_slots = player.active_slots();
+            for (slot, creature) in player.team.iter_mut().enumerate() {
+                creature.last_damage_dealt = 0;
+                creature.last_damage_taken = 0;
+                if options.team_preview || active_slots.contains(&slot) {
+                    creature.species_revealed = true;
+                }
+            }
+        }
 
         let ability_start = run_all_ability(next.clone(), "onTurnStart", &mut rng_recorder, None, None);
         next = ability_start.state.unwrap_or(next);
@@ -68,16 +299,17 @@ impl BattleEngine {
             next = apply_event(&next, &event);
         }
 
-        for player in next.players.clone() {
+        for battler_id in active_battler_ids(&next) {
             let status_result = run_status_hooks(
                 &next,
-                &player.id,
+                &battler_id,
                 "onTurnStart",
                 StatusHookContext {
                     rng: &mut rng_recorder,
                     action: None,
                     move_data: None,
                     type_chart: &self.type_chart,
+                    rules: &self.rules,
                 },
             );
             next = status_result.state.unwrap_or(next);
@@ -94,6 +326,7 @@ impl BattleEngine {
                 action: None,
                 move_data: None,
                 type_chart: &self.type_chart,
+                rules: &self.rules,
             },
         );
         next = field_start.state.unwrap_or(next);
@@ -114,7 +347,7 @@ impl BattleEngine {
                         .find(|p| p.id == action.player_id)
                         .map(|p| p.name.clone())
                         .unwrap_or_else(|| action.player_id.clone());
-                    next.log.push(format!(
+                    next.push_log(LogCategory::System, format!(
                         "{}の 追加アクションは シングルバトルでは 無視される。",
                         player_name
                     ));
@@ -146,7 +379,7 @@ impl BattleEngine {
                     base_priority,
                     AbilityValueContext {
                         move_data,
-                        category: move_data.and_then(|m| m.category.as_deref()),
+                        category: move_data.and_then(|m| m.category),
                         target: None,
                         weather: None,
                         turn: next.turn,
@@ -157,7 +390,7 @@ impl BattleEngine {
                 OrderedAction {
                     action: action.clone(),
                     priority,
-                    speed: creature_speed(&next, &action.player_id),
+                    speed: creature_speed(&next, &action.player_id, &self.rules),
                     rand: rng_recorder(),
                 }
             })
@@ -179,17 +412,22 @@ impl BattleEngine {
         for ordered_action in ordered {
             let mut action = ordered_action.action;
             let player_id = action.player_id.clone();
-            let attacker_name = next
-                .players
-                .iter()
-                .find(|p| p.id == player_id)
-                .map(|p| p.name.clone())
+            let attacker_name = find_battler(&next.players, &player_id)
+                .map(|(p, _)| p.name.clone())
                 .unwrap_or_else(|| player_id.clone());
 
+            // フリーフォールで連れ去られている間は 交代も含めて何もできない
+            if let Some(active) = get_active_creature(&next, &action.player_id) {
+                if active.statuses.iter().any(|s| s.id == "sky_dropped") {
+                    next.push_log(LogCategory::System, format!("{}は 上空で 身動きが とれない！", active.name));
+                    continue;
+                }
+            }
+
             if action.action_type != ActionType::Switch {
                 if let Some(active) = get_active_creature(&next, &action.player_id) {
                     if active.statuses.iter().any(|s| s.id == "pending_switch") {
-                        next.log.push(format!("{}は 交代しなければならない！", attacker_name));
+                        next.push_log(LogCategory::System, format!("{}は 交代しなければならない！", attacker_name));
                         continue;
                     }
                 }
@@ -197,24 +435,24 @@ impl BattleEngine {
 
             if action.action_type == ActionType::Switch {
                 let Some(slot) = action.slot else {
-                    next.log.push(format!("{} tried to switch without a slot.", attacker_name));
+                    next.push_log(LogCategory::System, format!("{} tried to switch without a slot.", attacker_name));
                     continue;
                 };
-                let Some(player) = next.players.iter().find(|p| p.id == player_id) else {
-                    next.log.push(format!("{} tried to switch but player not found.", attacker_name));
+                let Some((player, _)) = find_battler(&next.players, &player_id) else {
+                    next.push_log(LogCategory::System, format!("{} tried to switch but player not found.", attacker_name));
                     continue;
                 };
                 if slot >= player.team.len() {
-                    next.log.push(format!("{} tried to switch to an invalid slot.", attacker_name));
+                    next.push_log(LogCategory::System, format!("{} tried to switch to an invalid slot.", attacker_name));
                     continue;
                 }
-                if slot == player.active_slot {
-                    next.log.push(format!("{} tried to switch to the active slot.", attacker_name));
+                if player.active_slots().contains(&slot) {
+                    next.push_log(LogCategory::System, format!("{} tried to switch to the active slot.", attacker_name));
                     continue;
                 }
                 if let Some(target) = player.team.get(slot) {
                     if target.hp <= 0 {
-                        next.log.push(format!("{} tried to switch to a fainted Pokémon.", attacker_name));
+                        next.push_log(LogCategory::System, format!("{} tried to switch to a fainted Pokémon.", attacker_name));
                         continue;
                     }
                 }
@@ -222,12 +460,18 @@ impl BattleEngine {
                 if let Some(active) = get_active_creature(&next, &action.player_id) {
                     if active.hp > 0 {
                         let is_ghost = active.types.iter().any(|t| t == "ghost");
-                        if !is_ghost {
-                            let trapper = next.players.iter().find(|p| {
-                                p.id != action.player_id
+                        let has_shed_shell = get_item_id(active).as_deref() == Some("shed_shell");
+                        if !is_ghost && !has_shed_shell {
+                            if active.statuses.iter().any(|s| is_trapping_status(&s.id)) {
+                                next.push_log(LogCategory::System, format!("{}は 交代できなかった！", attacker_name));
+                                continue;
+                            }
+                            let side_id = split_battler_id(&action.player_id).0;
+                            let trapper = active_battler_ids(&next).into_iter().find(|id| {
+                                split_battler_id(id).0 != side_id
                                     && run_ability_check_hook(
                                         &next,
-                                        &p.id,
+                                        id,
                                         "onTrap",
                                         AbilityCheckContext {
                                             status_id: None,
@@ -239,39 +483,27 @@ impl BattleEngine {
                                     )
                             });
                             if trapper.is_some() {
-                                next.log.push(format!("{}は 交代できなかった！", attacker_name));
+                                next.push_log(LogCategory::System, format!("{}は 交代できなかった！", attacker_name));
                                 continue;
                             }
                         }
                     }
                 }
 
-                next = apply_event(
-                    &next,
-                    &BattleEvent::Switch {
-                        player_id: action.player_id.clone(),
-                        slot,
-                    },
-                );
-
-                let switch_result = run_ability_hooks(
-                    &next,
-                    &action.player_id,
-                    "onSwitchIn",
-                    AbilityHookContext {
-                        rng: &mut rng_recorder,
-                        action: None,
-                        move_data: None,
-                    },
-                );
-                next = switch_result.state.unwrap_or(next);
-                for event in switch_result.events {
-                    next = apply_event(&next, &event);
-                }
+                next = switch_in(next, &action.player_id, slot, &mut rng_recorder, &self.type_chart, &self.rules);
                 continue;
             }
 
             if action.action_type == ActionType::UseItem {
+                // バッグの道具: move_id に道具ID、slot に使う相手の手持ち位置
+                if let Some(item_id) = action.move_id.clone() {
+                    let slot = action.slot.or_else(|| find_battler(&next.players, &player_id).map(|(_, slot)| slot));
+                    next.push_log(LogCategory::System, format!("{}は {}を 使った！", attacker_name, item_id));
+                    if !slot.is_some_and(|slot| use_bag_item(&mut next, &player_id, &item_id, slot)) {
+                        next.push_log(LogCategory::System, "しかし 効果が なかった！".to_string());
+                    }
+                    continue;
+                }
                 let can_use = run_ability_check_hook(
                     &next,
                     &action.player_id,
@@ -285,41 +517,36 @@ impl BattleEngine {
                     true,
                 );
                 if !can_use {
-                    next.log.push(format!("{}は 道具を使えない！", attacker_name));
+                    next.push_log(LogCategory::System, format!("{}は 道具を使えない！", attacker_name));
                     continue;
                 }
                 let Some(active) = get_active_creature(&next, &action.player_id) else {
                     continue;
                 };
                 if !has_item(active) {
-                    next.log.push(format!("{}は 使う道具を 持っていない！", attacker_name));
+                    next.push_log(LogCategory::System, format!("{}は 使う道具を 持っていない！", attacker_name));
                     continue;
                 }
-                next.log.push(format!("{}は 道具を使った！", attacker_name));
+                next.push_log(LogCategory::System, format!("{}は 道具を使った！", attacker_name));
                 continue;
             }
 
             let active = get_active_creature(&next, &player_id);
             if active.is_none() || active.unwrap().hp <= 0 {
-                next.log.push(format!("{} cannot act.", attacker_name));
+                next.push_log(LogCategory::System, format!("{} cannot act.", attacker_name));
                 continue;
             }
 
-            let target_id = action.target_id.clone().or_else(|| {
-                next.players
-                    .iter()
-                    .find(|p| p.id != player_id)
-                    .map(|p| p.id.clone())
-            });
+            let target_id = resolve_action_target(&next, &player_id, action.target_id.as_deref());
             let Some(target_id) = target_id else {
-                next.log.push(format!("No valid target for {}.", attacker_name));
+                next.push_log(LogCategory::System, format!("No valid target for {}.", attacker_name));
                 continue;
             };
 
             let mut move_id = match action.move_id.as_deref() {
                 Some(id) => id.to_string(),
                 None => {
-                    next.log.push(format!("{} has no move selected.", attacker_name));
+                    next.push_log(LogCategory::System, format!("{} has no move selected.", attacker_name));
                     continue;
                 }
             };
@@ -327,7 +554,7 @@ impl BattleEngine {
             let mut move_data = match self.move_db.get(&move_id) {
                 Some(data) => data,
                 None => {
-                    next.log.push(format!("{} tried unknown move {}.", attacker_name, move_id));
+                    next.push_log(LogCategory::System, format!("{} tried unknown move {}.", attacker_name, move_id));
                     continue;
                 }
             };
@@ -349,6 +576,12 @@ impl BattleEngine {
                 next = apply_event(&next, &event);
             }
             if ability_before.prevent_action {
+                next = reset_protect_chain(next, &player_id);
+                outcomes.push(MoveRecord {
+                    player_id: player_id.clone(),
+                    move_id: move_id.clone(),
+                    outcome: MoveOutcome::Prevented,
+                });
                 continue;
             }
             if let Some(override_action) = ability_before.override_action {
@@ -359,12 +592,12 @@ impl BattleEngine {
                             move_id = new_move_id.to_string();
                             move_data = new_move_data;
                         } else {
-                            next.log.push(format!("{} tried unknown move {}.", attacker_name, new_move_id));
+                            next.push_log(LogCategory::System, format!("{} tried unknown move {}.", attacker_name, new_move_id));
                             continue;
                         }
                     }
                 } else {
-                    next.log.push(format!("{} has no move selected.", attacker_name));
+                    next.push_log(LogCategory::System, format!("{} has no move selected.", attacker_name));
                     continue;
                 }
             }
@@ -378,13 +611,21 @@ impl BattleEngine {
                     action: Some(&action),
                     move_data: Some(move_data),
                     type_chart: &self.type_chart,
+                    rules: &self.rules,
                 },
             );
             next = status_before.state.unwrap_or(next);
             for event in status_before.events {
                 next = apply_event(&next, &event);
             }
+            // ひるみ・まひ などで動けなかったターンも まもるの連続成功は途切れる
             if status_before.prevent_action {
+                next = reset_protect_chain(next, &player_id);
+                outcomes.push(MoveRecord {
+                    player_id: player_id.clone(),
+                    move_id: move_id.clone(),
+                    outcome: MoveOutcome::Prevented,
+                });
                 continue;
             }
             if let Some(override_action) = status_before.override_action {
@@ -395,12 +636,12 @@ impl BattleEngine {
                             move_id = new_move_id.to_string();
                             move_data = new_move_data;
                         } else {
-                            next.log.push(format!("{} tried unknown move {}.", attacker_name, new_move_id));
+                            next.push_log(LogCategory::System, format!("{} tried unknown move {}.", attacker_name, new_move_id));
                             continue;
                         }
                     }
                 } else {
-                    next.log.push(format!("{} has no move selected.", attacker_name));
+                    next.push_log(LogCategory::System, format!("{} has no move selected.", attacker_name));
                     continue;
                 }
             }
@@ -413,6 +654,7 @@ impl BattleEngine {
                     action: Some(&action),
                     move_data: Some(move_data),
                     type_chart: &self.type_chart,
+                    rules: &self.rules,
                 },
             );
             next = field_before.state.unwrap_or(next);
@@ -420,29 +662,39 @@ impl BattleEngine {
                 next = apply_event(&next, &event);
             }
 
-            if !move_data.steps.iter().any(|e| e.effect_type == "protect") {
-                if let Some(active) = get_active_creature(&next, &player_id) {
-                    if active.volatile_data.get("protectSuccessCount").is_some() {
-                        let event = BattleEvent::SetVolatile {
-                            target_id: player_id.clone(),
-                            key: "protectSuccessCount".to_string(),
-                            value: Value::Number(0.into()),
-                        };
-                        next = apply_event(&next, &event);
-                    }
-                }
+            if !move_data.steps.iter().any(|e| matches!(e.effect_type.as_str(), "protect" | "endure")) {
+                next = reset_protect_chain(next, &player_id);
             }
 
             if let Some(active) = get_active_creature_mut(&mut next, &player_id) {
                 if !consume_move_pp(active, &move_id, move_data) {
                     let move_name = move_data.name.clone().unwrap_or_else(|| move_id.clone());
-                    next.log.push(format!("{}の {}は PPが 足りない！", attacker_name, move_name));
+                    next.push_log(LogCategory::Move, format!("{}の {}は PPが 足りない！", attacker_name, move_name));
+                    outcomes.push(MoveRecord {
+                        player_id: player_id.clone(),
+                        move_id: move_id.clone(),
+                        outcome: MoveOutcome::Failed("no_pp".to_string()),
+                    });
                     continue;
                 }
+                // メトロノーム(道具): 同じ技の連続使用回数。違う技を出したら 0 に戻る
+                let streak = if active.volatile_data.get("lastMove").and_then(|v| v.as_str()) == Some(move_id.as_str()) {
+                    active.volatile_data.get("metronomeCount").and_then(|v| v.as_u64()).unwrap_or(0) + 1
+                } else {
+                    0
+                };
+                active
+                    .volatile_data
+                    .insert("metronomeCount".to_string(), Value::from(streak));
                 active
                     .volatile_data
                     .insert("lastMove".to_string(), Value::String(move_id.clone()));
+                if !active.revealed_moves.contains(&move_id) {
+                    active.revealed_moves.push(move_id.clone());
+                }
             }
+            let move_name = self.move_db.get(&move_id).and_then(|m| m.name.clone());
+            next = apply_choice_lock(next, &player_id, &move_id, move_name);
 
             let mut effect_ctx = EffectContext {
                 attacker_player_id: action.player_id.clone(),
@@ -451,6 +703,7 @@ impl BattleEngine {
                 rng: &mut rng_recorder,
                 turn: next.turn,
                 type_chart: &self.type_chart,
+                rules: &self.rules,
                 bypass_protect: false,
                 ignore_immunity: false,
                 bypass_substitute: false,
@@ -459,182 +712,186 @@ impl BattleEngine {
                 last_damage: None,
             };
             let move_name = move_data.name.as_deref().unwrap_or(&move_id);
-            next.log.push(format!("{}の {}！", attacker_name, move_name));
+            next.push_log(LogCategory::Move, format!("{}の {}！", attacker_name, move_name));
+
+            if is_powder_immune(&next, move_data, &action.player_id, &target_id) {
+                if let Some(target) = get_active_creature(&next, &target_id) {
+                    next.push_log(LogCategory::Move, format!("{}には 効果が ないようだ……", target.name));
+                }
+                outcomes.push(MoveRecord {
+                    player_id: player_id.clone(),
+                    move_id: move_id.clone(),
+                    outcome: MoveOutcome::NoEffect,
+                });
+                continue;
+            }
 
             let mut events = apply_effects(&next, &move_data.steps, &mut effect_ctx);
 
-            events = apply_ability_event_modifiers(&next, &events, self.move_db.as_map());
+            events = apply_ability_event_modifiers(&next, &events, self.move_db.as_map(), &mut rng_recorder);
 
             let transforms = collect_event_transforms(
                 &next,
                 &mut rng_recorder,
                 &self.type_chart,
+                &self.rules,
+                Some(&action),
+                Some(move_data),
             );
             events = apply_event_transforms(&events, &transforms);
             let turn = next.turn;
             events = expand_random_moves(
                 &mut next,
                 &events,
-                &self.move_db,
+                self,
                 &mut rng_recorder,
                 &action.player_id,
                 &target_id,
                 turn,
-                &self.type_chart,
             );
 
+            attach_move_names(&mut events, &self.move_db);
+
+            outcomes.push(MoveRecord {
+                player_id: player_id.clone(),
+                move_id: move_id.clone(),
+                outcome: move_outcome(&next, &events),
+            });
             next = apply_events(&next, &events);
+            next.field.last_move = Some(move_id.clone());
 
             if is_battle_over(&next) {
                 break;
             }
         }
 
-        let ability_end = run_all_ability(next.clone(), "onTurnEnd", &mut rng_recorder, None, None);
-        next = ability_end.state.unwrap_or(next);
-        for event in ability_end.events {
-            next = apply_event(&next, &event);
+        for phase in &self.end_turn_phases {
+            next = self.run_end_turn_phase(next, *phase, &mut rng_recorder);
         }
 
-        // ターン終了時効果を順序通りに発動
-        // 1. 天気ダメージ
-        let weather_result = run_field_hooks(
-            &next,
-            "onWeatherEnd",
-            StatusHookContext {
-                rng: &mut rng_recorder,
-                action: None,
-                move_data: None,
-                type_chart: &self.type_chart,
-            },
-        );
-        next = weather_result.state.unwrap_or(next);
-        for event in weather_result.events {
-            next = apply_event(&next, &event);
-        }
+        next = tick_statuses(&next);
+        next = tick_field_effects(&next);
 
-        // 2. ねがいごと
-        for player in next.players.clone() {
-            let wish_result = run_status_hooks(
-                &next,
-                &player.id,
-                "onWishResolve",
-                StatusHookContext {
-                    rng: &mut rng_recorder,
-                    action: None,
-                    move_data: None,
-                    type_chart: &self.type_chart,
-                },
-            );
-            next = wish_result.state.unwrap_or(next);
-            for event in wish_result.events {
-                next = apply_event(&next, &event);
-            }
+        if options.record_history {
+            let turn_log = next.log[log_start..].to_vec();
+            let history = next.history.get_or_insert(BattleHistory { turns: Vec::new() });
+            history.turns.push(BattleTurn {
+                turn: next.turn,
+                actions: actions.to_vec(),
+                log: turn_log,
+                rng: rng_log,
+                outcomes,
+            });
         }
+        retain_log(&mut next, options.log_retention);
 
-        // 3. グラスフィールド回復
-        let grassy_result = run_field_hooks(
-            &next,
-            "onGrassyTerrainHeal",
-            StatusHookContext {
-                rng: &mut rng_recorder,
-                action: None,
-                move_data: None,
-                type_chart: &self.type_chart,
-            },
-        );
-        next = grassy_result.state.unwrap_or(next);
-        for event in grassy_result.events {
-            next = apply_event(&next, &event);
-        }
+        next
+    }
 
-        // 4. 道具効果（たべのこし、くろいヘドロ）
-        for player in next.players.clone() {
-            let item_result = run_status_hooks(
-                &next,
-                &player.id,
-                "onItemEndTurn",
-                StatusHookContext {
-                    rng: &mut rng_recorder,
-                    action: None,
-                    move_data: None,
-                    type_chart: &self.type_chart,
-                },
-            );
-            next = item_result.state.unwrap_or(next);
-            for event in item_result.events {
-                next = apply_event(&next, &event);
-            }
+    /// 倒れたポケモンの後続を繰り出す。ターンは進めず、ターン終了時の処理も行わない。
+    /// 交代が必要なプレイヤーの `Switch` が欠けている・不正な場合はエラーを返す。
+    pub fn resolve_forced_switches(
+        &self,
+        state: &BattleState,
+        actions: &[Action],
+        rng: &mut dyn FnMut() -> f64,
+    ) -> Result<BattleState, String> {
+        let pending = pending_switch_players(state);
+        // 交代待ちでないプレイヤーの交代は この場では受け付けない
+        if let Some(action) = actions.iter().find(|a| !pending.contains(&a.player_id)) {
+            return Err(format!("{} has no fainted creature to replace", action.player_id));
         }
-
-        // 5. やどりぎのタネ
-        for player in next.players.clone() {
-            let leech_result = run_status_hooks(
-                &next,
-                &player.id,
-                "onLeechSeed",
-                StatusHookContext {
-                    rng: &mut rng_recorder,
-                    action: None,
-                    move_data: None,
-                    type_chart: &self.type_chart,
-                },
-            );
-            next = leech_result.state.unwrap_or(next);
-            for event in leech_result.events {
-                next = apply_event(&next, &event);
+        let mut next = state.clone();
+        for player_id in pending {
+            let slot = actions
+                .iter()
+                .find(|a| a.player_id == player_id && a.action_type == ActionType::Switch)
+                .and_then(|a| a.slot)
+                .ok_or_else(|| format!("{} must switch out a fainted creature", player_id))?;
+            let (player, _) = find_battler(&next.players, &player_id).unwrap();
+            match player.team.get(slot) {
+                Some(incoming) if !player.active_slots().contains(&slot) && incoming.hp > 0 => {}
+                _ => return Err(format!("{} cannot switch to slot {}", player_id, slot)),
             }
+            next = switch_in(next, &player_id, slot, rng, &self.type_chart, &self.rules);
         }
+        Ok(next)
+    }
 
-        // 6. 状態異常ダメージ（どく、やけど）
-        for player in next.players.clone() {
-            let status_result = run_status_hooks(
-                &next,
-                &player.id,
-                "onStatusDamage",
-                StatusHookContext {
-                    rng: &mut rng_recorder,
-                    action: None,
-                    move_data: None,
-                    type_chart: &self.type_chart,
-                },
-            );
-            next = status_result.state.unwrap_or(next);
-            for event in status_result.events {
+    pub fn legal_actions(&self, state: &BattleState, player_id: &str) -> Vec<Action> {
+        legal_actions(state, player_id, &self.move_db)
+    }
+
+    /// Steps `state` in place and returns a token that `undo` can use to roll the turn back.
+    /// The accumulated log is moved aside instead of cloned, and the token takes the pre-turn
+    /// field and teams by value.
+    pub fn apply_turn(
+        &self,
+        state: &mut BattleState,
+        actions: &[Action],
+        rng: &mut dyn FnMut() -> f64,
+        options: BattleOptions,
+    ) -> UndoToken {
+        let retention = options.log_retention;
+        let mut log = std::mem::take(&mut state.log);
+        let mut log_categories = std::mem::take(&mut state.log_categories);
+        let (log_len, log_categories_len) = (log.len(), log_categories.len());
+        let mut next = self.step_battle(state, actions, rng, options);
+        log.append(&mut next.log);
+        log_categories.append(&mut next.log_categories);
+        next.log = log;
+        next.log_categories = log_categories;
+        retain_log(&mut next, retention);
+        // The pre-turn state is not needed any more, so it becomes the token without a second copy.
+        std::mem::replace(state, next).into_undo_token(log_len, log_categories_len)
+    }
+
+    fn run_end_turn_phase(
+        &self,
+        mut next: BattleState,
+        phase: EndTurnPhase,
+        rng: &mut dyn FnMut() -> f64,
+    ) -> BattleState {
+        if phase == EndTurnPhase::AbilityTurnEnd {
+            let result = run_all_ability(next.clone(), "onTurnEnd", rng, None, None);
+            next = result.state.unwrap_or(next);
+            for event in result.events {
                 next = apply_event(&next, &event);
             }
+            return next;
         }
 
-        // 7. バインドダメージ
-        for player in next.players.clone() {
-            let bind_result = run_status_hooks(
+        let hook = phase.hook();
+        if phase.is_field_phase() {
+            let result = run_field_hooks(
                 &next,
-                &player.id,
-                "onBindDamage",
+                hook,
                 StatusHookContext {
-                    rng: &mut rng_recorder,
+                    rng,
                     action: None,
                     move_data: None,
                     type_chart: &self.type_chart,
+                    rules: &self.rules,
                 },
             );
-            next = bind_result.state.unwrap_or(next);
-            for event in bind_result.events {
+            next = result.state.unwrap_or(next);
+            for event in result.events {
                 next = apply_event(&next, &event);
             }
+            return next;
         }
 
-        // その他のターン終了時効果（混乱解除など）
-        for player in next.players.clone() {
+        for player_id in players_by_speed(&next, &self.rules) {
             let result = run_status_hooks(
                 &next,
-                &player.id,
-                "onTurnEnd",
+                &player_id,
+                hook,
                 StatusHookContext {
-                    rng: &mut rng_recorder,
+                    rng,
                     action: None,
                     move_data: None,
                     type_chart: &self.type_chart,
+                    rules: &self.rules,
                 },
             );
             next = result.state.unwrap_or(next);
@@ -642,38 +899,49 @@ impl BattleEngine {
                 next = apply_event(&next, &event);
             }
         }
+        next
+    }
+}
 
-        let field_end = run_field_hooks(
-            &next,
-            "onTurnEnd",
-            StatusHookContext {
-                rng: &mut rng_recorder,
-                action: None,
-                move_data: None,
-                type_chart: &self.type_chart,
-            },
-        );
-        next = field_end.state.unwrap_or(next);
-        for event in field_end.events {
-            next = apply_event(&next, &event);
-        }
-
-        next = tick_statuses(&next);
-        next = tick_field_effects(&next);
-
-        if options.record_history {
-            let turn_log = next.log[log_start..].to_vec();
-            let history = next.history.get_or_insert(BattleHistory { turns: Vec::new() });
-            history.turns.push(BattleTurn {
-                turn: next.turn,
-                actions: actions.to_vec(),
-                log: turn_log,
-                rng: rng_log,
-            });
-        }
+/// こだわり系の道具: 最初に出した技に `choice_lock` で固定する。
+/// アンコールやあばれる等の `lock_move` とは別物で、引っ込むか道具を失えば外れる
+fn apply_choice_lock(state: BattleState, player_id: &str, move_id: &str, move_name: Option<String>) -> BattleState {
+    let should_lock = get_active_creature(&state, player_id).is_some_and(|active| {
+        get_item_id(active).is_some_and(|item| item.starts_with("choice_"))
+            && !active.statuses.iter().any(|s| s.id == "choice_lock")
+    });
+    if !should_lock {
+        return state;
+    }
+    let mut data = HashMap::new();
+    data.insert("moveId".to_string(), Value::String(move_id.to_string()));
+    if let Some(name) = move_name {
+        data.insert("moveName".to_string(), Value::String(name));
+    }
+    let event = BattleEvent::ApplyStatus {
+        target_id: player_id.to_string(),
+        status_id: "choice_lock".to_string(),
+        duration: None,
+        stack: false,
+        data,
+        meta: Map::new(),
+    };
+    apply_event(&state, &event)
+}
 
-        next
+/// まもる系の連続成功回数を 0 に戻す
+fn reset_protect_chain(state: BattleState, player_id: &str) -> BattleState {
+    let counted = get_active_creature(&state, player_id)
+        .is_some_and(|active| active.volatile_data.contains_key("protectSuccessCount"));
+    if !counted {
+        return state;
     }
+    let event = BattleEvent::SetVolatile {
+        target_id: player_id.to_string(),
+        key: "protectSuccessCount".to_string(),
+        value: Value::Number(0.into()),
+    };
+    apply_event(&state, &event)
 }
 
 #[derive(Clone, Debug)]
@@ -693,6 +961,261 @@ pub fn step_battle(
     BattleEngine::default().step_battle(state, actions, rng, options)
 }
 
+/// 決着時の結果。`winner` が None なら引き分け
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BattleResult {
+    pub winner: Option<String>,
+    pub turn: u32,
+}
+
+pub type BattleEndCallback<'a> = Box<dyn FnMut(&BattleState, &BattleResult) + 'a>;
+
+/// ターンを進めながら状態を保持するランナー。決着したターンに一度だけ `on_battle_end` を呼ぶ
+pub struct BattleRunner<'a> {
+    pub engine: &'a BattleEngine,
+    pub state: BattleState,
+    pub options: BattleOptions,
+    pub on_battle_end: Option<BattleEndCallback<'a>>,
+    finished: bool,
+}
+
+impl<'a> BattleRunner<'a> {
+    pub fn new(engine: &'a BattleEngine, state: BattleState) -> Self {
+        let finished = is_battle_over(&state);
+        Self {
+            engine,
+            state,
+            options: BattleOptions::default(),
+            on_battle_end: None,
+            finished,
+        }
+    }
+
+    pub fn on_battle_end(mut self, callback: impl FnMut(&BattleState, &BattleResult) + 'a) -> Self {
+        self.on_battle_end = Some(Box::new(callback));
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// 1ターン進める。決着後は何もしない
+    pub fn step(&mut self, actions: &[Action], rng: &mut dyn FnMut() -> f64) -> &BattleState {
+        if self.finished {
+            return &self.state;
+        }
+        self.state = self.engine.step_battle(&self.state, actions, rng, self.options.clone());
+        if is_battle_over(&self.state) {
+            self.finished = true;
+            let result = BattleResult {
+                winner: determine_winner(&self.state, &self.engine.rules),
+                turn: self.state.turn,
+            };
+            if let Some(callback) = self.on_battle_end.as_mut() {
+                callback(&self.state, &result);
+            }
+        }
+        &self.state
+    }
+}
+
+/// `player_id` がこのターンに選べる行動。倒れていれば交代のみ。
+/// PP の切れた技、ちょうはつ中の変化技、かなしばりで封じられた技は含めない。こだわりで固定されていればその技だけ。
+/// 技が一つも選べなければ交代だけを返す
+pub fn legal_actions(state: &BattleState, player_id: &str, move_db: &MoveDatabase) -> Vec<Action> {
+    let Some((player, _)) = find_battler(&state.players, player_id) else {
+        return Vec::new();
+    };
+    let active_slots = player.active_slots();
+    let switch_actions: Vec<Action> = player
+        .team
+        .iter()
+        .enumerate()
+        .filter(|(idx, c)| !active_slots.contains(idx) && c.hp > 0)
+        .map(|(idx, _)| Action {
+            player_id: player_id.to_string(),
+            action_type: ActionType::Switch,
+            move_id: None,
+            target_id: None,
+            slot: Some(idx),
+            priority: None,
+        })
+        .collect();
+
+    let Some(active) = get_active_creature(state, player_id) else {
+        return switch_actions;
+    };
+    if active.hp <= 0 || active.statuses.iter().any(|s| s.id == "pending_switch") {
+        return switch_actions;
+    }
+    let taunted = active.statuses.iter().any(|s| s.id == "taunt");
+    let disabled_move = active
+        .statuses
+        .iter()
+        .find(|s| s.id == "disable_move")
+        .and_then(|s| s.data.get("moveId"))
+        .and_then(|v| v.as_str());
+    // 道具を失った固定は次の行動で外れるので、こだわり道具を持っている間だけ絞る
+    let choice_locked_move = active
+        .statuses
+        .iter()
+        .find(|s| s.id == "choice_lock")
+        .filter(|_| get_item_id(active).is_some_and(|item| item.starts_with("choice_")))
+        .and_then(|s| s.data.get("moveId"))
+        .and_then(|v| v.as_str());
+    let target_id = resolve_action_target(state, player_id, None);
+    let mut actions: Vec<Action> = active
+        .moves
+        .iter()
+        .filter(|move_id| {
+            let Some(move_data) = move_db.get(move_id) else {
+                return false;
+            };
+            let has_pp = match move_data.pp {
+                None => true,
+                Some(pp) => active.move_pp.get(*move_id).copied().unwrap_or(pp) > 0,
+            };
+            has_pp
+                && !(taunted && is_status_move(move_data))
+                && disabled_move != Some(move_id.as_str())
+                && choice_locked_move.is_none_or(|locked| locked == move_id.as_str())
+        })
+        .map(|move_id| Action {
+            player_id: player_id.to_string(),
+            action_type: ActionType::Move,
+            move_id: Some(move_id.clone()),
+            target_id: target_id.clone(),
+            slot: None,
+            priority: None,
+        })
+        .collect();
+    if actions.is_empty() {
+        return switch_actions;
+    }
+    actions.extend(switch_actions);
+    actions
+}
+
+/// 場のポケモンが倒れていて、控えから後続を出さなければならないプレイヤー。
+/// ダブルバトルでは倒れた位置ごとのバトラーIDを、控えが足りる数だけ返す
+pub fn pending_switch_players(state: &BattleState) -> Vec<String> {
+    let mut pending = Vec::new();
+    for player in &state.players {
+        let active_slots = player.active_slots();
+        let mut replacements = player
+            .team
+            .iter()
+            .enumerate()
+            .filter(|(idx, c)| !active_slots.contains(idx) && c.hp > 0)
+            .count();
+        for (position, slot) in active_slots.iter().enumerate() {
+            let fainted = player
+                .team
+                .get(*slot)
+                .is_some_and(|c| c.hp <= 0 || c.statuses.iter().any(|s| s.id == "pending_switch"));
+            if fainted && replacements > 0 {
+                replacements -= 1;
+                pending.push(battler_id(&player.id, position));
+            }
+        }
+    }
+    pending
+}
+
+/// ダブルバトル用に、2体目の位置が空いているプレイヤーへ控えの先頭を割り当てる
+fn with_partner_slots(state: &BattleState) -> BattleState {
+    let mut next = state.clone();
+    for player in next.players.iter_mut().filter(|p| p.partner_slot.is_none()) {
+        player.partner_slot = player
+            .team
+            .iter()
+            .enumerate()
+            .position(|(idx, c)| idx != player.active_slot && c.hp > 0);
+    }
+    next
+}
+
+/// 技の対象を決める。指定がなければ相手の場の先頭。
+/// ダブルバトルで倒れた相手を指定していた場合は もう1体の相手へ向け直す
+fn resolve_action_target(state: &BattleState, attacker_id: &str, requested: Option<&str>) -> Option<String> {
+    let side_id = split_battler_id(attacker_id).0;
+    let is_alive = |id: &String| get_active_creature(state, id).is_some_and(|c| c.hp > 0);
+    let foes: Vec<String> = active_battler_ids(state)
+        .into_iter()
+        .filter(|id| split_battler_id(id).0 != side_id)
+        .collect();
+    match requested {
+        Some(target) if is_doubles(state) && split_battler_id(target).0 != side_id && !is_alive(&target.to_string()) => {
+            foes.into_iter().find(is_alive).or_else(|| Some(target.to_string()))
+        }
+        Some(target) => Some(target.to_string()),
+        None => foes.iter().find(|id| is_alive(id)).or(foes.first()).cloned(),
+    }
+}
+
+fn switch_in(
+    mut next: BattleState,
+    player_id: &str,
+    slot: usize,
+    rng: &mut dyn FnMut() -> f64,
+    type_chart: &TypeChart,
+    rules: &BattleRules,
+) -> BattleState {
+    next = apply_event(
+        &next,
+        &BattleEvent::Switch {
+            player_id: player_id.to_string(),
+            slot,
+        },
+    );
+
+    // まきびし・ステルスロックは特性より先に受ける
+    let hazards = run_side_field_hooks(
+        &next,
+        player_id,
+        "onSwitchIn",
+        StatusHookContext {
+            rng,
+            action: None,
+            move_data: None,
+            type_chart,
+            rules,
+        },
+    );
+    for event in hazards.events {
+        next = apply_event(&next, &event);
+    }
+
+    let switch_result = run_ability_hooks(
+        &next,
+        player_id,
+        "onSwitchIn",
+        AbilityHookContext {
+            rng,
+            action: None,
+            move_data: None,
+        },
+    );
+    next = switch_result.state.unwrap_or(next);
+    for event in switch_result.events {
+        next = apply_event(&next, &event);
+    }
+    next
+}
+
+/// 見せ合いで公開する内容。プレイヤーごとに手持ち全員の種族IDを並べる (技や持ち物は含まない)
+pub fn team_preview(state: &BattleState) -> Vec<(String, Vec<String>)> {
+    state
+        .players
+        .iter()
+        .map(|player| {
+            let species = player.team.iter().map(|creature| creature.species_id.clone()).collect();
+            (player.id.clone(), species)
+        })
+        .collect()
+}
+
 pub fn is_battle_over(state: &BattleState) -> bool {
     for player in &state.players {
         let alive = player.team.iter().any(|c| c.hp > 0);
@@ -703,7 +1226,8 @@ pub fn is_battle_over(state: &BattleState) -> bool {
     false
 }
 
-pub fn determine_winner(state: &BattleState) -> Option<String> {
+/// 全滅した側の負け。同時に全滅したら `rules` の素早さで先に倒れた側の負け
+pub fn determine_winner(state: &BattleState, rules: &BattleRules) -> Option<String> {
     if state.players.is_empty() {
         return None;
     }
@@ -730,8 +1254,8 @@ pub fn determine_winner(state: &BattleState) -> Option<String> {
     // the creature that would be processed first faints first and loses.
     let p1 = &state.players[0];
     let p2 = &state.players[1];
-    let p1_speed = creature_speed(state, &p1.id);
-    let p2_speed = creature_speed(state, &p2.id);
+    let p1_speed = creature_speed(state, &p1.id, rules);
+    let p2_speed = creature_speed(state, &p2.id, rules);
     if p1_speed == p2_speed {
         return None;
     }
@@ -801,44 +1325,108 @@ pub fn determine_timeout_winner(state: &BattleState) -> Option<String> {
     }
 }
 
-fn creature_speed(state: &BattleState, player_id: &str) -> i32 {
-    let creature = get_active_creature(state, player_id);
-    let Some(creature) = creature else {
-        return 0;
-    };
-    let mut speed = creature.speed as f32 * stage_multiplier(creature.stages.spe);
-    let side_tailwind = state
-        .field
-        .sides
-        .get(player_id)
-        .map(|effects| effects.iter().any(|effect| effect.id == "tailwind"))
-        .unwrap_or(false);
-    let global_tailwind = state.field.global.iter().any(|effect| effect.id == "tailwind");
-    if side_tailwind || global_tailwind {
-        speed *= 2.0;
+fn creature_speed(state: &BattleState, player_id: &str, rules: &BattleRules) -> i32 {
+    effective_speed(state, player_id, rules).round() as i32
+}
+
+/// 技の効果イベントから結果を判定する。effects 側が meta の outcome で外れ・効果なし・失敗を、
+/// まもる の置き換えが防いだことを示す。
+fn move_outcome(state: &BattleState, events: &[BattleEvent]) -> MoveOutcome {
+    for event in events {
+        let BattleEvent::Log { meta, .. } = event else {
+            continue;
+        };
+        match crate::core::events::meta_get_string(meta, "outcome").as_deref() {
+            Some("missed") => return MoveOutcome::Missed,
+            Some("noEffect") => return MoveOutcome::NoEffect,
+            Some("blocked") => return MoveOutcome::Blocked,
+            Some("failed") => {
+                let reason = crate::core::events::meta_get_string(meta, "reason").unwrap_or_default();
+                return MoveOutcome::Failed(reason);
+            }
+            _ => {}
+        }
     }
-    if creature.statuses.iter().any(|s| s.id == "paralysis") {
-        speed *= 0.5;
+
+    // 状態を付与するだけの技で、相手がすでにその状態なら失敗扱い
+    let applied: Vec<(&String, &String)> = events
+        .iter()
+        .filter_map(|event| match event {
+            BattleEvent::ApplyStatus { target_id, status_id, .. } => Some((target_id, status_id)),
+            _ => None,
+        })
+        .collect();
+    let status_only = events
+        .iter()
+        .all(|event| matches!(event, BattleEvent::Log { .. } | BattleEvent::ApplyStatus { .. }));
+    let redundant = applied.iter().all(|(target_id, status_id)| {
+        get_active_creature(state, target_id).is_some_and(|c| c.statuses.iter().any(|s| &s.id == *status_id))
+    });
+    if !applied.is_empty() && status_only && redundant {
+        return MoveOutcome::Failed("already_has_status".to_string());
     }
-    let weather = get_weather(state);
-    speed = run_ability_value_hook(
-        state,
-        player_id,
-        "onModifySpeed",
-        speed,
-        AbilityValueContext {
-            move_data: None,
-            category: None,
-            target: None,
-            weather: weather.as_ref().map(|w| match w {
-                crate::core::abilities::WeatherKind::Sun => "sun",
-                crate::core::abilities::WeatherKind::Rain => "rain",
-            }),
-            turn: state.turn,
-            stages: None,
-        },
-    );
-    speed.round() as i32
+    MoveOutcome::Hit
+}
+
+/// ダメージの meta と かなしばりの data に技名を載せる。おんねん・かなしばり のログは これを使う
+fn attach_move_names(events: &mut [BattleEvent], move_db: &MoveDatabase) {
+    let name_of = |move_id: Option<&str>| move_id.and_then(|id| move_db.get(id)).and_then(|m| m.name.clone());
+    for event in events {
+        match event {
+            BattleEvent::Damage { meta, .. } => {
+                if let Some(name) = name_of(meta.get("moveId").and_then(|v| v.as_str())) {
+                    meta.entry("moveName").or_insert(Value::String(name));
+                }
+            }
+            BattleEvent::ApplyStatus { status_id, data, .. } if status_id == "disable_move" => {
+                if let Some(name) = name_of(data.get("moveId").and_then(|v| v.as_str())) {
+                    data.entry("moveName".to_string()).or_insert(Value::String(name));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// くさタイプ・ぼうじん・ぼうじんゴーグルは こな・ほうし技を受けない
+fn is_powder_immune(state: &BattleState, move_data: &MoveData, attacker_id: &str, target_id: &str) -> bool {
+    if attacker_id == target_id || !move_data.tags.iter().any(|t| t == "powder") {
+        return false;
+    }
+    let Some(target) = get_active_creature(state, target_id) else {
+        return false;
+    };
+    target.types.iter().any(|t| t == "grass")
+        || run_ability_check_hook(
+            state,
+            target_id,
+            "onCheckPowderImmunity",
+            AbilityCheckContext {
+                status_id: None,
+                r#type: None,
+                target_id: Some(attacker_id),
+                action: None,
+            },
+            false,
+        )
+        || run_item_check_hook(state, target_id, "onCheckPowderImmunity", false)
+}
+
+fn is_trapping_status(status_id: &str) -> bool {
+    status_id == "bind" || status_id == "trapped"
+}
+
+/// ターン終了時の残留ダメージは素早さの高い順に処理する（同速はプレイヤー順）。
+fn players_by_speed(state: &BattleState, rules: &BattleRules) -> Vec<String> {
+    let mut ordered: Vec<(String, i32)> = active_battler_ids(state)
+        .into_iter()
+        .map(|id| {
+            let speed = creature_speed(state, &id, rules);
+            (id, speed)
+        })
+        .collect();
+    ordered.sort_by_key(|(_, speed)| std::cmp::Reverse(*speed));
+    ordered.into_iter().map(|(id, _)| id).collect()
 }
 
 fn run_all_ability(
@@ -863,18 +1451,22 @@ fn collect_event_transforms(
     state: &BattleState,
     rng: &mut dyn FnMut() -> f64,
     type_chart: &TypeChart,
+    rules: &BattleRules,
+    action: Option<&Action>,
+    move_data: Option<&MoveData>,
 ) -> Vec<EventTransform> {
     let mut transforms = Vec::new();
-    for player in state.players.clone() {
+    for battler_id in active_battler_ids(state) {
         let result = run_status_hooks(
             state,
-            &player.id,
+            &battler_id,
             "onEventTransform",
             StatusHookContext {
                 rng,
-                action: None,
-                move_data: None,
+                action,
+                move_data,
                 type_chart,
+                rules,
             },
         );
         transforms.extend(result.event_transforms);
@@ -887,6 +1479,7 @@ fn collect_event_transforms(
             action: None,
             move_data: None,
             type_chart,
+            rules,
         },
     );
     transforms.extend(field_result.event_transforms);
@@ -972,8 +1565,14 @@ fn event_target_id(event: &BattleEvent) -> Option<String> {
         | BattleEvent::RemoveStatus { target_id, .. }
         | BattleEvent::ReplaceStatus { target_id, .. }
         | BattleEvent::ModifyStage { target_id, .. }
+        | BattleEvent::SetStages { target_id, .. }
         | BattleEvent::ClearStages { target_id, .. }
         | BattleEvent::ResetStages { target_id, .. }
+        | BattleEvent::SetAbility { target_id, .. }
+        | BattleEvent::Revive { target_id, .. }
+        | BattleEvent::StanceChange { target_id, .. }
+        | BattleEvent::SetType { target_id, .. }
+        | BattleEvent::CureTeamStatus { target_id, .. }
         | BattleEvent::CureAllStatus { target_id, .. } => Some(target_id.clone()),
         _ => None,
     }
@@ -987,12 +1586,21 @@ fn event_source_id(event: &BattleEvent) -> Option<String> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SwapSideEffects { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. }
-        | BattleEvent::RandomMove { meta, .. } => crate::core::events::meta_get_string(meta, "source"),
+        | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. } => crate::core::events::meta_get_string(meta, "source"),
         _ => None,
     }
 }
@@ -1005,12 +1613,21 @@ fn event_meta(event: &BattleEvent) -> Option<&Map<String, Value>> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SwapSideEffects { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. }
-        | BattleEvent::RandomMove { meta, .. } => Some(meta),
+        | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. } => Some(meta),
         _ => None,
     }
 }
@@ -1061,24 +1678,15 @@ fn choose_random_move(
                 Vec::new()
             }
         }
-        "physical" => move_db
-            .as_map()
-            .iter()
-            .filter(|(_, m)| m.category.as_deref() == Some("physical"))
-            .map(|(id, _)| id.clone())
-            .collect(),
-        "special" => move_db
-            .as_map()
-            .iter()
-            .filter(|(_, m)| m.category.as_deref() == Some("special"))
-            .map(|(id, _)| id.clone())
-            .collect(),
-        "status" => move_db
-            .as_map()
-            .iter()
-            .filter(|(_, m)| m.category.as_deref() == Some("status"))
-            .map(|(id, _)| id.clone())
-            .collect(),
+        "physical" | "special" | "status" => {
+            let category = pool.parse::<MoveCategory>().ok();
+            move_db
+                .as_map()
+                .iter()
+                .filter(|(_, m)| m.category == category)
+                .map(|(id, _)| id.clone())
+                .collect()
+        }
         _ => move_db.as_map().keys().cloned().collect(),
     };
 
@@ -1111,16 +1719,62 @@ fn choose_random_move(
     filtered.get(idx).cloned()
 }
 
+/// ゆびをふる・オウムがえしなどで呼び出した技の効果を解決する
+fn run_called_move(
+    state: &BattleState,
+    engine: &BattleEngine,
+    rng: &mut dyn FnMut() -> f64,
+    called_move: &MoveData,
+    attacker_id: &str,
+    target_id: &str,
+    turn: u32,
+) -> Vec<BattleEvent> {
+    let mut effect_ctx = EffectContext {
+        attacker_player_id: attacker_id.to_string(),
+        target_player_id: target_id.to_string(),
+        move_data: Some(called_move),
+        rng,
+        turn,
+        type_chart: &engine.type_chart,
+        rules: &engine.rules,
+        bypass_protect: false,
+        ignore_immunity: false,
+        bypass_substitute: false,
+        ignore_substitute: false,
+        is_sound: false,
+        last_damage: None,
+    };
+    let events = apply_effects(state, &called_move.steps, &mut effect_ctx);
+    let events = apply_ability_event_modifiers(state, &events, engine.move_db.as_map(), rng);
+    let action = Action {
+        player_id: attacker_id.to_string(),
+        action_type: ActionType::Move,
+        move_id: Some(called_move.id.clone()),
+        target_id: Some(target_id.to_string()),
+        slot: None,
+        priority: None,
+    };
+    let transforms = collect_event_transforms(
+        state,
+        rng,
+        &engine.type_chart,
+        &engine.rules,
+        Some(&action),
+        Some(called_move),
+    );
+    apply_event_transforms(&events, &transforms)
+}
+
 fn expand_random_moves(
     state: &mut BattleState,
     events: &[BattleEvent],
-    move_db: &MoveDatabase,
+    engine: &BattleEngine,
     rng: &mut dyn FnMut() -> f64,
     attacker_id: &str,
     target_id: &str,
     turn: u32,
-    type_chart: &TypeChart,
 ) -> Vec<BattleEvent> {
+    let move_db = &engine.move_db;
     let mut expanded = Vec::new();
     let attacker_name = get_active_creature(state, attacker_id)
         .map(|c| c.name.clone())
@@ -1163,25 +1817,58 @@ fn expand_random_moves(
                     meta: Map::new(),
                 });
 
-                let mut effect_ctx = EffectContext {
-                    attacker_player_id: attacker_id.to_string(),
-                    target_player_id: target_id.to_string(),
-                    move_data: Some(chosen_move),
-                    rng,
-                    turn,
-                    type_chart,
-                    bypass_protect: false,
-                    ignore_immunity: false,
-                    bypass_substitute: false,
-                    ignore_substitute: false,
-                    is_sound: false,
-                    last_damage: None,
+                expanded.extend(run_called_move(state, engine, rng, chosen_move, attacker_id, target_id, turn));
+            }
+            BattleEvent::CallMove { move_id, .. } => {
+                let Some(called_move) = move_db.get(move_id) else {
+                    continue;
+                };
+                let move_name = called_move.name.as_deref().unwrap_or(move_id);
+                expanded.push(BattleEvent::Log {
+                    message: format!("{}の {}！", attacker_name, move_name),
+                    meta: Map::new(),
+                });
+                expanded.extend(run_called_move(state, engine, rng, called_move, attacker_id, target_id, turn));
+            }
+            BattleEvent::RestorePp {
+                target_id: restore_target,
+                move_id,
+                amount,
+                meta,
+            } => {
+                let Some(creature) = get_active_creature(state, restore_target) else {
+                    continue;
                 };
-                let mut sub_events = apply_effects(state, &chosen_move.steps, &mut effect_ctx);
-                sub_events = apply_ability_event_modifiers(state, &sub_events, move_db.as_map());
-                let transforms = collect_event_transforms(state, rng, type_chart);
-                sub_events = apply_event_transforms(&sub_events, &transforms);
-                expanded.extend(sub_events);
+                let restore_move = move_db.get(move_id);
+                let move_name = restore_move.and_then(|m| m.name.as_deref()).unwrap_or(move_id);
+                let max_pp = restore_move.and_then(|m| m.pp);
+                let current = creature.move_pp.get(move_id).copied();
+                // 一度も使っていない技は PP が満タン
+                let missing = match (max_pp, current) {
+                    (Some(max_pp), Some(current)) => max_pp - current,
+                    _ => 0,
+                };
+                if missing <= 0 {
+                    let mut meta = meta.clone();
+                    meta.insert("outcome".to_string(), Value::String("failed".to_string()));
+                    meta.insert("reason".to_string(), Value::String("pp_full".to_string()));
+                    expanded.push(BattleEvent::Log {
+                        message: format!("{}の {}の PPは 満タンだ！", creature.name, move_name),
+                        meta,
+                    });
+                    continue;
+                }
+                let restored = (*amount).clamp(0, missing);
+                expanded.push(BattleEvent::Log {
+                    message: format!("{}は {}の PPを {}回復した！", creature.name, move_name, restored),
+                    meta: Map::new(),
+                });
+                expanded.push(BattleEvent::RestorePp {
+                    target_id: restore_target.clone(),
+                    move_id: move_id.clone(),
+                    amount: restored,
+                    meta: meta.clone(),
+                });
             }
             _ => expanded.push(event.clone()),
         }
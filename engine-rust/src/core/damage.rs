@@ -0,0 +1,171 @@
+use crate::core::battle::BattleEngine;
+use crate::core::effects::{calc_damage, crit_chance, move_effectiveness, EffectContext};
+use crate::core::state::BattleState;
+use crate::core::utils::{get_active_creature, is_status_move};
+use crate::data::moves::MoveData;
+use serde::Serialize;
+
+fn move_power(move_data: &MoveData) -> i32 {
+    move_data
+        .power
+        .or_else(|| {
+            move_data
+                .steps
+                .iter()
+                .find(|effect| effect.effect_type == "damage")
+                .and_then(|effect| effect.data.get("power"))
+                .and_then(|v| v.as_i64())
+                .map(|v| v as i32)
+        })
+        .unwrap_or(0)
+}
+
+/// Damage of one hit at each of the 16 roll steps (85%..100%).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DamageRange {
+    pub min: i32,
+    pub max: i32,
+    pub rolls: [i32; 16],
+    /// Type multiplier as calc_damage sees it (Levitate, grounding, typeless moves).
+    pub effectiveness: f32,
+}
+
+/// Runs the battle's own damage calculation once per roll step, so abilities, items,
+/// weather and stages apply exactly as they would in a real turn.
+pub fn calc_damage_range(
+    state: &BattleState,
+    attacker_id: &str,
+    target_id: &str,
+    move_id: &str,
+    engine: &BattleEngine,
+) -> DamageRange {
+    calc_damage_range_with_crit(state, attacker_id, target_id, move_id, engine, false)
+}
+
+/// Same as `calc_damage_range`, with every roll a critical hit when `crit` is set.
+pub fn calc_damage_range_with_crit(
+    state: &BattleState,
+    attacker_id: &str,
+    target_id: &str,
+    move_id: &str,
+    engine: &BattleEngine,
+    crit: bool,
+) -> DamageRange {
+    let move_data = engine.move_db.get(move_id);
+    let rolls = move_data
+        .filter(|m| !is_status_move(m))
+        .map(|move_data| roll_damage(state, attacker_id, target_id, move_data, engine, crit))
+        .unwrap_or([0; 16]);
+    let effectiveness = match (move_data, get_active_creature(state, target_id)) {
+        (Some(move_data), Some(target)) => move_effectiveness(&engine.type_chart, move_data, target),
+        _ => 1.0,
+    };
+    DamageRange {
+        min: rolls[0],
+        max: rolls[15],
+        rolls,
+        effectiveness,
+    }
+}
+
+fn effect_context<'a>(
+    state: &BattleState,
+    attacker_id: &str,
+    target_id: &str,
+    move_data: &'a MoveData,
+    engine: &'a BattleEngine,
+    rng: &'a mut dyn FnMut() -> f64,
+) -> EffectContext<'a> {
+    EffectContext {
+        attacker_player_id: attacker_id.to_string(),
+        target_player_id: target_id.to_string(),
+        move_data: Some(move_data),
+        rng,
+        turn: state.turn,
+        type_chart: &engine.type_chart,
+        rules: &engine.rules,
+        bypass_protect: false,
+        ignore_immunity: move_data.steps.iter().any(|e| e.effect_type == "ignore_immunity"),
+        bypass_substitute: false,
+        ignore_substitute: false,
+        is_sound: false,
+        last_damage: None,
+    }
+}
+
+/// One hit at each of the 16 roll steps, with the crit outcome fixed to `crit`.
+fn roll_damage(
+    state: &BattleState,
+    attacker_id: &str,
+    target_id: &str,
+    move_data: &MoveData,
+    engine: &BattleEngine,
+    crit: bool,
+) -> [i32; 16] {
+    let power = move_power(move_data);
+    let mut rolls = [0; 16];
+    for (idx, slot) in rolls.iter_mut().enumerate() {
+        // With the crit outcome fixed, calc_damage draws the rng once: for the roll step.
+        let mut rng = move || (idx as f64 + 0.5) / 16.0;
+        let mut ctx = effect_context(state, attacker_id, target_id, move_data, engine, &mut rng);
+        *slot = calc_damage(power, state, attacker_id, target_id, &mut ctx, Some(crit)).0;
+    }
+    rolls
+}
+
+/// Probability that `n_hits` uses of the move KO the target from its current HP.
+/// Every hit rolls its damage and crit independently, through the same calculation as `calc_damage_range`.
+pub fn ko_probability(
+    state: &BattleState,
+    attacker_id: &str,
+    target_id: &str,
+    move_id: &str,
+    engine: &BattleEngine,
+    n_hits: usize,
+) -> f32 {
+    let Some(target) = get_active_creature(state, target_id) else {
+        return 0.0;
+    };
+    let hp = target.hp.max(0) as usize;
+    if hp == 0 {
+        return 1.0;
+    }
+    let Some(move_data) = engine.move_db.get(move_id).filter(|m| !is_status_move(m)) else {
+        return 0.0;
+    };
+
+    let mut rng = || 0.0;
+    let crit = crit_chance(
+        state,
+        attacker_id,
+        target,
+        &effect_context(state, attacker_id, target_id, move_data, engine, &mut rng),
+    );
+    let mut outcomes: Vec<(usize, f64)> = Vec::with_capacity(32);
+    for (is_crit, weight) in [(false, 1.0 - crit), (true, crit)] {
+        if weight <= 0.0 {
+            continue;
+        }
+        for amount in roll_damage(state, attacker_id, target_id, move_data, engine, is_crit) {
+            outcomes.push((amount.max(0) as usize, weight / 16.0));
+        }
+    }
+
+    // dist[d] = probability that total damage so far is d; damage at or past `hp` is pooled.
+    let mut dist = vec![0.0f64; hp + 1];
+    dist[0] = 1.0;
+    for _ in 0..n_hits {
+        let mut next = vec![0.0f64; hp + 1];
+        next[hp] = dist[hp];
+        for (dealt, p) in dist.iter().enumerate().take(hp) {
+            if *p == 0.0 {
+                continue;
+            }
+            for (amount, weight) in &outcomes {
+                next[(dealt + amount).min(hp)] += p * weight;
+            }
+        }
+        dist = next;
+    }
+    dist[hp] as f32
+}
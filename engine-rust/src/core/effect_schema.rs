@@ -0,0 +1,189 @@
+use serde_json::{json, Map, Value};
+
+/// 効果の `data` に書けるフィールドと、その値の種類
+pub struct EffectSpec {
+    pub effect_type: &'static str,
+    pub fields: &'static [(&'static str, &'static str)],
+}
+
+const TARGET: (&str, &str) = ("target", "target");
+const DELAY_FIELDS: &[(&str, &str)] = &[
+    ("afterTurns", "number"),
+    ("turns", "number"),
+    ("then", "effects"),
+    ("steps", "effects"),
+    ("timing", "string"),
+    TARGET,
+];
+
+/// `apply_effects` が扱う効果の一覧。分岐や読むフィールドを足したらここにも足す (載っていないとデバッグビルドで止まる)
+pub const EFFECT_SPECS: &[EffectSpec] = &[
+    EffectSpec { effect_type: "protect", fields: &[("stanceForme", "string"), ("onBlockReaction", "string")] },
+    EffectSpec { effect_type: "endure", fields: &[] },
+    EffectSpec {
+        effect_type: "damage",
+        fields: &[
+            ("power", "number"),
+            ("accuracy", "number"),
+            ("groundsTarget", "boolean"),
+            ("leaveAtOneHp", "boolean"),
+            ("skipAccuracyCheck", "boolean"),
+            ("spreadHit", "boolean"),
+            ("useDefensiveStat", "boolean"),
+            TARGET,
+        ],
+    },
+    EffectSpec {
+        effect_type: "speed_based_damage",
+        fields: &[("basePower", "number"), ("thresholds", "array"), ("accuracy", "number"), TARGET],
+    },
+    EffectSpec {
+        effect_type: "sky_drop",
+        fields: &[("power", "number"), ("accuracy", "number"), ("maxWeight", "number"), TARGET],
+    },
+    EffectSpec {
+        effect_type: "apply_status",
+        fields: &[
+            ("statusId", "string"),
+            ("duration", "number"),
+            ("chance", "number"),
+            ("stack", "boolean"),
+            ("data", "object"),
+            ("sourceId", "string"),
+            TARGET,
+        ],
+    },
+    EffectSpec { effect_type: "remove_status", fields: &[("statusId", "string"), TARGET] },
+    EffectSpec {
+        effect_type: "replace_status",
+        fields: &[("from", "string"), ("to", "string"), ("duration", "number"), ("data", "object"), TARGET],
+    },
+    EffectSpec {
+        effect_type: "modify_stage",
+        fields: &[
+            ("stages", "object"),
+            ("clamp", "boolean"),
+            ("fail_if_no_change", "boolean"),
+            ("show_event", "boolean"),
+            TARGET,
+        ],
+    },
+    EffectSpec { effect_type: "copy_stages", fields: &[TARGET] },
+    EffectSpec { effect_type: "swap_stages", fields: &[("stats", "array"), TARGET] },
+    EffectSpec { effect_type: "invert_stages", fields: &[TARGET] },
+    EffectSpec { effect_type: "clear_stages", fields: &[("show_event", "boolean"), TARGET] },
+    EffectSpec { effect_type: "reset_stages", fields: &[("show_event", "boolean"), TARGET] },
+    EffectSpec { effect_type: "disable_move", fields: &[("moveId", "string"), ("duration", "number"), TARGET] },
+    EffectSpec {
+        effect_type: "damage_ratio",
+        fields: &[("ratioMaxHp", "number"), ("ratioCurrentHp", "number"), TARGET],
+    },
+    EffectSpec {
+        effect_type: "heal",
+        fields: &[("ratioMaxHp", "number"), ("fail_if_full", "boolean"), TARGET],
+    },
+    EffectSpec { effect_type: "drain", fields: &[("ratio", "number")] },
+    EffectSpec { effect_type: "recoil", fields: &[("ratioDamage", "number"), ("ratioMaxHp", "number")] },
+    EffectSpec { effect_type: "struggle_recoil", fields: &[] },
+    EffectSpec { effect_type: "charge", fields: &[] },
+    EffectSpec { effect_type: "helping_hand", fields: &[TARGET] },
+    EffectSpec { effect_type: "restore_pp", fields: &[("moveId", "string"), ("amount", "number"), TARGET] },
+    EffectSpec { effect_type: "delay", fields: DELAY_FIELDS },
+    EffectSpec { effect_type: "wait", fields: DELAY_FIELDS },
+    EffectSpec {
+        effect_type: "over_time",
+        fields: &[("duration", "number"), ("steps", "effects"), ("timing", "string"), TARGET],
+    },
+    EffectSpec { effect_type: "chance", fields: &[("p", "number"), ("then", "effects"), ("else", "effects")] },
+    EffectSpec { effect_type: "repeat", fields: &[("times", "number"), ("count", "number"), ("steps", "effects")] },
+    EffectSpec {
+        effect_type: "conditional",
+        fields: &[("if", "condition"), ("then", "effects"), ("else", "effects")],
+    },
+    EffectSpec { effect_type: "log", fields: &[("message", "string")] },
+    EffectSpec {
+        effect_type: "apply_field_status",
+        fields: &[
+            ("statusId", "string"),
+            ("duration", "number"),
+            ("stack", "boolean"),
+            ("maxLayers", "number"),
+            ("side", "target"),
+            ("data", "object"),
+        ],
+    },
+    EffectSpec { effect_type: "remove_field_status", fields: &[("statusId", "string"), ("side", "target")] },
+    EffectSpec { effect_type: "random_move", fields: &[("pool", "string")] },
+    EffectSpec { effect_type: "copy_move", fields: &[("source", "string")] },
+    EffectSpec { effect_type: "nature_power", fields: &[] },
+    EffectSpec { effect_type: "apply_item", fields: &[("itemId", "string"), TARGET] },
+    EffectSpec { effect_type: "remove_item", fields: &[TARGET] },
+    EffectSpec { effect_type: "steal_item", fields: &[TARGET] },
+    EffectSpec { effect_type: "consume_item", fields: &[("markBerryConsumed", "boolean"), TARGET] },
+    EffectSpec {
+        effect_type: "ohko",
+        fields: &[
+            ("baseAccuracy", "number"),
+            ("requiredType", "string"),
+            ("nonMatchingTypeAccuracy", "number"),
+            ("levelScaling", "boolean"),
+            ("failIfTargetHigherLevel", "boolean"),
+            ("respectTypeImmunity", "boolean"),
+            ("immuneTypes", "array"),
+            ("name", "string"),
+        ],
+    },
+    EffectSpec { effect_type: "cure_all_status", fields: &[TARGET] },
+    EffectSpec { effect_type: "cure_team_status", fields: &[] },
+    EffectSpec { effect_type: "swap_side_effects", fields: &[] },
+    EffectSpec { effect_type: "cure_status", fields: &[("statusIds", "array"), TARGET] },
+    EffectSpec { effect_type: "self_switch", fields: &[] },
+    EffectSpec { effect_type: "force_switch", fields: &[TARGET] },
+    EffectSpec { effect_type: "replace_pokemon", fields: &[] },
+    EffectSpec { effect_type: "lock_move", fields: &[("duration", "number"), ("data", "object"), TARGET] },
+    EffectSpec { effect_type: "bind", fields: &[TARGET] },
+    EffectSpec { effect_type: "trap", fields: &[TARGET] },
+    EffectSpec { effect_type: "set_ability", fields: &[("abilityId", "string"), TARGET] },
+    EffectSpec { effect_type: "revive", fields: &[] },
+    EffectSpec {
+        effect_type: "set_type",
+        fields: &[("types", "array"), ("removeTypes", "array"), ("turnOnly", "boolean"), TARGET],
+    },
+    EffectSpec {
+        effect_type: "for_each_of_type",
+        fields: &[("creatureType", "string"), ("groundedOnly", "boolean"), ("steps", "effects")],
+    },
+    EffectSpec { effect_type: "run_away", fields: &[] },
+    EffectSpec { effect_type: "bypass_protect", fields: &[] },
+    EffectSpec { effect_type: "bypass_substitute", fields: &[] },
+    EffectSpec { effect_type: "ignore_immunity", fields: &[] },
+    EffectSpec { effect_type: "ignore_substitute", fields: &[] },
+    EffectSpec { effect_type: "sound", fields: &[] },
+    EffectSpec { effect_type: "manual", fields: &[("manualReason", "string")] },
+    EffectSpec { effect_type: "modify_damage", fields: &[("multiplier", "number")] },
+    EffectSpec { effect_type: "crit", fields: &[("multiplier", "number"), ("mult", "number")] },
+];
+
+pub fn effect_spec(effect_type: &str) -> Option<&'static EffectSpec> {
+    EFFECT_SPECS.iter().find(|spec| spec.effect_type == effect_type)
+}
+
+/// 技データの作者向けに、効果の種類ごとの `data` フィールドを JSON で返す。
+/// `{"effects": {"damage": {"fields": {"power": "number", ...}}, ...}}`
+pub fn effect_schema() -> Value {
+    let effects: Map<String, Value> = EFFECT_SPECS
+        .iter()
+        .map(|spec| {
+            let fields: Map<String, Value> = spec
+                .fields
+                .iter()
+                .map(|(name, kind)| (name.to_string(), Value::String(kind.to_string())))
+                .collect();
+            (spec.effect_type.to_string(), json!({ "fields": fields }))
+        })
+        .collect();
+    json!({
+        "targets": ["self", "target", "ally", "all", "all_adjacent", "all_adjacent_foes"],
+        "effects": effects,
+    })
+}
@@ -1,12 +1,18 @@
 use crate::core::abilities::{
-    run_ability_check_hook, run_ability_value_hook, AbilityCheckContext, AbilityValueContext, WeatherKind,
+    get_weather, run_ability_check_hook, run_ability_value_hook, AbilityCheckContext, AbilityValueContext, WeatherKind,
 };
+use crate::core::effect_schema::effect_spec;
 use crate::core::events::{
-    apply_event, meta_with_move_source, BattleEvent,
+    apply_event, is_major_status, meta_with_move_source, substitute_hp_from_max, BattleEvent,
 };
-use crate::core::state::BattleState;
-use crate::core::utils::{get_active_creature, stage_multiplier};
-use crate::data::moves::{Effect, MoveData};
+use crate::core::items::{run_item_check_hook, run_item_value_hook};
+use crate::core::rules::BattleRules;
+use crate::core::state::{BattleState, CreatureState, LogCategory, StatStages};
+use crate::core::utils::{
+    accuracy_stage_multiplier, active_battler_ids, ally_battler_id, find_battler, get_active_creature, is_doubles,
+    is_grounded, split_battler_id, stage_multiplier,
+};
+use crate::data::moves::{Effect, MoveCategory, MoveData};
 use crate::data::type_chart::TypeChart;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
@@ -18,6 +24,7 @@ pub struct EffectContext<'a> {
     pub rng: &'a mut dyn FnMut() -> f64,
     pub turn: u32,
     pub type_chart: &'a TypeChart,
+    pub rules: &'a BattleRules,
     pub bypass_protect: bool,
     pub ignore_immunity: bool,
     pub bypass_substitute: bool,
@@ -48,7 +55,12 @@ pub fn apply_effects(state: &BattleState, steps: &[Effect], ctx: &mut EffectCont
                 let effect_events = apply_effect(&working_state, effect, ctx);
                 update_last_damage_from_events(ctx, &effect_events);
                 working_state = apply_events(&working_state, &effect_events);
+                // 失敗した効果のあとは続けない (はねやすめが満タンで失敗したら ひこうタイプも残る)
+                let failed = effect_events.iter().any(is_failed_event);
                 events.extend(effect_events);
+                if failed {
+                    break;
+                }
             }
         }
     }
@@ -64,20 +76,51 @@ pub fn apply_events(state: &BattleState, events: &[BattleEvent]) -> BattleState
     next
 }
 
+/// 効果の `data` を読む。EFFECT_SPECS に載っていないフィールドを読んだらデバッグビルドで止める
+fn field<'e>(effect: &'e Effect, name: &str) -> Option<&'e Value> {
+    debug_assert!(
+        effect_spec(&effect.effect_type).is_some_and(|spec| spec.fields.iter().any(|(field, _)| *field == name)),
+        "{} reads `{}` but EFFECT_SPECS does not list it",
+        effect.effect_type,
+        name
+    );
+    effect.data.get(name)
+}
+
 fn apply_effect(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    debug_assert!(
+        effect_spec(&effect.effect_type).is_some(),
+        "{} is not listed in EFFECT_SPECS",
+        effect.effect_type
+    );
+    if let Some(targets) = spread_targets(state, effect, ctx) {
+        return apply_spread(state, effect, &targets, ctx);
+    }
     let effect_type = effect.effect_type.as_str();
     match effect_type {
         "protect" => apply_protect(state, effect, ctx),
+        "endure" => apply_endure(state, ctx),
         "damage" => apply_damage(state, effect, ctx),
         "speed_based_damage" => apply_speed_based_damage(state, effect, ctx),
+        "sky_drop" => apply_sky_drop(state, effect, ctx),
         "apply_status" => apply_status(state, effect, ctx),
         "remove_status" => apply_remove_status(effect, ctx),
         "replace_status" => apply_replace_status(state, effect, ctx),
         "modify_stage" => apply_modify_stage(effect, ctx),
+        "copy_stages" => apply_copy_stages(state, effect, ctx),
+        "swap_stages" => apply_swap_stages(state, effect, ctx),
+        "invert_stages" => apply_invert_stages(state, effect, ctx),
         "clear_stages" => apply_clear_stages(effect, ctx),
         "reset_stages" => apply_reset_stages(effect, ctx),
         "disable_move" => apply_disable_move(state, effect, ctx),
         "damage_ratio" => apply_damage_ratio(state, effect, ctx),
+        "heal" => apply_heal(state, effect, ctx),
+        "drain" => apply_drain(state, effect, ctx),
+        "recoil" => apply_recoil(state, effect, ctx),
+        "charge" => apply_charge(state, ctx),
+        "helping_hand" => apply_helping_hand(state, effect, ctx),
+        "restore_pp" => apply_restore_pp(state, effect, ctx),
+        "struggle_recoil" => apply_struggle_recoil(state, ctx),
         "delay" | "wait" => apply_delay(state, effect, ctx),
         "over_time" => apply_over_time(state, effect, ctx),
         "chance" => apply_chance(state, effect, ctx),
@@ -87,15 +130,27 @@ fn apply_effect(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         "apply_field_status" => apply_field_status(state, effect, ctx),
         "remove_field_status" => apply_remove_field_status(effect, ctx),
         "random_move" => apply_random_move(effect, ctx),
+        "copy_move" => apply_copy_move(state, effect, ctx),
+        "nature_power" => apply_nature_power(state, ctx),
         "apply_item" => apply_apply_item(state, effect, ctx),
         "remove_item" => apply_remove_item(state, effect, ctx),
+        "steal_item" => apply_steal_item(state, effect, ctx),
         "consume_item" => apply_consume_item(state, effect, ctx),
         "ohko" => apply_ohko(state, effect, ctx),
         "cure_all_status" => apply_cure_all_status(effect, ctx),
+        "cure_team_status" => apply_cure_team_status(state, ctx),
+        "swap_side_effects" => apply_swap_side_effects(state, ctx),
+        "cure_status" => apply_cure_status(state, effect, ctx),
         "self_switch" => apply_self_switch(ctx),
         "force_switch" => apply_force_switch(state, effect, ctx),
         "replace_pokemon" => apply_replace_pokemon(ctx),
         "lock_move" => apply_lock_move(state, effect, ctx),
+        "bind" => apply_bind(state, effect, ctx),
+        "trap" => apply_trap(state, effect, ctx),
+        "set_ability" => apply_set_ability(effect, ctx),
+        "revive" => apply_revive(state, ctx),
+        "set_type" => apply_set_type(state, effect, ctx),
+        "for_each_of_type" => apply_for_each_of_type(state, effect, ctx),
         "run_away" => apply_run_away(),
         "bypass_protect"
         | "bypass_substitute"
@@ -103,12 +158,15 @@ fn apply_effect(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         | "ignore_substitute"
         | "sound" => Vec::new(),
         "manual" => apply_manual_effect(effect, ctx),
-        _ => Vec::new(),
+        _ => {
+            debug_assert!(effect_spec(effect_type).is_none(), "{} is listed in EFFECT_SPECS but apply_effect does not handle it", effect_type);
+            Vec::new()
+        }
     }
 }
 
 fn apply_manual_effect(effect: &Effect, ctx: &EffectContext<'_>) -> Vec<BattleEvent> {
-    let reason = effect.data.get("manualReason").and_then(|v| v.as_str()).unwrap_or("");
+    let reason = field(effect, "manualReason").and_then(|v| v.as_str()).unwrap_or("");
     if reason.contains("Switching") {
         return vec![BattleEvent::ApplyStatus {
             target_id: ctx.attacker_player_id.clone(),
@@ -133,11 +191,60 @@ fn apply_manual_effect(effect: &Effect, ctx: &EffectContext<'_>) -> Vec<BattleEv
     }]
 }
 
-fn apply_protect(state: &BattleState, _effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+fn apply_protect(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
     let Some(attacker) = get_active_creature(state, &ctx.attacker_player_id) else {
         return Vec::new();
     };
 
+    // キングシールド: バトルスイッチなら 守る前に シールドフォルムへ戻る
+    let mut events = Vec::new();
+    if let Some(forme) = field(effect, "stanceForme").and_then(|v| v.as_str()) {
+        let can_change = run_ability_check_hook(
+            state,
+            &ctx.attacker_player_id,
+            "onCheckStanceChange",
+            AbilityCheckContext {
+                status_id: None,
+                r#type: None,
+                target_id: None,
+                action: None,
+            },
+            false,
+        );
+        if can_change {
+            events.push(BattleEvent::StanceChange {
+                target_id: ctx.attacker_player_id.clone(),
+                forme: forme.to_string(),
+                meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+            });
+        }
+    }
+
+    let failure = format!("{}の まもりは 失敗した！", attacker.name);
+    let mut data = HashMap::new();
+    if let Some(reaction) = field(effect, "onBlockReaction") {
+        data.insert("onBlockReaction".to_string(), reaction.clone());
+    }
+    events.extend(protect_chain_events(attacker, "protect", data, failure, ctx));
+    events
+}
+
+/// こらえる: 1ターンだけ ひんしになるダメージを HP 1 で耐える。まもると連続成功回数を共有する
+fn apply_endure(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(attacker) = get_active_creature(state, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    protect_chain_events(attacker, "endure", HashMap::new(), "しかし うまく 決まらなかった！".to_string(), ctx)
+}
+
+/// まもる系の成否判定。連続で成功するたびに成功率が 1/3 になり、成功すれば 1ターンの `status_id` を付ける
+fn protect_chain_events(
+    attacker: &CreatureState,
+    status_id: &str,
+    data: HashMap<String, Value>,
+    failure: String,
+    ctx: &mut EffectContext<'_>,
+) -> Vec<BattleEvent> {
     let success_count = attacker
         .volatile_data
         .get("protectSuccessCount")
@@ -152,8 +259,8 @@ fn apply_protect(state: &BattleState, _effect: &Effect, ctx: &mut EffectContext<
     if (ctx.rng)() > chance {
         return vec![
             BattleEvent::Log {
-                message: format!("{}の まもりは 失敗した！", attacker.name),
-                meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+                message: failure,
+                meta: outcome_meta(ctx, "failed", Some(&format!("{}_failed", status_id))),
             },
             BattleEvent::SetVolatile {
                 target_id: ctx.attacker_player_id.clone(),
@@ -170,17 +277,23 @@ fn apply_protect(state: &BattleState, _effect: &Effect, ctx: &mut EffectContext<
             value: Value::Number((success_count + 1).into()),
         },
         BattleEvent::ApplyStatus {
-        target_id: ctx.attacker_player_id.clone(),
-        status_id: "protect".to_string(),
-        duration: Some(1),
-        stack: false,
-        data: HashMap::new(),
-        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
-    }]
+            target_id: ctx.attacker_player_id.clone(),
+            status_id: status_id.to_string(),
+            duration: Some(1),
+            stack: false,
+            data,
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+    ]
+}
+
+/// 攻撃側の命中ランクと相手の回避ランクの差で命中率を補正する
+fn stage_adjusted_accuracy(accuracy: f64, attacker: &CreatureState, target: &CreatureState) -> f64 {
+    accuracy * accuracy_stage_multiplier(attacker.stages.accuracy - target.stages.evasion) as f64
 }
 
 fn apply_damage(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let Some(attacker) = get_active_creature(state, &ctx.attacker_player_id) else {
         return Vec::new();
     };
@@ -188,58 +301,95 @@ fn apply_damage(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         return Vec::new();
     };
 
-    let accuracy = value_f64(effect.data.get("accuracy"), state, ctx).unwrap_or(1.0);
-    let move_category = get_move_category(ctx.move_data);
-    let accuracy = run_ability_value_hook(
-        state,
-        &ctx.attacker_player_id,
-        "onModifyAccuracy",
-        accuracy as f32,
-        AbilityValueContext {
-            move_data: ctx.move_data,
-            category: move_category.as_deref(),
-            target: Some(target),
-            weather: None,
-            turn: ctx.turn,
-            stages: None,
-        },
-    ) as f64;
+    // accuracy のない技 (スピードスター等) と連続技の2発目以降は必中。ランクや特性・道具の補正も受けない
+    let skip_accuracy = field(effect, "skipAccuracyCheck").and_then(|v| v.as_bool()).unwrap_or(false);
+    let accuracy = match value_f64(field(effect, "accuracy"), state, ctx).filter(|_| !skip_accuracy) {
+        Some(accuracy) => {
+            let accuracy = stage_adjusted_accuracy(accuracy, attacker, target);
+            let accuracy = run_ability_value_hook(
+                state,
+                &ctx.attacker_player_id,
+                "onModifyAccuracy",
+                accuracy as f32,
+                AbilityValueContext {
+                    move_data: ctx.move_data,
+                    category: get_move_category(ctx.move_data),
+                    target: Some(target),
+                    weather: None,
+                    turn: ctx.turn,
+                    stages: None,
+                },
+            ) as f64;
+            run_item_value_hook(state, &ctx.attacker_player_id, "onModifyAccuracy", accuracy as f32) as f64
+        }
+        None => 1.0,
+    };
+
+    // じゅうでんは でんき技を出せば 当たらなくても消える
+    let mut events = Vec::new();
+    if is_charged(attacker) && damage_move_type(ctx.move_data) == Some("electric") {
+        events.push(BattleEvent::SetVolatile {
+            target_id: ctx.attacker_player_id.clone(),
+            key: "charged".to_string(),
+            value: Value::Bool(false),
+        });
+    }
 
     if (ctx.rng)() > accuracy {
-        return vec![BattleEvent::Log {
+        events.push(BattleEvent::Log {
             message: "しかし はずれた！".to_string(),
-            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
-        }];
+            meta: outcome_meta(ctx, "missed", None),
+        });
+        return events;
     }
 
-    let power = value_i32(effect.data.get("power"), state, ctx).unwrap_or(0);
+    let power = value_i32(field(effect, "power"), state, ctx).unwrap_or(0);
     let attacker_id = ctx.attacker_player_id.clone();
+    // 浮いている相手にも等倍で当て、そのまま撃ち落とす（サウザンアロー）
+    let grounds_target = field(effect, "groundsTarget").and_then(|v| v.as_bool()).unwrap_or(false)
+        && !is_grounded(target);
+    let prev_ignore_immunity = ctx.ignore_immunity;
+    if grounds_target {
+        ctx.ignore_immunity = true;
+    }
     
-    // Pass false for is_secondary_hit, let calc_damage handle crit logic
-    let (amount, is_crit) = calc_damage(power, state, &attacker_id, &target_id, ctx, false);
-    
-    let mut events = Vec::new();
+    let (mut amount, is_crit) = calc_damage(power, state, &attacker_id, &target_id, ctx, None);
+    ctx.ignore_immunity = prev_ignore_immunity;
+    if amount > 0 && field(effect, "spreadHit").and_then(|v| v.as_bool()).unwrap_or(false) {
+        amount = ((amount as f32 * ctx.rules.spread_multiplier).floor() as i32).max(1);
+    }
+    // みねうち・てかげん: 本体の HP は 1 残す。みがわりは普通に壊せる
+    let hits_substitute = !ctx.bypass_substitute
+        && target_id != ctx.attacker_player_id
+        && target.statuses.iter().any(|s| s.id == "substitute");
+    if field(effect, "leaveAtOneHp").and_then(|v| v.as_bool()).unwrap_or(false) && !hits_substitute {
+        amount = amount.min(target.hp - 1).max(0);
+    }
+
+    if amount == 0 && power > 0 && !grounds_target && !ctx.ignore_immunity {
+        let immune = damage_move_type(ctx.move_data).is_some_and(|move_type| {
+            type_effectiveness(ctx.type_chart, move_type, target) == 0.0
+                || wonder_guard_blocks(ctx.type_chart, move_type, target)
+        });
+        if immune {
+            events.push(BattleEvent::Log {
+                message: format!("{}には 効果が ないようだ……", target.name),
+                meta: outcome_meta(ctx, "noEffect", None),
+            });
+        }
+    }
 
     if amount > 0 {
         if is_crit {
-            events.push(BattleEvent::Log {
-                message: "急所に あたった！".to_string(),
-                meta: Map::new(),
-            });
+            events.push(BattleEvent::log_with(LogCategory::Damage, "急所に あたった！".to_string()));
         }
 
-        if let Some(move_type) = ctx.move_data.and_then(|m| m.move_type.as_deref()) {
-            let eff = ctx.type_chart.effectiveness(move_type, &target.types);
+        if let Some(move_type) = damage_move_type(ctx.move_data) {
+            let eff = type_effectiveness(ctx.type_chart, move_type, target);
             if eff > 1.0 {
-                events.push(BattleEvent::Log {
-                    message: "効果は 抜群だ！".to_string(),
-                    meta: Map::new(),
-                });
+                events.push(BattleEvent::log_with(LogCategory::Damage, "効果は 抜群だ！".to_string()));
             } else if eff > 0.0 && eff < 1.0 {
-                events.push(BattleEvent::Log {
-                    message: "効果は 今ひとつの ようだ……".to_string(),
-                    meta: Map::new(),
-                });
+                events.push(BattleEvent::log_with(LogCategory::Damage, "効果は 今ひとつの ようだ……".to_string()));
             }
         }
     }
@@ -247,16 +397,32 @@ fn apply_damage(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
     let mut meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
     meta.insert("target".to_string(), Value::String(target_id.clone()));
     meta.insert("cancellable".to_string(), Value::Bool(true));
+    meta.insert("hpBefore".to_string(), Value::from(hp_absorbing_hit(target, &target_id, ctx)));
+    if ctx.move_data.and_then(|m| m.move_type.as_deref()) == Some("fire") {
+        meta.insert("thawsTarget".to_string(), Value::Bool(true));
+    }
     events.push(BattleEvent::Damage {
         target_id: target_id.clone(),
         amount,
         meta,
     });
 
+    if grounds_target && amount > 0 {
+        events.push(BattleEvent::ApplyStatus {
+            target_id: target_id.clone(),
+            status_id: "grounded".to_string(),
+            duration: None,
+            stack: false,
+            data: HashMap::new(),
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        });
+        events.push(BattleEvent::log_with(LogCategory::Status, format!("{}は 地面に 落とされた！", target.name)));
+    }
+
     if attacker.ability.as_deref() == Some("parental_bond") {
-        let second_power = (power as f32 * 0.25).floor() as i32;
-        // Pass true for is_secondary_hit, parental bond 2nd hit doesn't crit
-        let (second_amount, _) = calc_damage(second_power, state, &attacker_id, &target_id, ctx, true);
+        let second_power = (power as f32 * ctx.rules.parental_bond_multiplier).floor() as i32;
+        // おやこあいの2発目は急所に当たらない
+        let (second_amount, _) = calc_damage(second_power, state, &attacker_id, &target_id, ctx, Some(false));
         
         let mut second_meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
         second_meta.insert("target".to_string(), Value::String(ctx.target_player_id.clone()));
@@ -274,16 +440,16 @@ fn apply_damage(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
 }
 
 fn apply_speed_based_damage(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let attacker_speed = compute_speed(state, &ctx.attacker_player_id, ctx.turn);
-    let target_speed = compute_speed(state, &ctx.target_player_id, ctx.turn);
+    let attacker_speed = effective_speed(state, &ctx.attacker_player_id, ctx.rules);
+    let target_speed = effective_speed(state, &ctx.target_player_id, ctx.rules);
     let ratio = if target_speed <= 0.0 {
         f32::INFINITY
     } else {
         attacker_speed / target_speed
     };
 
-    let mut chosen_power = value_i32(effect.data.get("basePower"), state, ctx).unwrap_or(0);
-    if let Some(Value::Array(thresholds)) = effect.data.get("thresholds") {
+    let mut chosen_power = value_i32(field(effect, "basePower"), state, ctx).unwrap_or(0);
+    if let Some(Value::Array(thresholds)) = field(effect, "thresholds") {
         let mut parsed: Vec<(f32, i32)> = thresholds
             .iter()
             .filter_map(|v| {
@@ -301,23 +467,129 @@ fn apply_speed_based_damage(state: &BattleState, effect: &Effect, ctx: &mut Effe
         }
     }
 
-    let mut cloned = effect.clone();
+    let mut cloned = as_damage_effect(effect, &["basePower", "thresholds"]);
     cloned.data.insert("power".to_string(), Value::Number(chosen_power.into()));
     apply_damage(state, &cloned, ctx)
 }
 
+/// 威力を決めたあとの攻撃は `damage` として撃つ。`damage` の知らないフィールドは落とす
+fn as_damage_effect(effect: &Effect, own_fields: &[&str]) -> Effect {
+    let mut damage = effect.clone();
+    damage.effect_type = "damage".to_string();
+    damage.data.retain(|key, _| !own_fields.contains(&key.as_str()));
+    damage
+}
+
+/// フリーフォール。1ターン目は相手を上空へ連れ去り (`sky_dropped`)、2ターン目に落として攻撃する。
+/// 重さが `maxWeight` (kg) 以上の相手は持ち上げられない
+fn apply_sky_drop(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(attacker) = get_active_creature(state, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    let move_id = ctx.move_data.map(|m| m.id.as_str());
+
+    if let Some(lifting) = attacker.statuses.iter().find(|s| s.id == "sky_drop") {
+        let target_id = lifting
+            .data
+            .get("targetId")
+            .and_then(|v| v.as_str())
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| ctx.target_player_id.clone());
+        let mut events = vec![BattleEvent::RemoveStatus {
+            target_id: ctx.attacker_player_id.clone(),
+            status_id: "sky_drop".to_string(),
+            meta: meta_with_move_source(move_id, Some(&ctx.attacker_player_id)),
+        }];
+        let held = get_active_creature(state, &target_id)
+            .is_some_and(|target| target.statuses.iter().any(|s| s.id == "sky_dropped"));
+        if !held {
+            events.push(BattleEvent::Log {
+                message: "しかし うまく 決まらなかった！".to_string(),
+                meta: outcome_meta(ctx, "failed", Some("target_released")),
+            });
+            return events;
+        }
+        // 命中判定は連れ去ったときに済んでいる
+        let mut drop = as_damage_effect(effect, &["maxWeight"]);
+        drop.data.remove("accuracy");
+        drop.data.insert("target".to_string(), Value::String("target".to_string()));
+        let prev_target = std::mem::replace(&mut ctx.target_player_id, target_id.clone());
+        events.extend(apply_damage(state, &drop, ctx));
+        ctx.target_player_id = prev_target;
+        events.push(BattleEvent::RemoveStatus {
+            target_id,
+            status_id: "sky_dropped".to_string(),
+            meta: meta_with_move_source(move_id, Some(&ctx.attacker_player_id)),
+        });
+        return events;
+    }
+
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let max_weight = value_f64(field(effect, "maxWeight"), state, ctx).map(|w| w as f32);
+    if max_weight.is_some_and(|max| target.weight.is_some_and(|w| w >= max)) {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("too_heavy")),
+        }];
+    }
+    let accuracy = value_f64(field(effect, "accuracy"), state, ctx)
+        .map_or(1.0, |accuracy| stage_adjusted_accuracy(accuracy, attacker, target));
+    if (ctx.rng)() > accuracy {
+        return vec![BattleEvent::Log {
+            message: "しかし はずれた！".to_string(),
+            meta: outcome_meta(ctx, "missed", None),
+        }];
+    }
+
+    let mut held_data = HashMap::new();
+    held_data.insert("sourceId".to_string(), Value::String(ctx.attacker_player_id.clone()));
+    let mut lifting_data = HashMap::new();
+    lifting_data.insert("targetId".to_string(), Value::String(target_id.clone()));
+    if let Some(move_id) = move_id {
+        lifting_data.insert("moveId".to_string(), Value::String(move_id.to_string()));
+    }
+    vec![
+        BattleEvent::log_with(LogCategory::Move, format!("{}は {}を 上空へ 連れ去った！", attacker.name, target.name)),
+        BattleEvent::ApplyStatus {
+            target_id: ctx.attacker_player_id.clone(),
+            status_id: "sky_drop".to_string(),
+            duration: Some(2),
+            stack: false,
+            data: lifting_data,
+            meta: meta_with_move_source(move_id, Some(&ctx.attacker_player_id)),
+        },
+        BattleEvent::ApplyStatus {
+            target_id,
+            status_id: "sky_dropped".to_string(),
+            duration: Some(2),
+            stack: false,
+            data: held_data,
+            meta: meta_with_move_source(move_id, Some(&ctx.attacker_player_id)),
+        },
+    ]
+}
+
 fn apply_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let status_id = match effect.data.get("statusId").and_then(|v| v.as_str()) {
+    let status_id = match field(effect, "statusId").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
 
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     if is_item_status(&status_id) {
         return apply_item_status(state, &status_id, &target_id, ctx);
     }
+    if status_id == "sleep" && ctx.rules.sleep_clause && sleep_clause_blocks(state, &target_id, ctx) {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("sleep_clause")),
+        }];
+    }
 
-    if let Some(chance) = value_f64(effect.data.get("chance"), state, ctx) {
+    if let Some(chance) = value_f64(field(effect, "chance"), state, ctx) {
         if (ctx.rng)() > chance {
             return vec![BattleEvent::Log {
                 message: format!("{}の {}は 効かなかった！",
@@ -328,8 +600,8 @@ fn apply_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         }
     }
 
-    let mut duration = value_i32(effect.data.get("duration"), state, ctx);
-    if let Some(Value::Object(range)) = effect.data.get("duration") {
+    let mut duration = value_i32(field(effect, "duration"), state, ctx);
+    if let Some(Value::Object(range)) = field(effect, "duration") {
         if let (Some(min), Some(max)) = (range.get("min").and_then(|v| v.as_i64()), range.get("max").and_then(|v| v.as_i64())) {
             let span = (max - min + 1) as f64;
             duration = Some(min as i32 + ((ctx.rng)() * span).floor() as i32);
@@ -337,7 +609,7 @@ fn apply_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
     }
 
     let mut data = HashMap::new();
-    if let Some(Value::Object(raw)) = effect.data.get("data") {
+    if let Some(Value::Object(raw)) = field(effect, "data") {
         for (k, v) in raw {
             data.insert(k.clone(), v.clone());
         }
@@ -350,6 +622,13 @@ fn apply_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
             );
         }
     }
+    // ねむりクローズ用に、相手に眠らされた (あくびを含む) ことを残しておく
+    if matches!(status_id.as_str(), "sleep" | "yawn")
+        && split_battler_id(&target_id).0 != split_battler_id(&ctx.attacker_player_id).0
+    {
+        data.entry("sourceId".to_string())
+            .or_insert_with(|| Value::String(ctx.attacker_player_id.clone()));
+    }
     if status_id == "substitute" && !data.contains_key("hp") {
         if let Some(target) = get_active_creature(state, &target_id) {
             let hp = ((target.max_hp as f64) * 0.25).floor() as i32;
@@ -361,18 +640,137 @@ fn apply_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         target_id,
         status_id: status_id.clone(),
         duration: if status_id == "sleep" { None } else { duration },
-        stack: effect.data.get("stack").and_then(|v| v.as_bool()).unwrap_or(false),
+        stack: field(effect, "stack").and_then(|v| v.as_bool()).unwrap_or(false),
+        data,
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
+// ねむりクローズ: 相手に眠らされたポケモンが既にいれば 2体目は眠らせられない。ねむる等で自分から眠ったものは数えない
+fn sleep_clause_blocks(state: &BattleState, target_id: &str, ctx: &EffectContext<'_>) -> bool {
+    let target_player = split_battler_id(target_id).0;
+    if target_player == split_battler_id(&ctx.attacker_player_id).0 {
+        return false;
+    }
+    let Some(target) = get_active_creature(state, target_id) else {
+        return false;
+    };
+    state
+        .players
+        .iter()
+        .filter(|p| p.id == target_player)
+        .flat_map(|p| p.team.iter())
+        .any(|c| {
+            c.id != target.id
+                && c.hp > 0
+                && c.statuses.iter().any(|s| {
+                    s.id == "sleep"
+                        && s.data
+                            .get("sourceId")
+                            .and_then(|v| v.as_str())
+                            .is_some_and(|source| split_battler_id(source).0 != target_player)
+                })
+        })
+}
+
+// しめつける・うずしお等: 4-5ターン交代を封じ、毎ターン最大HPの1/8を削る
+fn apply_bind(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    // 攻撃が外れた・無効だった場合は締めつけない
+    let landed = match ctx.last_damage {
+        Some(amount) => amount > 0,
+        None => !ctx
+            .move_data
+            .is_some_and(|m| m.steps.iter().any(|e| e.effect_type == "damage")),
+    };
+    if !landed {
+        return Vec::new();
+    }
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    match get_active_creature(state, &target_id) {
+        Some(target) if target.hp > 0 && !target.statuses.iter().any(|s| s.id == "bind") => {}
+        _ => return Vec::new(),
+    }
+    let duration = 4 + ((ctx.rng)() * 2.0).floor() as i32;
+    let mut data = HashMap::new();
+    data.insert("sourceId".to_string(), Value::String(ctx.attacker_player_id.clone()));
+    if let Some(name) = ctx.move_data.and_then(|m| m.name.clone()) {
+        data.insert("moveName".to_string(), Value::String(name));
+    }
+    vec![BattleEvent::ApplyStatus {
+        target_id,
+        status_id: "bind".to_string(),
+        duration: Some(duration),
+        stack: false,
         data,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
+// くろいまなざし・とおせんぼう等: 仕掛けた側が場を離れるまで交代を封じる
+fn apply_trap(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    if target.hp <= 0 || target.statuses.iter().any(|s| s.id == "trapped") {
+        return Vec::new();
+    }
+    let mut data = HashMap::new();
+    data.insert("sourceId".to_string(), Value::String(ctx.attacker_player_id.clone()));
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    vec![
+        BattleEvent::Log {
+            message: format!("{}は もう 逃げられない！", target.name),
+            meta: meta.clone(),
+        },
+        BattleEvent::ApplyStatus {
+            target_id,
+            status_id: "trapped".to_string(),
+            duration: None,
+            stack: false,
+            data,
+            meta,
+        },
+    ]
+}
+
+fn apply_set_ability(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(ability_id) = field(effect, "abilityId").and_then(|v| v.as_str()) else {
+        return Vec::new();
+    };
+    vec![BattleEvent::SetAbility {
+        target_id: resolve_target(field(effect, "target"), ctx),
+        ability_id: ability_id.to_string(),
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
+// さいきのいのり: 手持ちで最初の ひんしのポケモンを HP半分で復活させる
+fn apply_revive(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let slot = state
+        .players
+        .iter()
+        .find(|p| p.id == split_battler_id(&ctx.attacker_player_id).0)
+        .and_then(|p| p.team.iter().enumerate().position(|(idx, c)| !p.active_slots().contains(&idx) && c.hp <= 0));
+    let Some(slot) = slot else {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_fainted_ally")),
+        }];
+    };
+    vec![BattleEvent::Revive {
+        target_id: ctx.attacker_player_id.clone(),
+        slot,
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
 fn apply_remove_status(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let status_id = match effect.data.get("statusId").and_then(|v| v.as_str()) {
+    let status_id = match field(effect, "statusId").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     vec![BattleEvent::RemoveStatus {
         target_id,
         status_id,
@@ -381,15 +779,15 @@ fn apply_remove_status(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<Batt
 }
 
 fn apply_replace_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let from = match effect.data.get("from").and_then(|v| v.as_str()) {
+    let from = match field(effect, "from").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
-    let to = match effect.data.get("to").and_then(|v| v.as_str()) {
+    let to = match field(effect, "to").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     if from == "active" && to == "pending_switch" {
         return vec![BattleEvent::ApplyStatus {
             target_id,
@@ -400,9 +798,9 @@ fn apply_replace_status(state: &BattleState, effect: &Effect, ctx: &mut EffectCo
             meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
         }];
     }
-    let duration = value_i32(effect.data.get("duration"), state, ctx);
+    let duration = value_i32(field(effect, "duration"), state, ctx);
     let mut data = HashMap::new();
-    if let Some(Value::Object(raw)) = effect.data.get("data") {
+    if let Some(Value::Object(raw)) = field(effect, "data") {
         for (k, v) in raw {
             data.insert(k.clone(), v.clone());
         }
@@ -418,9 +816,9 @@ fn apply_replace_status(state: &BattleState, effect: &Effect, ctx: &mut EffectCo
 }
 
 fn apply_modify_stage(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let mut stages = HashMap::new();
-    if let Some(Value::Object(raw)) = effect.data.get("stages") {
+    if let Some(Value::Object(raw)) = field(effect, "stages") {
         for (k, v) in raw {
             if let Some(delta) = v.as_i64() {
                 stages.insert(k.clone(), delta as i32);
@@ -430,40 +828,149 @@ fn apply_modify_stage(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<Battl
     vec![BattleEvent::ModifyStage {
         target_id,
         stages,
-        clamp: effect.data.get("clamp").and_then(|v| v.as_bool()).unwrap_or(true),
-        fail_if_no_change: effect.data.get("fail_if_no_change").and_then(|v| v.as_bool()).unwrap_or(false),
-        show_event: effect.data.get("show_event").and_then(|v| v.as_bool()).unwrap_or(true),
+        clamp: field(effect, "clamp").and_then(|v| v.as_bool()).unwrap_or(true),
+        fail_if_no_change: field(effect, "fail_if_no_change").and_then(|v| v.as_bool()).unwrap_or(false),
+        show_event: field(effect, "show_event").and_then(|v| v.as_bool()).unwrap_or(true),
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
+/// じこあんじ: 相手の能力ランクをそのまま自分に写す。SetStages で値ごと書き換えるので、特性による増減や下がったときの反応は起きない
+fn apply_copy_stages(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let source_id = resolve_target(field(effect, "target"), ctx);
+    let (Some(user), Some(source)) = (
+        get_active_creature(state, &ctx.attacker_player_id),
+        get_active_creature(state, &source_id),
+    ) else {
+        return Vec::new();
+    };
+    vec![
+        BattleEvent::SetStages {
+            target_id: ctx.attacker_player_id.clone(),
+            stages: stage_values(&source.stages, STAGE_KEYS),
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+        BattleEvent::log_with(LogCategory::Status, format!("{}は {}の 能力変化を コピーした！", user.name, source.name)),
+    ]
+}
+
+/// じこあんじ・ハートスワップのようにランク変化をまるごと扱う効果が見るキー
+const STAGE_KEYS: &[&str] = &["atk", "def", "spa", "spd", "spe", "accuracy", "evasion", "crit"];
+
+fn stage_value(stages: &StatStages, key: &str) -> i32 {
+    match key {
+        "atk" => stages.atk,
+        "def" => stages.def,
+        "spa" => stages.spa,
+        "spd" => stages.spd,
+        "spe" => stages.spe,
+        "accuracy" => stages.accuracy,
+        "evasion" => stages.evasion,
+        "crit" => stages.crit,
+        _ => 0,
+    }
+}
+
+fn stage_values(stages: &StatStages, keys: &[&str]) -> HashMap<String, i32> {
+    keys.iter().map(|key| (key.to_string(), stage_value(stages, key))).collect()
+}
+
+/// パワースワップ・ガードスワップ・ハートスワップ。`stats` を省くと全ランクを入れ替える
+fn apply_swap_stages(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let (Some(user), Some(target)) = (
+        get_active_creature(state, &ctx.attacker_player_id),
+        get_active_creature(state, &target_id),
+    ) else {
+        return Vec::new();
+    };
+    let keys: Vec<&str> = match field(effect, "stats").and_then(|v| v.as_array()) {
+        Some(stats) => stats.iter().filter_map(|v| v.as_str()).collect(),
+        None => STAGE_KEYS.to_vec(),
+    };
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    vec![
+        BattleEvent::SetStages {
+            target_id: ctx.attacker_player_id.clone(),
+            stages: stage_values(&target.stages, &keys),
+            meta: meta.clone(),
+        },
+        BattleEvent::SetStages {
+            target_id: target_id.clone(),
+            stages: stage_values(&user.stages, &keys),
+            meta,
+        },
+        BattleEvent::log_with(LogCategory::Status, format!("{}は {}と 能力変化を 入れ替えた！", user.name, target.name)),
+    ]
+}
+
+/// ひっくりかえす: 相手のランク変化の符号を反転する。あまのじゃく・たんじゅんは関係ない
+fn apply_invert_stages(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let stages: HashMap<String, i32> = STAGE_KEYS
+        .iter()
+        .map(|key| (key.to_string(), -stage_value(&target.stages, key)))
+        .filter(|(_, value)| *value != 0)
+        .collect();
+    if stages.is_empty() {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_stage_changes")),
+        }];
+    }
+    vec![
+        BattleEvent::SetStages {
+            target_id,
+            stages,
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+        BattleEvent::log_with(LogCategory::Status, format!("{}の 能力変化が ひっくり返った！", target.name)),
+    ]
+}
+
 fn apply_clear_stages(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     vec![BattleEvent::ClearStages {
         target_id,
-        show_event: effect.data.get("show_event").and_then(|v| v.as_bool()).unwrap_or(true),
+        show_event: field(effect, "show_event").and_then(|v| v.as_bool()).unwrap_or(true),
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
 fn apply_reset_stages(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     vec![BattleEvent::ResetStages {
         target_id,
-        show_event: effect.data.get("show_event").and_then(|v| v.as_bool()).unwrap_or(true),
+        show_event: field(effect, "show_event").and_then(|v| v.as_bool()).unwrap_or(true),
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
 fn apply_disable_move(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
-    let move_id = effect.data.get("moveId").and_then(|v| v.as_str()).unwrap_or("");
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    // 技の指定がなければ 相手が最後に出した技を封じる（かなしばり）
+    let move_id = field(effect, "moveId")
+        .and_then(|v| v.as_str())
+        .or_else(|| {
+            get_active_creature(state, &target_id)
+                .and_then(|target| target.volatile_data.get("lastMove"))
+                .and_then(|v| v.as_str())
+        });
+    let Some(move_id) = move_id else {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_last_move")),
+        }];
+    };
     let mut data = HashMap::new();
     data.insert("moveId".to_string(), Value::String(move_id.to_string()));
     vec![BattleEvent::ApplyStatus {
         target_id,
         status_id: "disable_move".to_string(),
-        duration: value_i32(effect.data.get("duration"), state, ctx),
+        duration: value_i32(field(effect, "duration"), state, ctx),
         stack: false,
         data,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
@@ -471,19 +978,19 @@ fn apply_disable_move(state: &BattleState, effect: &Effect, ctx: &mut EffectCont
 }
 
 fn apply_damage_ratio(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let Some(target) = get_active_creature(state, &target_id) else {
         return Vec::new();
     };
     // Support both ratioMaxHp (based on max HP) and ratioCurrentHp (based on current HP)
-    let mut amount = if let Some(ratio) = value_f64(effect.data.get("ratioCurrentHp"), state, ctx) {
+    let mut amount = if let Some(ratio) = value_f64(field(effect, "ratioCurrentHp"), state, ctx) {
         (target.hp as f64 * ratio).floor() as i32
     } else {
-        let ratio = value_f64(effect.data.get("ratioMaxHp"), state, ctx).unwrap_or(0.0);
+        let ratio = value_f64(field(effect, "ratioMaxHp"), state, ctx).unwrap_or(0.0);
         (target.max_hp as f64 * ratio).floor() as i32
     };
-    let ratio = value_f64(effect.data.get("ratioCurrentHp"), state, ctx)
-        .or_else(|| value_f64(effect.data.get("ratioMaxHp"), state, ctx))
+    let ratio = value_f64(field(effect, "ratioCurrentHp"), state, ctx)
+        .or_else(|| value_f64(field(effect, "ratioMaxHp"), state, ctx))
         .unwrap_or(0.0);
     if amount == 0 && ratio != 0.0 {
         amount = if ratio > 0.0 { 1 } else { -1 };
@@ -503,24 +1010,207 @@ fn apply_damage_ratio(state: &BattleState, effect: &Effect, ctx: &mut EffectCont
     }]
 }
 
+/// 回復技。最大HPの `ratioMaxHp` (既定 0.5) を回復する。最大HPを超えては回復しない
+fn apply_heal(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let missing = target.max_hp - target.hp;
+    if missing <= 0 {
+        if field(effect, "fail_if_full").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return vec![BattleEvent::Log {
+                message: format!("{}の HPは 満タンだ！", target.name),
+                meta: outcome_meta(ctx, "failed", Some("hp_full")),
+            }];
+        }
+        return Vec::new();
+    }
+    let ratio = value_f64(field(effect, "ratioMaxHp"), state, ctx).unwrap_or(0.5);
+    let heal = ((target.max_hp as f64 * ratio).floor() as i32).max(1).min(missing);
+    let mut meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    meta.insert("target".to_string(), Value::String(target_id.clone()));
+    vec![BattleEvent::Damage {
+        target_id,
+        amount: -heal,
+        meta,
+    }]
+}
+
+/// ヒメリのみ等。`moveId` (無ければ最後に出した技) の PP を `amount` (既定 10) 回復する。
+/// 最大PPでの切り詰めと 満タン時の失敗は MoveDatabase を持つ battle.rs が行う
+fn apply_restore_pp(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = match field(effect, "target") {
+        Some(target) => resolve_target(Some(target), ctx),
+        None => ctx.attacker_player_id.clone(),
+    };
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let move_id = field(effect, "moveId")
+        .or_else(|| target.volatile_data.get("lastMove"))
+        .and_then(|v| v.as_str());
+    let Some(move_id) = move_id else {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_move")),
+        }];
+    };
+    vec![BattleEvent::RestorePp {
+        target_id,
+        move_id: move_id.to_string(),
+        amount: value_i32(field(effect, "amount"), state, ctx).unwrap_or(10),
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
+/// 吸収技。直前に与えたダメージの `ratio` (既定 0.5) だけ自分の HP を回復する。おおきなねっこで増える
+fn apply_drain(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let dealt = ctx.last_damage.unwrap_or(0);
+    if dealt <= 0 {
+        return Vec::new();
+    }
+    let Some(target) = get_active_creature(state, &ctx.target_player_id) else {
+        return Vec::new();
+    };
+    let ratio = value_f64(field(effect, "ratio"), state, ctx).unwrap_or(0.5);
+    let heal = ((dealt as f64 * ratio).floor() as i32).max(1);
+    let heal = run_item_value_hook(state, &ctx.attacker_player_id, "onModifyDrain", heal as f32).floor() as i32;
+    let mut meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    meta.insert("target".to_string(), Value::String(ctx.attacker_player_id.clone()));
+    vec![
+        BattleEvent::log_with(LogCategory::Damage, format!("{}から 体力を 吸い取った！", target.name)),
+        BattleEvent::Damage {
+            target_id: ctx.attacker_player_id.clone(),
+            amount: -heal,
+            meta,
+        },
+    ]
+}
+
+/// てだすけ。味方にそのターンだけ有効な印 (`helpingHand` = ターン数) を付け、`calc_damage` が技の威力を1.5倍にする
+fn apply_helping_hand(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let ally_id = match field(effect, "target") {
+        Some(target) => resolve_target(Some(target), ctx),
+        None => ally_battler_id(&ctx.attacker_player_id),
+    };
+    let (Some(user), Some(ally)) = (
+        get_active_creature(state, &ctx.attacker_player_id),
+        get_active_creature(state, &ally_id).filter(|ally| ally.hp > 0),
+    ) else {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_ally")),
+        }];
+    };
+    vec![
+        BattleEvent::SetVolatile {
+            target_id: ally_id,
+            key: "helpingHand".to_string(),
+            value: Value::Number(ctx.turn.into()),
+        },
+        BattleEvent::Log {
+            message: format!("{}は {}を 手助けする 体勢に 入った！", user.name, ally.name),
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+    ]
+}
+
+/// じゅうでん。次に出すでんき技の威力を2倍にする (`calc_damage` が参照し、`apply_damage` が消す)
+fn apply_charge(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(user) = get_active_creature(state, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    vec![
+        BattleEvent::SetVolatile {
+            target_id: ctx.attacker_player_id.clone(),
+            key: "charged".to_string(),
+            value: Value::Bool(true),
+        },
+        BattleEvent::Log {
+            message: format!("{}は じゅうでんを はじめた！", user.name),
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+    ]
+}
+
+fn is_charged(creature: &CreatureState) -> bool {
+    creature.volatile_data.get("charged").and_then(|v| v.as_bool()) == Some(true)
+}
+
+/// 反動技。与えたダメージの `ratioDamage` (既定 1/3)、または最大HPの `ratioMaxHp` 分を自分が受ける
+fn apply_recoil(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(user) = get_active_creature(state, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    let amount = if let Some(ratio) = value_f64(field(effect, "ratioMaxHp"), state, ctx) {
+        (user.max_hp as f64 * ratio).floor() as i32
+    } else {
+        let dealt = ctx.last_damage.unwrap_or(0);
+        if dealt <= 0 {
+            return Vec::new();
+        }
+        let ratio = value_f64(field(effect, "ratioDamage"), state, ctx).unwrap_or(1.0 / 3.0);
+        (dealt as f64 * ratio).floor() as i32
+    };
+    // いしあたま・マジックガード
+    let immune = run_ability_check_hook(
+        state,
+        &ctx.attacker_player_id,
+        "onRecoilImmunity",
+        AbilityCheckContext {
+            status_id: None,
+            r#type: None,
+            target_id: None,
+            action: None,
+        },
+        false,
+    );
+    if immune {
+        return Vec::new();
+    }
+    vec![
+        BattleEvent::Damage {
+            target_id: ctx.attacker_player_id.clone(),
+            amount: amount.max(1),
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+        BattleEvent::log_with(LogCategory::Damage, format!("{}は 反動を 受けた！", user.name)),
+    ]
+}
+
+/// わるあがきの反動。自分の最大HPに `rules.struggle_recoil` を掛けた分を受ける
+fn apply_struggle_recoil(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(user) = get_active_creature(state, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    let amount = ((user.max_hp as f32 * ctx.rules.struggle_recoil).floor() as i32).max(1);
+    vec![
+        BattleEvent::Damage {
+            target_id: ctx.attacker_player_id.clone(),
+            amount,
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        },
+        BattleEvent::log_with(LogCategory::Damage, format!("{}は 反動を 受けた！", user.name)),
+    ]
+}
+
 fn apply_delay(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
-    let after_turns = value_i32(effect.data.get("turns"), state, ctx)
-        .or_else(|| value_i32(effect.data.get("afterTurns"), state, ctx))
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let after_turns = value_i32(field(effect, "turns"), state, ctx)
+        .or_else(|| value_i32(field(effect, "afterTurns"), state, ctx))
         .unwrap_or(0);
     let trigger_turn = ctx.turn as i32 + after_turns;
     let mut data = HashMap::new();
     data.insert("triggerTurn".to_string(), Value::Number(trigger_turn.into()));
     data.insert("sourceId".to_string(), Value::String(ctx.attacker_player_id.clone()));
     data.insert("targetId".to_string(), Value::String(target_id.clone()));
-    let steps_value = effect
-        .data
-        .get("steps")
-        .or_else(|| effect.data.get("then"));
+    let steps_value = field(effect, "steps")
+        .or_else(|| field(effect, "then"));
     if let Some(Value::Array(steps_value)) = steps_value {
         data.insert("effects".to_string(), Value::Array(steps_value.clone()));
     }
-    if let Some(Value::String(timing)) = effect.data.get("timing") {
+    if let Some(Value::String(timing)) = field(effect, "timing") {
         data.insert("timing".to_string(), Value::String(timing.clone()));
     }
     vec![BattleEvent::ApplyStatus {
@@ -534,12 +1224,12 @@ fn apply_delay(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>
 }
 
 fn apply_over_time(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let mut data = HashMap::new();
-    if let Some(Value::Array(steps_value)) = effect.data.get("steps") {
+    if let Some(Value::Array(steps_value)) = field(effect, "steps") {
         data.insert("effects".to_string(), Value::Array(steps_value.clone()));
     }
-    if let Some(Value::String(timing)) = effect.data.get("timing") {
+    if let Some(Value::String(timing)) = field(effect, "timing") {
         data.insert("timing".to_string(), Value::String(timing.clone()));
     }
     data.insert("sourceId".to_string(), Value::String(ctx.attacker_player_id.clone()));
@@ -547,7 +1237,7 @@ fn apply_over_time(state: &BattleState, effect: &Effect, ctx: &mut EffectContext
     vec![BattleEvent::ApplyStatus {
         target_id,
         status_id: "over_time_effect".to_string(),
-        duration: value_i32(effect.data.get("duration"), state, ctx),
+        duration: value_i32(field(effect, "duration"), state, ctx),
         stack: false,
         data,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
@@ -556,34 +1246,42 @@ fn apply_over_time(state: &BattleState, effect: &Effect, ctx: &mut EffectContext
 
 fn apply_chance(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
     let roll = (ctx.rng)();
-    let p = value_f64(effect.data.get("p"), state, ctx).unwrap_or(0.0);
+    let p = value_f64(field(effect, "p"), state, ctx).unwrap_or(0.0);
     if roll <= p {
-        let steps = steps_from_value(effect.data.get("then"));
+        let steps = steps_from_value(field(effect, "then"));
         return apply_effects(state, &steps, ctx);
     }
-    let steps = steps_from_value(effect.data.get("else"));
+    let steps = steps_from_value(field(effect, "else"));
     apply_effects(state, &steps, ctx)
 }
 
 fn apply_repeat(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let mut times = value_i32(effect.data.get("times"), state, ctx)
-        .or_else(|| value_i32(effect.data.get("count"), state, ctx))
+    let mut times = value_i32(field(effect, "times"), state, ctx)
+        .or_else(|| value_i32(field(effect, "count"), state, ctx))
         .unwrap_or(1);
-    if let Some(Value::Object(range)) = effect.data.get("times") {
-        let min = range.get("min").and_then(|v| v.as_i64()).unwrap_or(1);
+    let is_skill_link = run_ability_check_hook(
+        state,
+        &ctx.attacker_player_id,
+        "onSkillLink",
+        AbilityCheckContext {
+            status_id: None,
+            r#type: None,
+            target_id: None,
+            action: None,
+        },
+        false,
+    );
+    let multiaccuracy = ctx
+        .move_data
+        .is_some_and(|m| m.tags.iter().any(|tag| tag == "multiaccuracy"));
+    let loaded_dice = run_item_check_hook(state, &ctx.attacker_player_id, "onLoadedDice", false);
+    if let Some(Value::Object(range)) = field(effect, "times") {
+        let mut min = range.get("min").and_then(|v| v.as_i64()).unwrap_or(1);
         let max = range.get("max").and_then(|v| v.as_i64()).unwrap_or(min);
-        let is_skill_link = run_ability_check_hook(
-            state,
-            &ctx.attacker_player_id,
-            "onSkillLink",
-            AbilityCheckContext {
-                status_id: None,
-                r#type: None,
-                target_id: None,
-                action: None,
-            },
-            false,
-        );
+        // いかさまダイス: 2-5回の技は 4回以上、ネズミざんのような multiaccuracy の技は 4-10回
+        if loaded_dice {
+            min = if multiaccuracy { 4.min(max) } else { min.max(4).min(max) };
+        }
         if is_skill_link {
             times = max as i32;
         } else {
@@ -592,40 +1290,73 @@ fn apply_repeat(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_
         }
     }
 
-    let steps = steps_from_value(effect.data.get("steps"));
+    // multiaccuracy の技 (トリプルアクセルなど) は1発ごとに命中判定し、外れたらそこで終わる。
+    // それ以外とスキルリンク・いかさまダイスは 1発目の判定だけで残りは必ず当たる
+    let per_hit_accuracy = !is_skill_link && !loaded_dice && multiaccuracy;
+    let steps = steps_from_value(field(effect, "steps"));
+    let follow_up_steps = if per_hit_accuracy { steps.clone() } else { without_accuracy_checks(&steps) };
     let mut collected = Vec::new();
     let mut working_state = state.clone();
     let mut hits = 0;
-    for _ in 0..times {
+    for index in 0..times {
         if let Some(target) = get_active_creature(&working_state, &ctx.target_player_id) {
             if target.hp <= 0 {
                 break;
             }
         }
-        let events = apply_effects(&working_state, &steps, ctx);
+        let events = apply_effects(&working_state, if index == 0 { &steps } else { &follow_up_steps }, ctx);
+        let missed = events.iter().any(is_missed_event);
         working_state = apply_events(&working_state, &events);
         collected.extend(events);
+        if missed {
+            break;
+        }
         hits += 1;
     }
     if hits > 1 {
-        collected.push(BattleEvent::Log {
-            message: format!("{}回 あたった！", hits),
-            meta: Map::new(),
-        });
+        collected.push(BattleEvent::log_with(LogCategory::Damage, format!("{}回 あたった！", hits)));
     }
     collected
 }
 
+/// 2発目以降の damage に `skipAccuracyCheck` を付けて命中判定を外す
+fn without_accuracy_checks(steps: &[Effect]) -> Vec<Effect> {
+    steps
+        .iter()
+        .map(|step| {
+            let mut step = step.clone();
+            if step.effect_type == "damage" {
+                step.data.insert("skipAccuracyCheck".to_string(), Value::Bool(true));
+            }
+            step
+        })
+        .collect()
+}
+
+fn is_failed_event(event: &BattleEvent) -> bool {
+    matches!(
+        event,
+        BattleEvent::Log { meta, .. } if meta.get("outcome").and_then(|v| v.as_str()) == Some("failed")
+    )
+}
+
+fn is_missed_event(event: &BattleEvent) -> bool {
+    matches!(
+        event,
+        BattleEvent::Log { meta, .. } if meta.get("outcome").and_then(|v| v.as_str()) == Some("missed")
+    )
+}
+
 fn apply_conditional(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let condition = effect.data.get("if");
+    let condition = field(effect, "if");
     let result = evaluate_condition(state, condition, ctx);
     let next_key = if result { "then" } else { "else" };
-    let steps = steps_from_value(effect.data.get(next_key));
+    let steps = steps_from_value(field(effect, next_key));
     apply_effects(state, &steps, ctx)
 }
 
 fn apply_log(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    if let Some(message) = effect.data.get("message").and_then(|v| v.as_str()) {
+    if let Some(message) = field(effect, "message").and_then(|v| v.as_str()) {
         return vec![BattleEvent::Log {
             message: message.to_string(),
             meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
@@ -635,40 +1366,65 @@ fn apply_log(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
 }
 
 fn apply_field_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let status_id = match effect.data.get("statusId").and_then(|v| v.as_str()) {
+    let status_id = match field(effect, "statusId").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
+    // `side: target` で相手の陣営、`side: self` で自分の陣営にだけ付ける (まきびし・ステルスロックなど)
+    let side = field(effect, "side")
+        .map(|side| split_battler_id(&resolve_target(Some(side), ctx)).0.to_string());
     let mut data = HashMap::new();
-    if let Some(Value::Object(raw)) = effect.data.get("data") {
+    if let Some(Value::Object(raw)) = field(effect, "data") {
         for (k, v) in raw {
             data.insert(k.clone(), v.clone());
         }
     }
+    // `maxLayers`: 張り直すたびに `data.layers` を1つ増やす (まきびしは3層まで)
+    if let Some(max_layers) = field(effect, "maxLayers").and_then(|v| v.as_u64()) {
+        let effects = match &side {
+            Some(side) => state.field.sides.get(side).map(Vec::as_slice).unwrap_or_default(),
+            None => state.field.global.as_slice(),
+        };
+        let layers = effects
+            .iter()
+            .find(|e| e.id == status_id)
+            .map(|e| e.data.get("layers").and_then(|v| v.as_u64()).unwrap_or(1))
+            .unwrap_or(0);
+        if layers >= max_layers {
+            return vec![BattleEvent::Log {
+                message: "しかし うまく 決まらなかった！".to_string(),
+                meta: outcome_meta(ctx, "failed", Some("max_layers")),
+            }];
+        }
+        data.insert("layers".to_string(), Value::from(layers + 1));
+    }
     vec![BattleEvent::ApplyFieldStatus {
         status_id,
-        duration: value_i32(effect.data.get("duration"), state, ctx),
-        stack: effect.data.get("stack").and_then(|v| v.as_bool()).unwrap_or(false),
+        duration: value_i32(field(effect, "duration"), state, ctx),
+        stack: field(effect, "stack").and_then(|v| v.as_bool()).unwrap_or(false),
+        side,
         data,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
 fn apply_remove_field_status(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let status_id = match effect.data.get("statusId").and_then(|v| v.as_str()) {
+    let status_id = match field(effect, "statusId").and_then(|v| v.as_str()) {
         Some(id) => id.to_string(),
         None => return Vec::new(),
     };
+    // apply_field_status と同じく、`side` があればその陣営の場から外す
+    let side = field(effect, "side")
+        .map(|side| split_battler_id(&resolve_target(Some(side), ctx)).0.to_string());
     vec![BattleEvent::RemoveFieldStatus {
         status_id,
+        side,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
 fn apply_random_move(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let pool = effect
-        .data
-        .get("pool")
+    let pool = field(effect, "pool")
         .and_then(|v| v.as_str())
         .unwrap_or("all")
         .to_string();
@@ -678,14 +1434,99 @@ fn apply_random_move(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<Battle
     }]
 }
 
+/// オウムがえし・まねっこで出せない技
+pub const UNCOPYABLE_MOVES: &[&str] = &[
+    "mirror_move",
+    "copycat",
+    "metronome",
+    "sleep_talk",
+    "struggle",
+    "protect",
+    "detect",
+    "endure",
+    "counter",
+    "mirror_coat",
+    "sky_drop",
+    "assist",
+    "nature_power",
+];
+
+/// 相手が最後に出した技 (`source: target`、オウムがえし) か、場で最後に出た技 (`source: last`、まねっこ)、
+/// 控えの仲間が覚えている技からランダムに1つ (`source: team`、ねこのて) を出す
+fn apply_copy_move(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let move_id = match field(effect, "source").and_then(|v| v.as_str()).unwrap_or("target") {
+        "last" => state.field.last_move.clone(),
+        "team" => {
+            let mut candidates: Vec<&String> = Vec::new();
+            if let Some((player, _)) = find_battler(&state.players, &ctx.attacker_player_id) {
+                let active_slots = player.active_slots();
+                for (slot, teammate) in player.team.iter().enumerate() {
+                    if active_slots.contains(&slot) {
+                        continue;
+                    }
+                    for move_id in &teammate.moves {
+                        if !UNCOPYABLE_MOVES.contains(&move_id.as_str()) && !candidates.contains(&move_id) {
+                            candidates.push(move_id);
+                        }
+                    }
+                }
+            }
+            if candidates.is_empty() {
+                None
+            } else {
+                let idx = (((ctx.rng)() * candidates.len() as f64) as usize).min(candidates.len() - 1);
+                Some(candidates[idx].clone())
+            }
+        }
+        _ => get_active_creature(state, &ctx.target_player_id)
+            .and_then(|target| target.volatile_data.get("lastMove"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    };
+    match move_id.filter(|id| !UNCOPYABLE_MOVES.contains(&id.as_str())) {
+        Some(move_id) => vec![BattleEvent::CallMove {
+            move_id,
+            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+        }],
+        None => vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("nothing_to_copy")),
+        }],
+    }
+}
+
+/// しぜんのちから: 場のフィールドに応じた技になる。フィールドが無ければ じしん
+pub const NATURE_POWER_MOVES: &[(&str, &str)] = &[
+    ("electric_terrain", "thunderbolt"),
+    ("grassy_terrain", "energy_ball"),
+    ("misty_terrain", "moonblast"),
+    ("psychic_terrain", "psychic"),
+];
+
+fn apply_nature_power(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let move_id = state
+        .field
+        .global
+        .iter()
+        .find_map(|effect| {
+            NATURE_POWER_MOVES
+                .iter()
+                .find(|(terrain, _)| *terrain == effect.id)
+                .map(|(_, move_id)| *move_id)
+        })
+        .unwrap_or("earthquake");
+    vec![BattleEvent::CallMove {
+        move_id: move_id.to_string(),
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
 fn apply_apply_item(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let Some(target) = get_active_creature(state, &target_id) else {
         return Vec::new();
     };
-    let item_id = effect
-        .data
-        .get("itemId")
+    let item_id = field(effect, "itemId")
         .and_then(|v| v.as_str())
         .unwrap_or("item")
         .to_string();
@@ -705,7 +1546,7 @@ fn apply_apply_item(state: &BattleState, effect: &Effect, ctx: &mut EffectContex
 }
 
 fn apply_remove_item(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let Some(target) = get_active_creature(state, &target_id) else {
         return Vec::new();
     };
@@ -732,16 +1573,76 @@ fn apply_remove_item(state: &BattleState, effect: &Effect, ctx: &mut EffectConte
     ]
 }
 
+// どろぼう: 自分が道具を持っていなければ 相手の道具を奪う。ねんちゃく持ちからは奪えない
+fn apply_steal_item(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    if ctx.last_damage.is_some_and(|amount| amount <= 0) {
+        return Vec::new();
+    }
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let (Some(attacker), Some(target)) = (
+        get_active_creature(state, &ctx.attacker_player_id),
+        get_active_creature(state, &target_id),
+    ) else {
+        return Vec::new();
+    };
+    if attacker.hp <= 0 || has_item(attacker) {
+        return Vec::new();
+    }
+    let Some(item_id) = get_item_id(target) else {
+        return Vec::new();
+    };
+    let sticky = run_ability_check_hook(
+        state,
+        &target_id,
+        "onCheckItemRemoval",
+        AbilityCheckContext {
+            status_id: None,
+            r#type: None,
+            target_id: Some(&ctx.attacker_player_id),
+            action: None,
+        },
+        false,
+    );
+    if sticky {
+        return Vec::new();
+    }
+
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    let mut data = HashMap::new();
+    data.insert("itemId".to_string(), Value::String(item_id.clone()));
+    vec![
+        BattleEvent::RemoveStatus {
+            target_id: target_id.clone(),
+            status_id: "item".to_string(),
+            meta: meta.clone(),
+        },
+        BattleEvent::RemoveStatus {
+            target_id,
+            status_id: "berry".to_string(),
+            meta: meta.clone(),
+        },
+        BattleEvent::ApplyStatus {
+            target_id: ctx.attacker_player_id.clone(),
+            status_id: "item".to_string(),
+            duration: None,
+            stack: false,
+            data,
+            meta: meta.clone(),
+        },
+        BattleEvent::Log {
+            message: format!("{}は {}から {}を 奪い取った！", attacker.name, target.name, item_id),
+            meta,
+        },
+    ]
+}
+
 fn apply_consume_item(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     let Some(target) = get_active_creature(state, &target_id) else {
         return Vec::new();
     };
     if !has_item(target) {
-        return vec![BattleEvent::Log {
-            message: format!("{}は 道具を持っていない！", target.name),
-            meta: Map::new(),
-        }];
+        return vec![BattleEvent::log_with(LogCategory::Move, format!("{}は 道具を持っていない！", target.name))];
     }
     let item_id = get_item_id(target).unwrap_or_else(|| "item".to_string());
     let mut events = vec![
@@ -756,7 +1657,7 @@ fn apply_consume_item(state: &BattleState, effect: &Effect, ctx: &mut EffectCont
             meta: Map::new(),
         },
     ];
-    if effect.data.get("markBerryConsumed").and_then(|v| v.as_bool()).unwrap_or(false)
+    if field(effect, "markBerryConsumed").and_then(|v| v.as_bool()).unwrap_or(false)
         || item_id.contains("berry")
     {
         events.push(BattleEvent::ApplyStatus {
@@ -768,10 +1669,7 @@ fn apply_consume_item(state: &BattleState, effect: &Effect, ctx: &mut EffectCont
             meta: Map::new(),
         });
     }
-    events.push(BattleEvent::Log {
-        message: format!("{}の {}が 発動した！", target.name, item_id),
-        meta: Map::new(),
-    });
+    events.push(BattleEvent::log_with(LogCategory::Status, format!("{}の {}が 発動した！", target.name, item_id)));
     events
 }
 
@@ -783,47 +1681,49 @@ fn apply_ohko(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>)
         return Vec::new();
     };
 
-    if effect.data.get("respectTypeImmunity").and_then(|v| v.as_bool()).unwrap_or(true)
+    if field(effect, "respectTypeImmunity").and_then(|v| v.as_bool()).unwrap_or(true)
         && !ctx.ignore_immunity
     {
         if let Some(move_type) = ctx.move_data.and_then(|m| m.move_type.as_deref()) {
             if ctx.type_chart.effectiveness(move_type, &target.types) == 0.0 {
                 return vec![BattleEvent::Log {
                     message: "しかし 効かないようだ……".to_string(),
-                    meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+                    meta: outcome_meta(ctx, "noEffect", None),
                 }];
             }
         }
     }
 
-    if let Some(Value::Array(immune_types)) = effect.data.get("immuneTypes") {
+    if let Some(Value::Array(immune_types)) = field(effect, "immuneTypes") {
         if immune_types.iter().any(|t| t.as_str().map(|s| target.types.iter().any(|ty| ty == s)).unwrap_or(false)) {
             return vec![BattleEvent::Log {
                 message: format!("{}は {}には 効かないようだ……", target.name, move_name(ctx.move_data, effect)),
-                meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+                meta: outcome_meta(ctx, "noEffect", None),
             }];
         }
     }
 
-    if effect.data.get("failIfTargetHigherLevel").and_then(|v| v.as_bool()).unwrap_or(true)
+    if field(effect, "failIfTargetHigherLevel").and_then(|v| v.as_bool()).unwrap_or(true)
         && attacker.level < target.level
     {
         return vec![BattleEvent::Log {
             message: format!("{}には 効かないようだ……", move_name(ctx.move_data, effect)),
-            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+            meta: outcome_meta(ctx, "failed", Some("target_higher_level")),
         }];
     }
 
-    let base_accuracy = effect
-        .data
-        .get("baseAccuracy")
-        .and_then(|v| v.as_f64())
-        .unwrap_or(0.3);
+    let mut base_accuracy = field(effect, "baseAccuracy").and_then(|v| v.as_f64()).unwrap_or(0.3);
+    // ぜったいれいど: 決まったタイプ以外が使うと命中が下がる
+    if let Some(required_type) = field(effect, "requiredType").and_then(|v| v.as_str()) {
+        if !attacker.types.iter().any(|t| t == required_type) {
+            base_accuracy = field(effect, "nonMatchingTypeAccuracy").and_then(|v| v.as_f64()).unwrap_or(base_accuracy);
+        }
+    }
     let mut accuracy = base_accuracy;
-    if effect.data.get("levelScaling").and_then(|v| v.as_bool()).unwrap_or(true) {
+    if field(effect, "levelScaling").and_then(|v| v.as_bool()).unwrap_or(true) {
         accuracy += (attacker.level as f64 - target.level as f64) / 100.0;
     }
-    accuracy = accuracy.clamp(0.0, 1.0);
+    accuracy = stage_adjusted_accuracy(accuracy.clamp(0.0, 1.0), attacker, target);
 
     let move_category = get_move_category(ctx.move_data);
     let accuracy = run_ability_value_hook(
@@ -833,7 +1733,7 @@ fn apply_ohko(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>)
         accuracy as f32,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: move_category.as_deref(),
+            category: move_category,
             target: Some(target),
             weather: None,
             turn: ctx.turn,
@@ -844,15 +1744,12 @@ fn apply_ohko(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>)
     if (ctx.rng)() > accuracy {
         return vec![BattleEvent::Log {
             message: "しかし はずれた！".to_string(),
-            meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+            meta: outcome_meta(ctx, "missed", None),
         }];
     }
 
     vec![
-        BattleEvent::Log {
-            message: "一撃必殺！".to_string(),
-            meta: Map::new(),
-        },
+        BattleEvent::log_with(LogCategory::Damage, "一撃必殺！".to_string()),
         BattleEvent::Damage {
             target_id: ctx.target_player_id.clone(),
             amount: target.hp,
@@ -862,37 +1759,241 @@ fn apply_ohko(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>)
 }
 
 fn apply_cure_all_status(effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     vec![BattleEvent::CureAllStatus {
         target_id,
         meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
     }]
 }
 
+// リフレッシュ等: statusIds (省略時は状態異常すべて) を治す。対象の省略時は自分
+fn apply_cure_status(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = match field(effect, "target") {
+        None => ctx.attacker_player_id.clone(),
+        target => resolve_target(target, ctx),
+    };
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let status_ids: Option<Vec<&str>> = field(effect, "statusIds")
+        .and_then(|v| v.as_array())
+        .map(|ids| ids.iter().filter_map(|id| id.as_str()).collect());
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+
+    let mut events: Vec<BattleEvent> = target
+        .statuses
+        .iter()
+        .filter(|s| match &status_ids {
+            Some(ids) => ids.contains(&s.id.as_str()),
+            None => is_major_status(&s.id),
+        })
+        .map(|s| BattleEvent::RemoveStatus {
+            target_id: target_id.clone(),
+            status_id: s.id.clone(),
+            meta: meta.clone(),
+        })
+        .collect();
+    if events.is_empty() {
+        events.push(BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("nothing_to_cure")),
+        });
+    } else {
+        events.push(BattleEvent::Log {
+            message: format!("{}の 状態異常が 治った！", target.name),
+            meta,
+        });
+    }
+    events
+}
+
+/// ダブルバトルで target が all_adjacent (味方も含む) / all_adjacent_foes の効果が届く相手
+fn spread_targets(state: &BattleState, effect: &Effect, ctx: &EffectContext<'_>) -> Option<Vec<String>> {
+    // 全効果について呼ばれるので、target を持たない効果はここで外す
+    if !effect_spec(&effect.effect_type).is_some_and(|spec| spec.fields.iter().any(|(field, _)| *field == "target")) {
+        return None;
+    }
+    let include_ally = match field(effect, "target").and_then(|v| v.as_str()) {
+        Some("all_adjacent") => true,
+        Some("all_adjacent_foes") => false,
+        _ => return None,
+    };
+    if !is_doubles(state) {
+        return None;
+    }
+    let (side_id, _) = split_battler_id(&ctx.attacker_player_id);
+    let targets = active_battler_ids(state)
+        .into_iter()
+        .filter(|id| *id != ctx.attacker_player_id)
+        .filter(|id| include_ally || split_battler_id(id).0 != side_id)
+        .filter(|id| get_active_creature(state, id).is_some_and(|c| c.hp > 0))
+        .collect();
+    Some(targets)
+}
+
+// 範囲技: 1体ずつ target に差し替えて適用する。2体以上に当たるときはダメージに rules.spread_multiplier がかかる
+fn apply_spread(state: &BattleState, effect: &Effect, targets: &[String], ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let mut single = effect.clone();
+    single.data.insert("target".to_string(), Value::String("target".to_string()));
+    if targets.len() > 1 {
+        single.data.insert("spreadHit".to_string(), Value::Bool(true));
+    }
+    let original_target = ctx.target_player_id.clone();
+    let mut events = Vec::new();
+    for target_id in targets {
+        ctx.target_player_id = target_id.clone();
+        events.extend(apply_effect(state, &single, ctx));
+    }
+    ctx.target_player_id = original_target;
+    events
+}
+
+// 場に出ている creatureType タイプのポケモン全員に steps を適用する (たがやす / フラワーガード)
+fn apply_for_each_of_type(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some(creature_type) = field(effect, "creatureType").and_then(|v| v.as_str()) else {
+        return Vec::new();
+    };
+    let grounded_only = field(effect, "groundedOnly").and_then(|v| v.as_bool()).unwrap_or(false);
+    let target_ids: Vec<String> = active_battler_ids(state)
+        .into_iter()
+        .filter(|id| {
+            get_active_creature(state, id).is_some_and(|c| {
+                c.hp > 0 && c.types.iter().any(|t| t == creature_type) && (!grounded_only || is_grounded(c))
+            })
+        })
+        .collect();
+    if target_ids.is_empty() {
+        return vec![BattleEvent::Log {
+            message: "しかし うまく 決まらなかった！".to_string(),
+            meta: outcome_meta(ctx, "failed", Some("no_matching_type")),
+        }];
+    }
+
+    let steps = steps_from_value(field(effect, "steps"));
+    let original_target = ctx.target_player_id.clone();
+    let mut events = Vec::new();
+    for target_id in target_ids {
+        ctx.target_player_id = target_id;
+        events.extend(apply_effects(state, &steps, ctx));
+    }
+    ctx.target_player_id = original_target;
+    events
+}
+
+// タイプを types に置き換えるか removeTypes を取り除く。turnOnly ならターン終了時に元へ戻す (はねやすめ)
+fn apply_set_type(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let target_id = match field(effect, "target") {
+        None => ctx.attacker_player_id.clone(),
+        target => resolve_target(target, ctx),
+    };
+    let Some(target) = get_active_creature(state, &target_id) else {
+        return Vec::new();
+    };
+    let list = |key: &str| -> Option<Vec<String>> {
+        field(effect, key)
+            .and_then(|v| v.as_array())
+            .map(|ids| ids.iter().filter_map(|id| id.as_str().map(|s| s.to_string())).collect())
+    };
+    let types = match (list("types"), list("removeTypes")) {
+        (Some(types), _) => types,
+        (None, Some(removed)) => target.types.iter().filter(|t| !removed.contains(t)).cloned().collect(),
+        (None, None) => return Vec::new(),
+    };
+    if types == target.types {
+        return Vec::new();
+    }
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+
+    let mut events = Vec::new();
+    if field(effect, "turnOnly").and_then(|v| v.as_bool()).unwrap_or(false) {
+        let mut data = HashMap::new();
+        data.insert(
+            "originalTypes".to_string(),
+            Value::Array(target.types.iter().cloned().map(Value::String).collect()),
+        );
+        events.push(BattleEvent::ApplyStatus {
+            target_id: target_id.clone(),
+            status_id: "type_change".to_string(),
+            duration: None,
+            stack: false,
+            data,
+            meta: meta.clone(),
+        });
+    } else {
+        events.push(BattleEvent::Log {
+            message: format!("{}は {}タイプに 変化した！", target.name, types.join("・")),
+            meta: meta.clone(),
+        });
+    }
+    events.push(BattleEvent::SetType { target_id, types, meta });
+    events
+}
+
+// アロマセラピー・いやしのすず: 控えも含めて状態異常を治す。音の技は ぼうおんの仲間に届かない
+fn apply_cure_team_status(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let Some((player, user_slot)) = find_battler(&state.players, &ctx.attacker_player_id) else {
+        return Vec::new();
+    };
+    let is_sound = ctx.move_data.is_some_and(|m| m.tags.iter().any(|t| t == "sound"));
+    let slots = player
+        .team
+        .iter()
+        .enumerate()
+        .filter(|(idx, c)| !(is_sound && *idx != user_slot && c.ability.as_deref() == Some("soundproof")))
+        .map(|(idx, _)| idx)
+        .collect();
+    vec![BattleEvent::CureTeamStatus {
+        target_id: ctx.attacker_player_id.clone(),
+        slots,
+        meta: meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id)),
+    }]
+}
+
+/// コートチェンジ。自分の陣営と相手の陣営の場の効果を、層の数や残りターンごと入れ替える
+fn apply_swap_side_effects(state: &BattleState, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
+    let user_side = split_battler_id(&ctx.attacker_player_id).0;
+    let Some(opponent) = state.players.iter().find(|p| p.id != user_side) else {
+        return Vec::new();
+    };
+    let user_name = get_active_creature(state, &ctx.attacker_player_id)
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| user_side.to_string());
+    let meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    vec![
+        BattleEvent::SwapSideEffects {
+            side_a: user_side.to_string(),
+            side_b: opponent.id.clone(),
+            meta: meta.clone(),
+        },
+        BattleEvent::Log {
+            message: format!("{}は おたがいの 場の 効果を 入れ替えた！", user_name),
+            meta,
+        },
+    ]
+}
+
 fn apply_self_switch(ctx: &EffectContext<'_>) -> Vec<BattleEvent> {
     apply_pending_switch(&ctx.attacker_player_id, ctx)
 }
 
 fn apply_force_switch(state: &BattleState, effect: &Effect, ctx: &mut EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
     
     // Find the player being forced to switch
-    let Some(player) = state.players.iter().find(|p| p.id == target_id) else {
+    let Some((player, _)) = find_battler(&state.players, &target_id) else {
         return Vec::new();
     };
     
     // Collect available slots (not active, HP > 0)
+    let active_slots = player.active_slots();
     let available_slots: Vec<usize> = player.team.iter().enumerate()
-        .filter(|(i, c)| *i != player.active_slot && c.hp > 0)
+        .filter(|(i, c)| !active_slots.contains(i) && c.hp > 0)
         .map(|(i, _)| i)
         .collect();
     
     if available_slots.is_empty() {
         // No Pokémon to switch to
-        return vec![BattleEvent::Log {
-            message: format!("{} has no Pokémon to switch to!", player.name),
-            meta: Map::new(),
-        }];
+        return vec![BattleEvent::log_with(LogCategory::System, format!("{} has no Pokémon to switch to!", player.name))];
     }
     
     // Randomly select from available slots
@@ -921,10 +2022,10 @@ fn apply_pending_switch(target_id: &str, ctx: &EffectContext<'_>) -> Vec<BattleE
 }
 
 fn apply_lock_move(state: &BattleState, effect: &Effect, ctx: &EffectContext<'_>) -> Vec<BattleEvent> {
-    let target_id = resolve_target(effect.data.get("target"), ctx);
-    let duration = value_i32(effect.data.get("duration"), state, ctx);
+    let target_id = resolve_target(field(effect, "target"), ctx);
+    let duration = value_i32(field(effect, "duration"), state, ctx);
     let mut data = HashMap::new();
-    if let Some(Value::Object(raw)) = effect.data.get("data") {
+    if let Some(Value::Object(raw)) = field(effect, "data") {
         for (k, v) in raw {
             data.insert(k.clone(), v.clone());
         }
@@ -946,7 +2047,9 @@ fn apply_run_away() -> Vec<BattleEvent> {
 fn resolve_target(value: Option<&Value>, ctx: &EffectContext<'_>) -> String {
     match value.and_then(|v| v.as_str()) {
         Some("self") => ctx.attacker_player_id.clone(),
-        Some("all") => ctx.target_player_id.clone(),
+        Some("ally") => ally_battler_id(&ctx.attacker_player_id),
+        // ダブルバトルでは apply_spread が1体ずつに分けてから呼ぶ
+        Some("all") | Some("all_adjacent") | Some("all_adjacent_foes") => ctx.target_player_id.clone(),
         Some("target") | None => ctx.target_player_id.clone(),
         Some(other) => other.to_string(),
     }
@@ -1055,6 +2158,9 @@ fn resolve_variable(raw: &str, state: &BattleState, ctx: &EffectContext<'_>) ->
         "user.max_hp" => get_active_creature(state, &ctx.attacker_player_id).map(|c| c.max_hp as f64),
         "target.hp" => get_active_creature(state, &ctx.target_player_id).map(|c| c.hp as f64),
         "target.max_hp" => get_active_creature(state, &ctx.target_player_id).map(|c| c.max_hp as f64),
+        // おはかまいり: 自分の側で倒れている手持ちの数
+        "user.fainted_allies" => find_battler(&state.players, &ctx.attacker_player_id)
+            .map(|(player, _)| player.team.iter().filter(|c| c.hp <= 0).count() as f64),
         "damage" | "last_damage" => ctx.last_damage.map(|d| d as f64),
         _ => None,
     }
@@ -1074,26 +2180,14 @@ fn move_name(move_data: Option<&MoveData>, effect: &Effect) -> String {
     if let Some(name) = move_data.and_then(|m| m.name.clone()) {
         return name;
     }
-    effect
-        .data
-        .get("name")
+    field(effect, "name")
         .and_then(|v| v.as_str())
         .unwrap_or("move")
         .to_string()
 }
 
-fn get_move_category(move_data: Option<&MoveData>) -> Option<String> {
-    if let Some(move_data) = move_data {
-        if let Some(cat) = move_data.category.clone() {
-            return Some(cat);
-        }
-        let has_damage = move_data
-            .steps
-            .iter()
-            .any(|effect| effect.effect_type == "damage");
-        return Some(if has_damage { "physical" } else { "status" }.to_string());
-    }
-    None
+fn get_move_category(move_data: Option<&MoveData>) -> Option<MoveCategory> {
+    move_data.map(MoveData::resolved_category)
 }
 
 fn apply_modify_damage(
@@ -1102,7 +2196,7 @@ fn apply_modify_damage(
     state: &BattleState,
     ctx: &EffectContext<'_>,
 ) {
-    let multiplier = value_f64(effect.data.get("multiplier"), state, ctx).unwrap_or(1.0);
+    let multiplier = value_f64(field(effect, "multiplier"), state, ctx).unwrap_or(1.0);
     if multiplier == 1.0 {
         return;
     }
@@ -1121,8 +2215,8 @@ fn apply_force_crit(
     state: &BattleState,
     ctx: &EffectContext<'_>,
 ) {
-    let multiplier = value_f64(effect.data.get("multiplier"), state, ctx)
-        .or_else(|| value_f64(effect.data.get("mult"), state, ctx))
+    let multiplier = value_f64(field(effect, "multiplier"), state, ctx)
+        .or_else(|| value_f64(field(effect, "mult"), state, ctx))
         .unwrap_or(1.5);
     for event in events.iter_mut().rev() {
         if let BattleEvent::Damage { amount, .. } = event {
@@ -1192,10 +2286,31 @@ fn apply_meta_flags(events: &mut [BattleEvent], ctx: &EffectContext<'_>) {
     }
 }
 
+/// 攻撃を受け止める HP。みがわりがあれば みがわりの残り HP
+fn hp_absorbing_hit(target: &CreatureState, target_id: &str, ctx: &EffectContext<'_>) -> i32 {
+    if ctx.bypass_substitute || target_id == ctx.attacker_player_id {
+        return target.hp;
+    }
+    target
+        .statuses
+        .iter()
+        .find(|s| s.id == "substitute")
+        .map(|sub| {
+            sub.data
+                .get("hp")
+                .and_then(|v| v.as_i64())
+                .map(|hp| hp as i32)
+                .unwrap_or_else(|| substitute_hp_from_max(target.max_hp))
+        })
+        .unwrap_or(target.hp)
+}
+
+/// 直前のダメージ量を記録する。`hpBefore` があれば相手の残り HP を超えた分は数えない (吸収技の回復量に使う)
 fn update_last_damage_from_events(ctx: &mut EffectContext<'_>, events: &[BattleEvent]) {
     for event in events.iter().rev() {
-        if let BattleEvent::Damage { amount, .. } = event {
-            ctx.last_damage = Some(*amount);
+        if let BattleEvent::Damage { amount, meta, .. } = event {
+            let hp_before = meta.get("hpBefore").and_then(|v| v.as_i64()).map(|hp| hp as i32);
+            ctx.last_damage = Some(hp_before.map_or(*amount, |hp| (*amount).min(hp.max(0))));
             break;
         }
     }
@@ -1209,12 +2324,21 @@ fn event_meta_mut(event: &mut BattleEvent) -> Option<&mut Map<String, Value>> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SwapSideEffects { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. }
-        | BattleEvent::RandomMove { meta, .. } => Some(meta),
+        | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. } => Some(meta),
         _ => None,
     }
 }
@@ -1270,15 +2394,31 @@ fn evaluate_condition(state: &BattleState, cond: Option<&Value>, ctx: &EffectCon
 }
 
 fn weather_has_any(state: &BattleState, ids: &[&str]) -> bool {
+    if crate::core::abilities::weather_suppressed(state) {
+        return false;
+    }
     state.field.global.iter().any(|e| ids.contains(&e.id.as_str()))
 }
 
-fn compute_speed(state: &BattleState, player_id: &str, turn: u32) -> f32 {
+/// 行動順や素早さ比べで使う実効素早さ。ランク・おいかぜ・まひ・特性・持ち物を反映する
+pub fn effective_speed(state: &BattleState, player_id: &str, rules: &BattleRules) -> f32 {
     let Some(creature) = get_active_creature(state, player_id) else {
         return 0.0;
     };
-    let stage = creature.stages.spe;
-    let mut speed = creature.speed as f32 * stage_multiplier(stage);
+    let mut speed = creature.speed as f32 * stage_multiplier(creature.stages.spe);
+    let side_tailwind = state
+        .field
+        .sides
+        .get(split_battler_id(player_id).0)
+        .map(|effects| effects.iter().any(|effect| effect.id == "tailwind"))
+        .unwrap_or(false);
+    let global_tailwind = state.field.global.iter().any(|effect| effect.id == "tailwind");
+    if side_tailwind || global_tailwind {
+        speed *= 2.0;
+    }
+    if creature.statuses.iter().any(|s| s.id == "paralysis") {
+        speed *= rules.paralysis_speed_multiplier;
+    }
     let weather = crate::core::abilities::get_weather(state);
     speed = run_ability_value_hook(
         state,
@@ -1289,30 +2429,64 @@ fn compute_speed(state: &BattleState, player_id: &str, turn: u32) -> f32 {
             move_data: None,
             category: None,
             target: None,
-            weather: weather.as_ref().map(|w| match w {
-                WeatherKind::Sun => "sun",
-                WeatherKind::Rain => "rain",
-            }),
-            turn,
+            weather: weather.as_ref().map(WeatherKind::id),
+            turn: state.turn,
             stages: None,
         },
     );
-    speed
+    run_item_value_hook(state, player_id, "onModifySpeed", speed)
 }
 
-fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &str, ctx: &mut EffectContext<'_>, is_secondary_hit: bool) -> (i32, bool) {
-    let Some(attacker) = get_active_creature(state, attacker_id) else {
-        return (0, false);
-    };
-    let Some(target) = get_active_creature(state, target_id) else {
-        return (0, false);
-    };
-    let power = power.max(0) as f32;
-    if power <= 0.0 {
-        return (0, false);
+/// 技の結果（外れ・効果なし・失敗）を battle 側で拾えるようにログの meta に載せる。
+fn outcome_meta(ctx: &EffectContext<'_>, outcome: &str, reason: Option<&str>) -> Map<String, Value> {
+    let mut meta = meta_with_move_source(ctx.move_data.map(|m| m.id.as_str()), Some(&ctx.attacker_player_id));
+    meta.insert("category".to_string(), Value::String(LogCategory::Move.as_str().to_string()));
+    meta.insert("outcome".to_string(), Value::String(outcome.to_string()));
+    if let Some(reason) = reason {
+        meta.insert("reason".to_string(), Value::String(reason.to_string()));
+    }
+    meta
+}
+
+/// 相性とタイプ一致に使う技のタイプ。`typeless` タグの技 (わるあがき) はタイプを持たない
+fn damage_move_type(move_data: Option<&MoveData>) -> Option<&str> {
+    let move_data = move_data?;
+    if move_data.tags.iter().any(|tag| tag == "typeless") {
+        return None;
+    }
+    move_data.move_type.as_deref()
+}
+
+/// タイプ相性（じめん技は 撃ち落とされた ひこうタイプにも当たり、ふゆうには当たらない）
+fn type_effectiveness(type_chart: &TypeChart, move_type: &str, target: &CreatureState) -> f32 {
+    if move_type == "ground" {
+        if target.statuses.iter().any(|s| s.id == "grounded") {
+            let types: Vec<String> = target.types.iter().filter(|t| *t != "flying").cloned().collect();
+            return type_chart.effectiveness(move_type, &types);
+        }
+        if !is_grounded(target) && target.ability.as_deref() == Some("levitate") {
+            return 0.0;
+        }
     }
+    type_chart.effectiveness(move_type, &target.types)
+}
+
+/// 技が `target` に与える相性倍率。タイプのない技 (わるあがき) は等倍
+pub(crate) fn move_effectiveness(type_chart: &TypeChart, move_data: &MoveData, target: &CreatureState) -> f32 {
+    damage_move_type(Some(move_data)).map_or(1.0, |move_type| type_effectiveness(type_chart, move_type, target))
+}
+
+/// ふしぎなまもり: 効果抜群の技しか受けない
+fn wonder_guard_blocks(type_chart: &TypeChart, move_type: &str, target: &CreatureState) -> bool {
+    target.ability.as_deref() == Some("wonder_guard") && type_effectiveness(type_chart, move_type, target) <= 1.0
+}
 
-    let category = get_move_category(ctx.move_data).unwrap_or_else(|| "physical".to_string());
+/// 技の急所率。特性・道具の補正とルールの急所テーブルを通す
+pub(crate) fn crit_chance(state: &BattleState, attacker_id: &str, target: &CreatureState, ctx: &EffectContext<'_>) -> f64 {
+    let Some(attacker) = get_active_creature(state, attacker_id) else {
+        return 0.0;
+    };
+    let category = get_move_category(ctx.move_data).unwrap_or(MoveCategory::Physical);
     let mut crit_stage = ctx.move_data.and_then(|m| m.crit_rate).unwrap_or(0) as f32;
     crit_stage += attacker.stages.crit as f32;
     crit_stage = run_ability_value_hook(
@@ -1322,34 +2496,46 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         crit_stage,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: Some(&category),
+            category: Some(category),
             target: Some(target),
             weather: None,
             turn: ctx.turn,
             stages: None,
         },
     );
-    // 急所ランクの確率設定
-    // ランク0: 1/24 (~4.17%)
-    // ランク1: 1/8 (12.5%)
-    // ランク2: 1/2 (50%)
-    // ランク3+: 100%
-    let crit_chance = if crit_stage <= 0.0 {
-        1.0 / 24.0
-    } else if crit_stage <= 1.0 {
-        1.0 / 8.0
-    } else if crit_stage <= 2.0 {
-        1.0 / 2.0
-    } else {
-        1.0
+    crit_stage = run_item_value_hook(state, attacker_id, "onModifyCritChance", crit_stage);
+    ctx.rules.crit_chance(crit_stage)
+}
+
+/// 1回分のダメージと急所だったか。`crit` が None なら急所を乱数で決め、Some なら その結果に固定する
+/// 技の damage に `useDefensiveStat` があれば、分類によらず防御でダメージを受ける
+fn uses_defensive_stat(move_data: Option<&MoveData>) -> bool {
+    move_data.is_some_and(|m| {
+        m.steps.iter().any(|step| {
+            step.effect_type == "damage" && field(step, "useDefensiveStat").and_then(|v| v.as_bool()).unwrap_or(false)
+        })
+    })
+}
+
+pub(crate) fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &str, ctx: &mut EffectContext<'_>, crit: Option<bool>) -> (i32, bool) {
+    let Some(attacker) = get_active_creature(state, attacker_id) else {
+        return (0, false);
     };
-    
-    let is_crit = if is_secondary_hit {
-        false
-    } else if crit_chance >= 1.0 {
-        true
-    } else {
-        (ctx.rng)() < crit_chance
+    let Some(target) = get_active_creature(state, target_id) else {
+        return (0, false);
+    };
+    let power = power.max(0) as f32;
+    if power <= 0.0 {
+        return (0, false);
+    }
+
+    let category = get_move_category(ctx.move_data).unwrap_or(MoveCategory::Physical);
+    let is_crit = match crit {
+        Some(forced) => forced,
+        None => {
+            let crit_chance = crit_chance(state, attacker_id, target, ctx);
+            crit_chance >= 1.0 || (ctx.rng)() < crit_chance
+        }
     };
 
     let mut move_power = run_ability_value_hook(
@@ -1359,7 +2545,7 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         power,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: Some(&category),
+            category: Some(category),
             target: Some(target),
             weather: None,
             turn: ctx.turn,
@@ -1367,6 +2553,16 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         },
     );
 
+    // てだすけ: 同じターンに出す技の威力1.5倍
+    if attacker.volatile_data.get("helpingHand").and_then(|v| v.as_u64()) == Some(ctx.turn as u64) {
+        move_power *= 1.5;
+    }
+    // じゅうでん: 次のでんき技の威力2倍
+    if is_charged(attacker) && damage_move_type(ctx.move_data) == Some("electric") {
+        move_power *= 2.0;
+    }
+    move_power = run_item_value_hook(state, attacker_id, "onModifyPower", move_power);
+
     move_power = run_ability_value_hook(
         state,
         target_id,
@@ -1374,7 +2570,7 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         move_power,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: Some(&category),
+            category: Some(category),
             target: Some(attacker),
             weather: None,
             turn: ctx.turn,
@@ -1382,24 +2578,26 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         },
     );
 
-    let (offense_key, defense_key, stage_key_offense, stage_key_defense) = if category == "special" {
+    let (offense_key, mut defense_key, stage_key_offense, mut stage_key_defense) = if category == MoveCategory::Special {
         (attacker.sp_attack, target.sp_defense, attacker.stages.spa, target.stages.spd)
     } else {
         (attacker.attack, target.defense, attacker.stages.atk, target.stages.def)
     };
+    // サイコショック: 特殊技でも相手の防御で受ける
+    if uses_defensive_stat(ctx.move_data) {
+        defense_key = target.defense;
+        stage_key_defense = target.stages.def;
+    }
 
     let mut atk_stage = stage_key_offense;
     let mut def_stage = stage_key_defense;
-    
+
     // 急所の場合:
     // - 攻撃側の攻撃/特攻マイナスランクを無視
     // - 防御側の防御/特防プラスランクを無視
-    if is_crit && atk_stage < 0 {
-        atk_stage = 0;
-    }
-    // 急所の場合、相手の防御・特防上昇ランクを無視（0として扱う）
-    if is_crit && def_stage > 0 {
-        def_stage = 0;
+    if is_crit {
+        atk_stage = atk_stage.max(0);
+        def_stage = def_stage.min(0);
     }
 
     if attacker.ability.as_deref() == Some("unaware") {
@@ -1419,7 +2617,7 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         attack,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: Some(&category),
+            category: Some(category),
             target: Some(target),
             weather: None,
             turn: ctx.turn,
@@ -1427,6 +2625,9 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         },
     );
 
+    let offense_hook = if category == MoveCategory::Special { "onModifySpA" } else { "onModifyAtk" };
+    let attack = run_item_value_hook(state, attacker_id, offense_hook, attack);
+
     let defense = run_ability_value_hook(
         state,
         target_id,
@@ -1434,7 +2635,7 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
         defense,
         AbilityValueContext {
             move_data: ctx.move_data,
-            category: Some(&category),
+            category: Some(category),
             target: Some(attacker),
             weather: None,
             turn: ctx.turn,
@@ -1449,11 +2650,20 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
     let roll = (85 + roll_index) as f32 / 100.0;
 
     let mut modifier = 1.0;
-    if let Some(move_type) = ctx.move_data.and_then(|m| m.move_type.as_deref()) {
+    if let Some(move_type) = damage_move_type(ctx.move_data) {
         if attacker.types.iter().any(|t| t.eq_ignore_ascii_case(move_type)) {
             modifier *= 1.5;
         }
-        let mut effectiveness = ctx.type_chart.effectiveness(move_type, &target.types);
+        // 晴れはほのお1.5倍・みず半減、雨はその逆。ノーてんき・エアロックがいれば get_weather が None を返す
+        match (get_weather(state), move_type) {
+            (Some(WeatherKind::Sun), "fire") | (Some(WeatherKind::Rain), "water") => modifier *= 1.5,
+            (Some(WeatherKind::Sun), "water") | (Some(WeatherKind::Rain), "fire") => modifier *= 0.5,
+            _ => {}
+        }
+        let mut effectiveness = type_effectiveness(ctx.type_chart, move_type, target);
+        if !ctx.ignore_immunity && wonder_guard_blocks(ctx.type_chart, move_type, target) {
+            return (0, false);
+        }
         if effectiveness == 0.0 {
             if ctx.ignore_immunity {
                 effectiveness = 1.0;
@@ -1466,7 +2676,7 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
 
     // 壁補正（リフレクター/ひかりのかべ/オーロラベール）
     // まず target 側の side 効果を参照し、無ければ global も参照する。
-    let target_side_effects = state.field.sides.get(target_id);
+    let target_side_effects = state.field.sides.get(split_battler_id(target_id).0);
     let side_has = |status_id: &str| {
         target_side_effects
             .map(|effects| effects.iter().any(|e| e.id == status_id))
@@ -1475,16 +2685,16 @@ fn calc_damage(power: i32, state: &BattleState, attacker_id: &str, target_id: &s
     };
     if !is_crit {
         let has_aurora_veil = side_has("aurora_veil");
-        if category == "physical" && (side_has("reflect") || has_aurora_veil) {
+        if category == MoveCategory::Physical && (side_has("reflect") || has_aurora_veil) {
             modifier *= 0.5;
         }
-        if category == "special" && (side_has("light_screen") || has_aurora_veil) {
+        if category == MoveCategory::Special && (side_has("light_screen") || has_aurora_veil) {
             modifier *= 0.5;
         }
     }
 
     if is_crit {
-        modifier *= 1.5;
+        modifier *= ctx.rules.crit_multiplier;
     }
     let damage = (base * roll * modifier).floor() as i32;
     (damage.max(1), is_crit)
@@ -1504,7 +2714,7 @@ pub fn has_item(creature: &crate::core::state::CreatureState) -> bool {
         .any(|s| s.id == "item" || s.id == "berry")
 }
 
-fn get_item_id(creature: &crate::core::state::CreatureState) -> Option<String> {
+pub(crate) fn get_item_id(creature: &crate::core::state::CreatureState) -> Option<String> {
     if let Some(item) = &creature.item {
         return Some(item.clone());
     }
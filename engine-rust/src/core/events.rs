@@ -1,5 +1,7 @@
 use crate::core::abilities::{modify_stages_with_ability, run_ability_check_hook, AbilityCheckContext};
-use crate::core::state::{BattleState, Status, StatStages};
+use crate::core::items::run_item_check_hook;
+use crate::core::state::{BattleState, CreatureState, LogCategory, Status, StatStages};
+use crate::core::utils::{find_battler, find_battler_mut, get_active_creature, get_active_creature_mut, split_battler_id};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 
@@ -43,6 +45,12 @@ pub enum BattleEvent {
         show_event: bool,
         meta: Map<String, Value>,
     },
+    /// ランクを差分ではなく値そのもので書き換える (じこあんじ等)。能力が下がったとは扱わない
+    SetStages {
+        target_id: String,
+        stages: HashMap<String, i32>,
+        meta: Map<String, Value>,
+    },
     ClearStages {
         target_id: String,
         show_event: bool,
@@ -57,15 +65,18 @@ pub enum BattleEvent {
         target_id: String,
         meta: Map<String, Value>,
     },
+    /// `side` が指定されていれば その陣営の `field.sides` に、無ければ場全体 (`field.global`) に付ける
     ApplyFieldStatus {
         status_id: String,
         duration: Option<i32>,
         stack: bool,
+        side: Option<String>,
         data: HashMap<String, Value>,
         meta: Map<String, Value>,
     },
     RemoveFieldStatus {
         status_id: String,
+        side: Option<String>,
         meta: Map<String, Value>,
     },
     Switch {
@@ -76,11 +87,57 @@ pub enum BattleEvent {
         pool: String,
         meta: Map<String, Value>,
     },
+    /// 決まった技をその場で出す (オウムがえし・まねっこ)。PP は減らさない
+    CallMove {
+        move_id: String,
+        meta: Map<String, Value>,
+    },
+    /// 技の PP を `amount` 回復する。最大PPを超えないよう battle.rs が `amount` を切り詰めてから適用する
+    RestorePp {
+        target_id: String,
+        move_id: String,
+        amount: i32,
+        meta: Map<String, Value>,
+    },
     SetVolatile {
         target_id: String,
         key: String,
         value: Value,
     },
+    SetAbility {
+        target_id: String,
+        ability_id: String,
+        meta: Map<String, Value>,
+    },
+    /// 控えの `slot` にいる ひんしのポケモンを復活させる
+    Revive {
+        target_id: String,
+        slot: usize,
+        meta: Map<String, Value>,
+    },
+    /// 手持ちの `slots` にいるポケモンの状態異常を治す (控えも含む)
+    CureTeamStatus {
+        target_id: String,
+        slots: Vec<usize>,
+        meta: Map<String, Value>,
+    },
+    SetType {
+        target_id: String,
+        types: Vec<String>,
+        meta: Map<String, Value>,
+    },
+    /// バトルスイッチ: `forme` ("blade" / "shield") に変わり、攻撃と防御の能力値を入れ替える
+    StanceChange {
+        target_id: String,
+        forme: String,
+        meta: Map<String, Value>,
+    },
+    /// コートチェンジ: 2つの陣営の `field.sides` (まきびし・壁・おいかぜなど) を入れ替える
+    SwapSideEffects {
+        side_a: String,
+        side_b: String,
+        meta: Map<String, Value>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -110,6 +167,18 @@ impl Default for EventTransform {
     }
 }
 
+impl BattleEvent {
+    /// 種類 (`meta.category`) つきのログ
+    pub fn log_with(category: LogCategory, message: impl Into<String>) -> Self {
+        let mut meta = Map::new();
+        meta.insert("category".to_string(), Value::String(category.as_str().to_string()));
+        BattleEvent::Log {
+            message: message.into(),
+            meta,
+        }
+    }
+}
+
 pub fn event_type(event: &BattleEvent) -> &str {
     match event {
         BattleEvent::Log { .. } => "log",
@@ -118,6 +187,7 @@ pub fn event_type(event: &BattleEvent) -> &str {
         BattleEvent::RemoveStatus { .. } => "remove_status",
         BattleEvent::ReplaceStatus { .. } => "replace_status",
         BattleEvent::ModifyStage { .. } => "modify_stage",
+        BattleEvent::SetStages { .. } => "set_stages",
         BattleEvent::ClearStages { .. } => "clear_stages",
         BattleEvent::ResetStages { .. } => "reset_stages",
         BattleEvent::CureAllStatus { .. } => "cure_all_status",
@@ -125,28 +195,140 @@ pub fn event_type(event: &BattleEvent) -> &str {
         BattleEvent::RemoveFieldStatus { .. } => "remove_field_status",
         BattleEvent::Switch { .. } => "switch",
         BattleEvent::RandomMove { .. } => "random_move",
+        BattleEvent::CallMove { .. } => "call_move",
+        BattleEvent::RestorePp { .. } => "restore_pp",
         BattleEvent::SetVolatile { .. } => "set_volatile",
+        BattleEvent::SetAbility { .. } => "set_ability",
+        BattleEvent::Revive { .. } => "revive",
+        BattleEvent::CureTeamStatus { .. } => "cure_team_status",
+        BattleEvent::SetType { .. } => "set_type",
+        BattleEvent::StanceChange { .. } => "stance_change",
+        BattleEvent::SwapSideEffects { .. } => "swap_side_effects",
+    }
+}
+
+/// 交代しても残る状態異常 (やけど・どく・まひ・こおり・ねむり)
+pub fn is_major_status(status_id: &str) -> bool {
+    matches!(status_id, "burn" | "poison" | "toxic" | "paralysis" | "freeze" | "sleep")
+}
+
+/// もうどくのダメージ段階 (max_hp * n / 16) を持つ `status.data` のキー
+pub const TOXIC_COUNTER_KEY: &str = "toxicCounter";
+
+/// 状態を付けた時点の `status.data`。もうどくは付け直すたびに 1/16 からやり直す
+fn initial_status_data(status_id: &str, data: &HashMap<String, Value>) -> HashMap<String, Value> {
+    let mut data = data.clone();
+    if status_id == "toxic" {
+        data.insert(TOXIC_COUNTER_KEY.to_string(), Value::from(1));
+    }
+    data
+}
+
+/// こんじょう・ふしぎなうろこ・はやあしが見る「状態異常」。混乱などの一時的な状態は含めない
+pub fn has_major_status(creature: &CreatureState) -> bool {
+    creature.statuses.iter().any(|s| is_major_status(&s.id))
+}
+
+/// タイプによる状態異常・やどりぎのタネの無効。ふしょく持ちが相手なら どく・はがねにも毒が通る。
+/// タイプか特性で `status_id` を受け付けないか。`source_id` は状態を付けようとした側 (ふしょく判定用)
+pub fn is_status_immune(state: &BattleState, target_id: &str, status_id: &str, source_id: Option<&str>) -> bool {
+    has_type_status_immunity(state, target_id, status_id, source_id)
+        || run_ability_check_hook(
+            state,
+            target_id,
+            "onCheckStatusImmunity",
+            AbilityCheckContext {
+                status_id: Some(status_id),
+                r#type: None,
+                target_id: None,
+                action: None,
+            },
+            false,
+        )
+}
+
+/// がんじょう・きあいのタスキで ひんしのダメージを耐えられるか。
+/// Some(true) なら道具を使い切る。HP満タンかどうかは呼び出し側で見る
+fn lethal_hit_survival(state: &BattleState, target_id: &str) -> Option<bool> {
+    let sturdy = run_ability_check_hook(
+        state,
+        target_id,
+        "onSurviveLethalHit",
+        AbilityCheckContext {
+            status_id: None,
+            r#type: None,
+            target_id: None,
+            action: None,
+        },
+        false,
+    );
+    if sturdy {
+        Some(false)
+    } else {
+        run_item_check_hook(state, target_id, "onSurviveLethalHit", false).then_some(true)
+    }
+}
+
+fn has_type_status_immunity(state: &BattleState, target_id: &str, status_id: &str, source_id: Option<&str>) -> bool {
+    let Some(active) = get_active_creature(state, target_id) else {
+        return false;
+    };
+    let has_type = |type_id: &str| active.types.iter().any(|t| t == type_id);
+    match status_id {
+        "burn" => has_type("fire"),
+        "paralysis" => has_type("electric"),
+        "freeze" => has_type("ice"),
+        "leech_seed" => has_type("grass"),
+        "poison" | "toxic" => {
+            if !(has_type("poison") || has_type("steel")) {
+                return false;
+            }
+            let bypass = source_id.filter(|source| *source != target_id).is_some_and(|source| {
+                run_ability_check_hook(
+                    state,
+                    source,
+                    "onIgnoreStatusTypeImmunity",
+                    AbilityCheckContext {
+                        status_id: Some(status_id),
+                        r#type: None,
+                        target_id: Some(target_id),
+                        action: None,
+                    },
+                    false,
+                )
+            });
+            !bypass
+        }
+        _ => false,
     }
 }
 
 pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
     let mut next = state.clone();
     match event {
-        BattleEvent::Log { message, .. } => {
-            next.log.push(message.clone());
+        BattleEvent::Log { message, meta } => {
+            let category = meta_get_string(meta, "category")
+                .and_then(|category| LogCategory::parse(&category))
+                .unwrap_or_default();
+            next.push_log(category, message.clone());
         }
         BattleEvent::Damage {
             target_id, amount, ..
         } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            let mut grudge_target = None;
+            let source = event_meta(event).and_then(|meta| meta_get_string(meta, "source"));
+            let is_self = source.as_deref() == Some(target_id.as_str());
+            let survival = if source.is_some() && !is_self { lethal_hit_survival(state, target_id) } else { None };
+            let mut dealt = 0;
+            // 手持ちを借りている間は next に書けないので、ログはためておいて後で足す
+            let mut logs = Vec::new();
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     if *amount > 0 {
                         let meta = event_meta(event);
                         let bypass_substitute = meta
                             .and_then(|meta| meta_get_bool(meta, "bypassSubstitute"))
                             .unwrap_or(false);
-                        let source = meta.and_then(|meta| meta_get_string(meta, "source"));
-                        let is_self = source.as_deref() == Some(target_id.as_str());
                         if !bypass_substitute && !is_self {
                             if let Some(index) = active.statuses.iter().position(|s| s.id == "substitute") {
                                 let current = active.statuses[index]
@@ -156,31 +338,82 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
                                     .map(|v| v as i32)
                                     .unwrap_or_else(|| substitute_hp_from_max(active.max_hp));
                                 let remaining = current - *amount;
-                                if remaining > 0 {
+                                dealt = (*amount).min(current.max(0));
+                                let message = if remaining > 0 {
                                     active.statuses[index]
                                         .data
                                         .insert("hp".to_string(), Value::Number(remaining.into()));
-                                    next.log.push(format!("{}の みがわりが 攻撃を 受けた！", active.name));
+                                    format!("{}の みがわりが 攻撃を 受けた！", active.name)
                                 } else {
                                     active.statuses.remove(index);
-                                    next.log.push(format!("{}の みがわりは 壊れてしまった！", active.name));
+                                    format!("{}の みがわりは 壊れてしまった！", active.name)
+                                };
+                                next.push_log(LogCategory::Damage, message);
+                                if !is_self {
+                                    record_damage_dealt(&mut next, source.as_deref(), dealt);
                                 }
                                 return next;
                             }
                         }
                     }
-                    let new_hp = active.hp - *amount;
+                    // こらえる: 相手からの ひんしになるダメージは HP 1 で止める
+                    let endured = *amount >= active.hp
+                        && active.hp > 0
+                        && source.is_some()
+                        && !is_self
+                        && active.statuses.iter().any(|s| s.id == "endure");
+                    // がんじょう・きあいのタスキ: HP満タンからの 一撃は HP 1 で止める
+                    let hung_on = !endured
+                        && survival.is_some()
+                        && *amount >= active.hp
+                        && active.hp > 0
+                        && active.hp == active.max_hp;
+                    let applied = if endured || hung_on { active.hp - 1 } else { *amount };
+                    let new_hp = active.hp - applied;
+                    let hp_before = active.hp;
                     active.hp = new_hp.clamp(0, active.max_hp);
                     if *amount > 0 {
-                        next.log.push(format!("{}は {}ダメージ 受けた！", active.name, amount));
+                        dealt = hp_before - active.hp;
+                        active.last_damage_taken += dealt;
+                    }
+                    if *amount > 0 {
+                        logs.push((LogCategory::Damage, format!("{}は {}ダメージ 受けた！", active.name, applied)));
+                        if endured {
+                            logs.push((LogCategory::Damage, format!("{}は こらえた！", active.name)));
+                        }
+                        if hung_on && survival == Some(true) {
+                            active.item = None;
+                            active.statuses.retain(|s| s.id != "item");
+                            logs.push((LogCategory::Status, format!("{}は きあいのタスキで 持ちこたえた！", active.name)));
+                        } else if hung_on {
+                            logs.push((LogCategory::Ability, format!("{}は がんじょうで 持ちこたえた！", active.name)));
+                        }
                     } else if *amount < 0 {
-                        next.log.push(format!("{}の HPが {}回復した！", active.name, -amount));
+                        logs.push((LogCategory::Damage, format!("{}の HPが {}回復した！", active.name, -amount)));
                     } else {
-                        next.log.push(format!("{}には 効かないようだ……", active.name));
+                        logs.push((LogCategory::Damage, format!("{}には 効かないようだ……", active.name)));
+                    }
+                    // ほのお技を受けると こおりが とける
+                    let thaws = event_meta(event)
+                        .and_then(|meta| meta_get_bool(meta, "thawsTarget"))
+                        .unwrap_or(false);
+                    if thaws && *amount > 0 && active.hp > 0 && active.statuses.iter().any(|s| s.id == "freeze") {
+                        active.statuses.retain(|s| s.id != "freeze");
+                        logs.push((LogCategory::Status, format!("{}の こおりが とけた！", active.name)));
                     }
                     if active.hp <= 0 {
-                        next.log.push(format!("{}は たおれた！", active.name));
+                        logs.push((LogCategory::Faint, format!("{}は たおれた！", active.name)));
                         player.last_fainted_ability = active.ability.clone();
+                        // おんねん: 相手の技で倒れたら その技の PP を 0 にする
+                        if active.statuses.iter().any(|s| s.id == "grudge") {
+                            grudge_target = event_meta(event)
+                                .and_then(|meta| {
+                                    let move_id = meta_get_string(meta, "moveId")?;
+                                    let move_name = meta_get_string(meta, "moveName").unwrap_or_else(|| move_id.clone());
+                                    Some((meta_get_string(meta, "source")?, move_id, move_name))
+                                })
+                                .filter(|(source, _, _)| source != target_id);
+                        }
                         if !active.statuses.iter().any(|s| s.id == "pending_switch") {
                             active.statuses.push(Status {
                                 id: "pending_switch".to_string(),
@@ -191,6 +424,15 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
                     }
                 }
             }
+            for (category, message) in logs {
+                next.push_log(category, message);
+            }
+            if !is_self {
+                record_damage_dealt(&mut next, source.as_deref(), dealt);
+            }
+            if let Some((attacker_id, move_id, move_name)) = grudge_target {
+                drain_grudge_pp(&mut next, &attacker_id, &move_id, &move_name);
+            }
         }
         BattleEvent::ApplyStatus {
             target_id,
@@ -198,30 +440,20 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
             duration,
             stack,
             data,
-            ..
+            meta,
         } => {
-            if run_ability_check_hook(
-                &next,
-                target_id,
-                "onCheckStatusImmunity",
-                AbilityCheckContext {
-                    status_id: Some(status_id),
-                    r#type: None,
-                    target_id: None,
-                    action: None,
-                },
-                false,
-            ) {
-                if let Some(player) = next.players.iter().find(|p| p.id == *target_id) {
-                    if let Some(active) = player.team.get(player.active_slot) {
-                        next.log
-                            .push(format!("{}には {}は 効かない！", active.name, status_id));
-                    }
+            let source_id = meta_get_string(meta, "source");
+            if is_status_immune(&next, target_id, status_id, source_id.as_deref()) {
+                let message = find_battler(&next.players, target_id)
+                    .and_then(|(player, slot)| player.team.get(slot))
+                    .map(|active| format!("{}には {}は 効かない！", active.name, status_id));
+                if let Some(message) = message {
+                    next.push_log(LogCategory::Status, message);
                 }
                 return next;
             }
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     if status_id == "item" || status_id == "berry" {
                         if let Some(Value::String(item_id)) = data.get("itemId") {
                             active.item = Some(item_id.clone());
@@ -229,14 +461,15 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
                     }
                     if !stack {
                         if let Some(_existing) = active.statuses.iter().find(|s| s.id == *status_id) {
-                            next.log.push(format!("{}は すでに {}状態だ！", active.name, status_id));
+                            let message = format!("{}は すでに {}状態だ！", active.name, status_id);
+                            next.push_log(LogCategory::Status, message);
                             return next;
                         }
                     }
                     active.statuses.push(Status {
                         id: status_id.clone(),
                         remaining_turns: *duration,
-                        data: data.clone(),
+                        data: initial_status_data(status_id, data),
                     });
                 }
             }
@@ -244,8 +477,8 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
         BattleEvent::RemoveStatus {
             target_id, status_id, ..
         } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     active.statuses.retain(|s| s.id != *status_id);
                     if status_id == "item" || status_id == "berry" {
                         active.item = None;
@@ -261,8 +494,8 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
             data,
             ..
         } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     if !active.statuses.iter().any(|s| s.id == *from) {
                         return next;
                     }
@@ -270,7 +503,7 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
                     active.statuses.push(Status {
                         id: to.clone(),
                         remaining_turns: *duration,
-                        data: data.clone(),
+                        data: initial_status_data(to, data),
                     });
                 }
             }
@@ -283,8 +516,8 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
             ..
         } => {
             let adjusted = modify_stages_with_ability(&next, target_id, stages);
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     let mut changed = false;
                     for (key, delta) in adjusted {
                         let stage_ref = stage_ref_mut(&mut active.stages, &key);
@@ -305,16 +538,27 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
                 }
             }
         }
+        BattleEvent::SetStages { target_id, stages, .. } => {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
+                    for (key, value) in stages {
+                        if let Some(stage_ref) = stage_ref_mut(&mut active.stages, key) {
+                            *stage_ref = (*value).clamp(-6, 6);
+                        }
+                    }
+                }
+            }
+        }
         BattleEvent::ClearStages { target_id, .. } | BattleEvent::ResetStages { target_id, .. } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     active.stages = StatStages::default();
                 }
             }
         }
         BattleEvent::CureAllStatus { target_id, .. } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     active.statuses.clear();
                 }
             }
@@ -323,64 +567,208 @@ pub fn apply_event(state: &BattleState, event: &BattleEvent) -> BattleState {
             status_id,
             duration,
             stack,
+            side,
             data,
             ..
         } => {
+            let effects = match side {
+                Some(side) => next.field.sides.entry(side.clone()).or_default(),
+                None => &mut next.field.global,
+            };
             if !*stack {
-                next.field.global.retain(|e| e.id != *status_id);
+                effects.retain(|e| e.id != *status_id);
             }
-            next.field.global.push(crate::core::state::FieldEffect {
+            effects.push(crate::core::state::FieldEffect {
                 id: status_id.clone(),
                 remaining_turns: *duration,
                 data: data.clone(),
             });
         }
-        BattleEvent::RemoveFieldStatus { status_id, .. } => {
-            next.field.global.retain(|e| e.id != *status_id);
+        BattleEvent::RemoveFieldStatus { status_id, side, .. } => {
+            let effects = match side {
+                Some(side) => next.field.sides.get_mut(side),
+                None => Some(&mut next.field.global),
+            };
+            if let Some(effects) = effects {
+                effects.retain(|e| e.id != *status_id);
+            }
         }
         BattleEvent::Switch { player_id, slot } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *player_id) {
+            let (side_id, position) = split_battler_id(player_id);
+            if let Some((player, outgoing_slot)) = find_battler_mut(&mut next.players, player_id) {
                 if *slot < player.team.len() {
-                    if let Some(outgoing) = player.team.get_mut(player.active_slot) {
+                    if let Some(outgoing) = player.team.get_mut(outgoing_slot) {
                         outgoing.stages = StatStages::default();
+                        // ターン中だけのタイプ変化は 交代で元に戻る
+                        if let Some(original) = outgoing
+                            .statuses
+                            .iter()
+                            .find(|s| s.id == "type_change")
+                            .and_then(|s| s.data.get("originalTypes"))
+                            .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                        {
+                            outgoing.types = original;
+                        }
                         // Non-volatile statuses that persist on switch.
-                        let non_volatile = ["burn", "poison", "toxic", "paralysis", "freeze", "sleep"];
-                        outgoing.statuses.retain(|s| non_volatile.contains(&s.id.as_str()));
+                        outgoing.statuses.retain(|s| is_major_status(&s.id));
                         for status in &mut outgoing.statuses {
                             if status.id == "toxic" {
                                 // Toxic ramp resets when switching out.
-                                status.data.remove("counter");
+                                status.data.insert(TOXIC_COUNTER_KEY.to_string(), Value::from(1));
                             }
                         }
+                        // ブレードフォルムのまま引っ込むと シールドフォルムに戻る
+                        if stance_forme(outgoing) == "blade" {
+                            swap_stance_stats(outgoing);
+                        }
                         if let Some(original) = outgoing.ability_data.get("originalAbility").and_then(|v| v.as_str()) {
                             outgoing.ability = Some(original.to_string());
                         }
                         outgoing.ability_data.clear();
                         outgoing.volatile_data.clear();
                     }
-                    player.active_slot = *slot;
-                    if let Some(incoming) = player.team.get_mut(player.active_slot) {
+                    player.set_active_slot_at(position, *slot);
+                    if let Some(incoming) = player.team.get_mut(*slot) {
                         incoming.statuses.retain(|s| s.id != "pending_switch");
-                        next.log
-                            .push(format!("{}は {}を 繰り出した！", player.name, incoming.name));
+                        incoming.species_revealed = true;
+                        let message = format!("{}は {}を 繰り出した！", player.name, incoming.name);
+                        next.push_log(LogCategory::System, message);
+                    }
+                }
+            }
+            // 場を離れたポケモンが仕掛けた拘束は解除される
+            for other in next.players.iter_mut().filter(|p| p.id != side_id) {
+                for other_slot in other.active_slots() {
+                    if let Some(active) = other.team.get_mut(other_slot) {
+                        active.statuses.retain(|s| {
+                            !(matches!(s.id.as_str(), "trapped" | "bind")
+                                && s.data.get("sourceId").and_then(|v| v.as_str()) == Some(player_id.as_str()))
+                        });
                     }
                 }
             }
         }
-        BattleEvent::RandomMove { .. } => {
+        BattleEvent::RandomMove { .. } | BattleEvent::CallMove { .. } => {
             // Placeholder: move selection handled at action level.
         }
+        BattleEvent::RestorePp {
+            target_id,
+            move_id,
+            amount,
+            ..
+        } => {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                // 一度も使っていない技は PP が減っていないので そのまま
+                if let Some(pp) = player.team.get_mut(slot).and_then(|c| c.move_pp.get_mut(move_id)) {
+                    *pp += amount;
+                }
+            }
+        }
         BattleEvent::SetVolatile { target_id, key, value } => {
-            if let Some(player) = next.players.iter_mut().find(|p| p.id == *target_id) {
-                if let Some(active) = player.team.get_mut(player.active_slot) {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
                     active.volatile_data.insert(key.clone(), value.clone());
                 }
             }
         }
+        BattleEvent::SetAbility { target_id, ability_id, .. } => {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
+                    if active.ability.as_deref() == Some(ability_id.as_str()) {
+                        next.push_log(LogCategory::Move, "しかし うまく 決まらなかった！".to_string());
+                        return next;
+                    }
+                    // 交代時に元の特性へ戻すため保存しておく
+                    if !active.ability_data.contains_key("originalAbility") {
+                        active.ability_data.insert(
+                            "originalAbility".to_string(),
+                            Value::String(active.ability.clone().unwrap_or_default()),
+                        );
+                    }
+                    active.ability = Some(ability_id.clone());
+                    let message = format!("{}の 特性が {}に なった！", active.name, ability_id);
+                    next.push_log(LogCategory::Ability, message);
+                }
+            }
+        }
+        BattleEvent::CureTeamStatus { target_id, slots, .. } => {
+            let (side_id, _) = split_battler_id(target_id);
+            let mut cured = Vec::new();
+            if let Some(player) = next.players.iter_mut().find(|p| p.id == side_id) {
+                for slot in slots {
+                    let Some(creature) = player.team.get_mut(*slot) else {
+                        continue;
+                    };
+                    if creature.hp <= 0 || !has_major_status(creature) {
+                        continue;
+                    }
+                    creature.statuses.retain(|s| !is_major_status(&s.id));
+                    cured.push(creature.name.clone());
+                }
+            }
+            for name in cured {
+                next.push_log(LogCategory::Status, format!("{}の 状態異常が 治った！", name));
+            }
+        }
+        BattleEvent::SetType { target_id, types, .. } => {
+            if let Some(active) = get_active_creature_mut(&mut next, target_id) {
+                active.types = types.clone();
+            }
+        }
+        BattleEvent::StanceChange { target_id, forme, .. } => {
+            if let Some((player, slot)) = find_battler_mut(&mut next.players, target_id) {
+                if let Some(active) = player.team.get_mut(slot) {
+                    if stance_forme(active) != forme {
+                        swap_stance_stats(active);
+                        active.ability_data.insert("stanceForme".to_string(), Value::String(forme.clone()));
+                        let forme_name = if forme == "blade" { "ブレードフォルム" } else { "シールドフォルム" };
+                        let message = format!("{}は {}に チェンジ！", active.name, forme_name);
+                        next.push_log(LogCategory::Ability, message);
+                    }
+                }
+            }
+        }
+        BattleEvent::SwapSideEffects { side_a, side_b, .. } => {
+            let effects_a = next.field.sides.remove(side_a).unwrap_or_default();
+            let effects_b = next.field.sides.remove(side_b).unwrap_or_default();
+            if !effects_b.is_empty() {
+                next.field.sides.insert(side_a.clone(), effects_b);
+            }
+            if !effects_a.is_empty() {
+                next.field.sides.insert(side_b.clone(), effects_a);
+            }
+        }
+        BattleEvent::Revive { target_id, slot, .. } => {
+            let (side_id, _) = split_battler_id(target_id);
+            let creature = next
+                .players
+                .iter_mut()
+                .find(|p| p.id == side_id)
+                .and_then(|p| p.team.get_mut(*slot));
+            if let Some(creature) = creature.filter(|c| c.hp <= 0) {
+                creature.hp = (creature.max_hp / 2).max(1);
+                creature.statuses.clear();
+                let message = format!("{}は 元気を 取り戻した！", creature.name);
+                next.push_log(LogCategory::Status, message);
+            }
+        }
     }
     next
 }
 
+fn stance_forme(creature: &CreatureState) -> &str {
+    creature
+        .ability_data
+        .get("stanceForme")
+        .and_then(|v| v.as_str())
+        .unwrap_or("shield")
+}
+
+fn swap_stance_stats(creature: &mut CreatureState) {
+    std::mem::swap(&mut creature.attack, &mut creature.defense);
+    std::mem::swap(&mut creature.sp_attack, &mut creature.sp_defense);
+}
+
 fn stage_ref_mut<'a>(stages: &'a mut StatStages, key: &str) -> Option<&'a mut i32> {
     match key {
         "atk" => Some(&mut stages.atk),
@@ -418,7 +806,34 @@ pub fn meta_get_i32(meta: &Map<String, Value>, key: &str) -> Option<i32> {
     meta.get(key).and_then(|v| v.as_i64()).map(|v| v as i32)
 }
 
-fn substitute_hp_from_max(max_hp: i32) -> i32 {
+/// 攻撃側の `last_damage_dealt` に加算する。出どころが分からないダメージ (天気・状態異常など) は数えない
+fn record_damage_dealt(state: &mut BattleState, source_id: Option<&str>, dealt: i32) {
+    let Some(source_id) = source_id else {
+        return;
+    };
+    if dealt <= 0 {
+        return;
+    }
+    if let Some((player, slot)) = find_battler_mut(&mut state.players, source_id) {
+        if let Some(attacker) = player.team.get_mut(slot) {
+            attacker.last_damage_dealt += dealt;
+        }
+    }
+}
+
+fn drain_grudge_pp(state: &mut BattleState, attacker_id: &str, move_id: &str, move_name: &str) {
+    let Some((player, slot)) = find_battler_mut(&mut state.players, attacker_id) else {
+        return;
+    };
+    let Some(attacker) = player.team.get_mut(slot) else {
+        return;
+    };
+    attacker.move_pp.insert(move_id.to_string(), 0);
+    let name = attacker.name.clone();
+    state.push_log(LogCategory::Move, format!("{}の {}は おんねんで PPが 0に なった！", name, move_name));
+}
+
+pub(crate) fn substitute_hp_from_max(max_hp: i32) -> i32 {
     let hp = ((max_hp as f64) * 0.25).floor() as i32;
     hp.max(1)
 }
@@ -430,12 +845,21 @@ fn event_meta(event: &BattleEvent) -> Option<&Map<String, Value>> {
         | BattleEvent::RemoveStatus { meta, .. }
         | BattleEvent::ReplaceStatus { meta, .. }
         | BattleEvent::ModifyStage { meta, .. }
+        | BattleEvent::SetStages { meta, .. }
         | BattleEvent::ClearStages { meta, .. }
         | BattleEvent::ResetStages { meta, .. }
+        | BattleEvent::SetAbility { meta, .. }
+        | BattleEvent::Revive { meta, .. }
+        | BattleEvent::StanceChange { meta, .. }
+        | BattleEvent::SwapSideEffects { meta, .. }
+        | BattleEvent::SetType { meta, .. }
+        | BattleEvent::CureTeamStatus { meta, .. }
         | BattleEvent::CureAllStatus { meta, .. }
         | BattleEvent::ApplyFieldStatus { meta, .. }
         | BattleEvent::RemoveFieldStatus { meta, .. }
-        | BattleEvent::RandomMove { meta, .. } => Some(meta),
+        | BattleEvent::RandomMove { meta, .. }
+        | BattleEvent::CallMove { meta, .. }
+        | BattleEvent::RestorePp { meta, .. } => Some(meta),
         _ => None,
     }
 }
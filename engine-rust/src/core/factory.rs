@@ -146,6 +146,11 @@ pub fn create_creature(
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: species.weight,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack,
         defense,
         sp_attack,
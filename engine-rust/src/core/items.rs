@@ -0,0 +1,107 @@
+use crate::core::abilities::{run_ability_check_hook, AbilityCheckContext};
+use crate::core::effects::get_item_id;
+use crate::core::state::{BattleState, LogCategory};
+use crate::core::utils::{get_active_creature, split_battler_id};
+
+// ぶきよう等で 道具が使えない場合は None
+fn usable_item(state: &BattleState, player_id: &str) -> Option<String> {
+    let item = get_item_id(get_active_creature(state, player_id)?)?;
+    let can_use = run_ability_check_hook(
+        state,
+        player_id,
+        "onCheckItem",
+        AbilityCheckContext {
+            status_id: None,
+            r#type: None,
+            target_id: None,
+            action: None,
+        },
+        true,
+    );
+    can_use.then_some(item)
+}
+
+pub fn run_item_value_hook(state: &BattleState, player_id: &str, hook: &str, value: f32) -> f32 {
+    let Some(item) = usable_item(state, player_id) else {
+        return value;
+    };
+
+    match (item.as_str(), hook) {
+        // ピントレンズ / するどいツメ: 急所ランク+1
+        ("scope_lens" | "razor_claw", "onModifyCritChance") => value + 1.0,
+        // こだわりハチマキ / こだわりメガネ: 攻撃 / 特攻1.5倍
+        ("choice_band", "onModifyAtk") | ("choice_specs", "onModifySpA") => value * 1.5,
+        // こだわりスカーフ: 素早さ1.5倍
+        ("choice_scarf", "onModifySpeed") => value * 1.5,
+        // おおきなねっこ: 吸収技の回復量1.3倍
+        ("big_root", "onModifyDrain") => value * 1.3,
+        // こうかくレンズ: 命中率1.1倍
+        ("wide_lens", "onModifyAccuracy") => value * 1.1,
+        // メトロノーム: 同じ技を続けて出すたびに威力+20% (最大2倍)
+        ("metronome", "onModifyPower") => {
+            let streak = get_active_creature(state, player_id)
+                .and_then(|creature| creature.volatile_data.get("metronomeCount"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            value * (1.0 + 0.2 * streak as f32).min(2.0)
+        }
+        _ => value,
+    }
+}
+
+pub fn run_item_check_hook(state: &BattleState, player_id: &str, hook: &str, default_value: bool) -> bool {
+    let Some(item) = usable_item(state, player_id) else {
+        return default_value;
+    };
+
+    match (item.as_str(), hook) {
+        // ぼうじんゴーグル: こな・ほうし技を受けない
+        ("safety_goggles", "onCheckPowderImmunity") => true,
+        // いかさまダイス: 連続技の回数が増え、命中判定も1回で済む
+        ("loaded_dice", "onLoadedDice") => true,
+        // きあいのタスキ: HP満タンから一撃で倒れるダメージを HP 1 で耐える (使うと なくなる)
+        ("focus_sash", "onSurviveLethalHit") => true,
+        _ => default_value,
+    }
+}
+
+/// バッグの道具を手持ちの `slot` に使う。控えのポケモンにも使える。
+/// 効果がなかった場合は false を返し、状態は変えない。
+pub fn use_bag_item(state: &mut BattleState, player_id: &str, item_id: &str, slot: usize) -> bool {
+    let Some(creature) = state
+        .players
+        .iter_mut()
+        .find(|p| p.id == split_battler_id(player_id).0)
+        .and_then(|p| p.team.get_mut(slot))
+    else {
+        return false;
+    };
+
+    let message = match item_id {
+        "potion" | "super_potion" | "hyper_potion" | "max_potion" => {
+            if creature.hp <= 0 || creature.hp >= creature.max_hp {
+                return false;
+            }
+            let amount = match item_id {
+                "potion" => 20,
+                "super_potion" => 60,
+                "hyper_potion" => 120,
+                _ => creature.max_hp,
+            };
+            let healed = amount.min(creature.max_hp - creature.hp);
+            creature.hp += healed;
+            format!("{}の HPが {}回復した！", creature.name, healed)
+        }
+        "revive" | "max_revive" => {
+            if creature.hp > 0 {
+                return false;
+            }
+            creature.hp = if item_id == "revive" { (creature.max_hp / 2).max(1) } else { creature.max_hp };
+            creature.statuses.clear();
+            format!("{}は 元気を 取り戻した！", creature.name)
+        }
+        _ => return false,
+    };
+    state.push_log(LogCategory::System, message);
+    true
+}
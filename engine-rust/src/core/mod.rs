@@ -1,9 +1,16 @@
 pub mod abilities;
 pub mod battle;
+pub mod damage;
+pub mod effect_schema;
 pub mod effects;
 pub mod events;
 pub mod factory;
+pub mod items;
 pub mod replay;
+pub mod rng;
+pub mod rules;
 pub mod state;
 pub mod statuses;
+pub mod summary;
+pub mod undo;
 pub mod utils;
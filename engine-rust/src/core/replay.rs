@@ -1,16 +1,108 @@
-use crate::core::battle::{step_battle, BattleOptions};
-use crate::core::state::{BattleHistory, BattleState};
+use crate::core::battle::{BattleEngine, BattleOptions};
+use crate::core::state::{BattleHistory, BattleState, BattleTurn};
+use serde::{Deserialize, Serialize};
 
-pub fn replay_battle(initial_state: &BattleState, history: &BattleHistory) -> BattleState {
-    let mut next = initial_state.clone();
+/// HP movement of every creature over one replayed turn, for post-battle graphs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TurnTally {
+    pub turn: u32,
+    pub creatures: Vec<CreatureTally>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CreatureTally {
+    pub player_id: String,
+    pub slot: usize,
+    pub creature_id: String,
+    pub hp_before: i32,
+    pub hp_after: i32,
+    /// HP actually lost this turn, from `CreatureState::last_damage_taken`.
+    pub damage_taken: i32,
+    /// HP regained this turn; damage and healing in the same turn are counted separately.
+    pub healed: i32,
+}
+
+/// Replays `history` from `initial_state`. `engine` and `options` must be the ones the battle
+/// was played with, or rules, move data and doubles slots will not match the recording.
+pub fn replay_battle(
+    engine: &BattleEngine,
+    initial_state: &BattleState,
+    history: &BattleHistory,
+    options: BattleOptions,
+) -> BattleState {
+    replay_turns(engine, initial_state, &history.turns, BattleOptions { record_history: false, ..options })
+}
+
+/// Rebuilds the state before the last recorded turn by replaying `history` from `initial`
+/// minus its final turn, with the battle's own `engine` and `options`. With `record_history`
+/// set the replayed turns are recorded again, so the result can be rewound further.
+/// Returns `None` when there is no turn to undo.
+pub fn rewind_one_turn(
+    engine: &BattleEngine,
+    initial: &BattleState,
+    history: &BattleHistory,
+    options: BattleOptions,
+) -> Option<BattleState> {
+    let (_, kept) = history.turns.split_last()?;
+    Some(replay_turns(engine, initial, kept, options))
+}
+
+/// Replays `history` from `initial` with the battle's own `engine` and `options`
+/// and tallies each creature's damage and healing per turn.
+pub fn battle_timeline(
+    engine: &BattleEngine,
+    initial: &BattleState,
+    history: &BattleHistory,
+    options: BattleOptions,
+) -> Vec<TurnTally> {
+    let options = BattleOptions { record_history: false, ..options };
+    let mut state = initial.clone();
+    let mut timeline = Vec::with_capacity(history.turns.len());
     for turn in &history.turns {
-        let mut idx = 0usize;
-        let mut rng = || {
-            let v = turn.rng.get(idx).copied().unwrap_or(0.5);
-            idx += 1;
-            v
-        };
-        next = step_battle(&next, &turn.actions, &mut rng, BattleOptions { record_history: false });
+        let next = replay_turn(engine, &state, turn, options.clone());
+        timeline.push(tally_turn(&state, &next));
+        state = next;
+    }
+    timeline
+}
+
+fn tally_turn(before: &BattleState, after: &BattleState) -> TurnTally {
+    let mut creatures = Vec::new();
+    for player in &after.players {
+        let previous = before.players.iter().find(|p| p.id == player.id);
+        for (slot, creature) in player.team.iter().enumerate() {
+            let hp_before = previous
+                .and_then(|p| p.team.get(slot))
+                .map_or(creature.hp, |old| old.hp);
+            let damage_taken = creature.last_damage_taken;
+            creatures.push(CreatureTally {
+                player_id: player.id.clone(),
+                slot,
+                creature_id: creature.id.clone(),
+                hp_before,
+                hp_after: creature.hp,
+                damage_taken,
+                healed: (creature.hp - hp_before + damage_taken).max(0),
+            });
+        }
+    }
+    TurnTally { turn: after.turn, creatures }
+}
+
+fn replay_turns(engine: &BattleEngine, initial_state: &BattleState, turns: &[BattleTurn], options: BattleOptions) -> BattleState {
+    let mut next = initial_state.clone();
+    for turn in turns {
+        next = replay_turn(engine, &next, turn, options.clone());
     }
     next
 }
+
+fn replay_turn(engine: &BattleEngine, state: &BattleState, turn: &BattleTurn, options: BattleOptions) -> BattleState {
+    let mut idx = 0usize;
+    let mut rng = || {
+        let v = turn.rng.get(idx).copied().unwrap_or(0.5);
+        idx += 1;
+        v
+    };
+    engine.step_battle(state, &turn.actions, &mut rng, options)
+}
@@ -0,0 +1,62 @@
+/// Seedable SplitMix64 generator. The same seed always yields the same `rng_f64` stream,
+/// so battles driven by it can be reproduced exactly.
+#[derive(Clone, Debug)]
+pub struct SplitMix64Rng {
+    state: u64,
+}
+
+impl SplitMix64Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `[0, 1)` built from the top 53 bits.
+    pub fn rng_f64(&mut self) -> f64 {
+        const DEN: f64 = (1u64 << 53) as f64;
+        ((self.next_u64() >> 11) as f64) / DEN
+    }
+
+    /// Closure form for `step_battle`.
+    pub fn as_fn(&mut self) -> impl FnMut() -> f64 + '_ {
+        move || self.rng_f64()
+    }
+}
+
+/// Replays a recorded stream such as `BattleTurn.rng`.
+/// Running past the end is a replay mismatch and panics.
+#[derive(Clone, Debug)]
+pub struct ReplayRng {
+    values: Vec<f64>,
+    index: usize,
+}
+
+impl ReplayRng {
+    pub fn new(values: Vec<f64>) -> Self {
+        Self { values, index: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.values.len() - self.index
+    }
+
+    pub fn rng_f64(&mut self) -> f64 {
+        let Some(&value) = self.values.get(self.index) else {
+            panic!("ReplayRng exhausted after {} values", self.values.len());
+        };
+        self.index += 1;
+        value
+    }
+
+    /// Closure form for `step_battle`.
+    pub fn as_fn(&mut self) -> impl FnMut() -> f64 + '_ {
+        move || self.rng_f64()
+    }
+}
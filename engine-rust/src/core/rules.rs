@@ -0,0 +1,97 @@
+/// Crit stage that always crits, whatever the table says (e.g. Merciless vs a poisoned target).
+pub const GUARANTEED_CRIT_STAGE: f32 = 999.0;
+
+/// Generation-dependent battle constants.
+#[derive(Clone, Debug)]
+pub struct BattleRules {
+    /// Crit probability indexed by crit stage; stages past the end use the last entry.
+    pub crit_chances: Vec<f32>,
+    pub crit_multiplier: f32,
+    /// Speed multiplier while paralyzed.
+    pub paralysis_speed_multiplier: f32,
+    /// Power of Parental Bond's second hit relative to the first.
+    pub parental_bond_multiplier: f32,
+    /// Damage multiplier when a spread move hits more than one target in doubles.
+    pub spread_multiplier: f32,
+    /// Fraction of the user's max HP lost to Struggle recoil.
+    pub struggle_recoil: f32,
+    /// Sleep Clause: a move cannot put a second opposing creature to sleep while
+    /// one on that team is already asleep. Off in every generation preset.
+    pub sleep_clause: bool,
+}
+
+impl Default for BattleRules {
+    fn default() -> Self {
+        Self::gen(9)
+    }
+}
+
+impl BattleRules {
+    /// Preset for generation `n`. Generations before 3 use the Gen 3 values,
+    /// and anything newer than 9 uses the Gen 9 values.
+    pub fn gen(n: u8) -> Self {
+        // 急所ランクの確率設定
+        // 第6世代以前: 1/16, 1/8, 1/4 (第6世代は 1/2), 1/3, 1/2
+        // 第7世代以降: 1/24, 1/8, 1/2, 100%
+        let crit_chances = match n {
+            0..=5 => vec![1.0 / 16.0, 1.0 / 8.0, 1.0 / 4.0, 1.0 / 3.0, 1.0 / 2.0],
+            6 => vec![1.0 / 16.0, 1.0 / 8.0, 1.0 / 2.0, 1.0],
+            _ => vec![1.0 / 24.0, 1.0 / 8.0, 1.0 / 2.0, 1.0],
+        };
+        Self {
+            crit_chances,
+            crit_multiplier: if n <= 5 { 2.0 } else { 1.5 },
+            paralysis_speed_multiplier: if n <= 6 { 0.25 } else { 0.5 },
+            parental_bond_multiplier: if n <= 6 { 0.5 } else { 0.25 },
+            spread_multiplier: if n <= 3 { 0.5 } else { 0.75 },
+            struggle_recoil: if n <= 3 { 0.5 } else { 0.25 },
+            sleep_clause: false,
+        }
+    }
+
+    pub fn with_crit_chances(mut self, crit_chances: Vec<f32>) -> Self {
+        self.crit_chances = crit_chances;
+        self
+    }
+
+    pub fn with_crit_multiplier(mut self, multiplier: f32) -> Self {
+        self.crit_multiplier = multiplier;
+        self
+    }
+
+    pub fn with_paralysis_speed_multiplier(mut self, multiplier: f32) -> Self {
+        self.paralysis_speed_multiplier = multiplier;
+        self
+    }
+
+    pub fn with_parental_bond_multiplier(mut self, multiplier: f32) -> Self {
+        self.parental_bond_multiplier = multiplier;
+        self
+    }
+
+    pub fn with_spread_multiplier(mut self, multiplier: f32) -> Self {
+        self.spread_multiplier = multiplier;
+        self
+    }
+
+    pub fn with_struggle_recoil(mut self, fraction: f32) -> Self {
+        self.struggle_recoil = fraction;
+        self
+    }
+
+    pub fn with_sleep_clause(mut self, enabled: bool) -> Self {
+        self.sleep_clause = enabled;
+        self
+    }
+
+    pub fn crit_chance(&self, crit_stage: f32) -> f64 {
+        if crit_stage >= GUARANTEED_CRIT_STAGE {
+            return 1.0;
+        }
+        let Some(last) = self.crit_chances.len().checked_sub(1) else {
+            return 0.0;
+        };
+        let index = (crit_stage.ceil().max(0.0) as usize).min(last);
+        self.crit_chances[index] as f64
+    }
+}
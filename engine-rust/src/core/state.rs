@@ -30,6 +30,7 @@ impl Default for StatStages {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Status {
     pub id: String,
     pub remaining_turns: Option<i32>,
@@ -38,6 +39,7 @@ pub struct Status {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreatureState {
     pub id: String,
     pub species_id: String,
@@ -58,6 +60,21 @@ pub struct CreatureState {
     pub ability_data: HashMap<String, Value>,
     #[serde(default)]
     pub volatile_data: HashMap<String, Value>,
+    /// Moves the opponent has seen this creature use. Unlike `volatile_data`, survives switching.
+    #[serde(default)]
+    pub revealed_moves: Vec<String>,
+    /// 相手に種族が知られているか。場に出るか、見せ合いがあれば true
+    #[serde(default)]
+    pub species_revealed: bool,
+    /// 重さ (kg)。種族データに無ければ None
+    #[serde(default)]
+    pub weight: Option<f32>,
+    /// このターンに相手へ与えたダメージの合計。みがわりが受けた分も含む
+    #[serde(default)]
+    pub last_damage_dealt: i32,
+    /// このターンに受けたダメージの合計 (実際に減った HP)
+    #[serde(default)]
+    pub last_damage_taken: i32,
     pub attack: i32,
     pub defense: i32,
     pub sp_attack: i32,
@@ -66,6 +83,7 @@ pub struct CreatureState {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PlayerState {
     pub id: String,
     pub name: String,
@@ -73,9 +91,49 @@ pub struct PlayerState {
     pub active_slot: usize,
     #[serde(default)]
     pub last_fainted_ability: Option<String>,
+    /// ダブルバトルで2体目として場に出ている手持ちの位置。シングルでは None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partner_slot: Option<usize>,
+}
+
+impl PlayerState {
+    /// 先頭の手持ちを場に出したシングルバトルのプレイヤー
+    pub fn new(id: impl Into<String>, name: impl Into<String>, team: Vec<CreatureState>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            team,
+            active_slot: 0,
+            last_fainted_ability: None,
+            partner_slot: None,
+        }
+    }
+
+    /// 場の位置 (0 が1体目、1 がダブルバトルの2体目) にいる手持ちの位置
+    pub fn active_slot_at(&self, position: usize) -> Option<usize> {
+        match position {
+            0 => Some(self.active_slot),
+            1 => self.partner_slot,
+            _ => None,
+        }
+    }
+
+    pub fn set_active_slot_at(&mut self, position: usize, slot: usize) {
+        if position == 0 {
+            self.active_slot = slot;
+        } else {
+            self.partner_slot = Some(slot);
+        }
+    }
+
+    /// 場に出ている手持ちの位置。ダブルバトルでは2体
+    pub fn active_slots(&self) -> Vec<usize> {
+        std::iter::once(self.active_slot).chain(self.partner_slot).collect()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FieldEffect {
     pub id: String,
     pub remaining_turns: Option<i32>,
@@ -84,38 +142,157 @@ pub struct FieldEffect {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FieldState {
     #[serde(default)]
     pub global: Vec<FieldEffect>,
     #[serde(default)]
     pub sides: HashMap<String, Vec<FieldEffect>>,
+    /// 最後に 場で 出た技。まねっこが参照する
+    #[serde(default)]
+    pub last_move: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BattleHistory {
     pub turns: Vec<BattleTurn>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BattleTurn {
     pub turn: u32,
     pub actions: Vec<Action>,
     pub log: Vec<String>,
     pub rng: Vec<f64>,
+    #[serde(default)]
+    pub outcomes: Vec<MoveRecord>,
+}
+
+/// 技を使った結果。外れたのか、効かなかったのか、失敗したのかを区別する。
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MoveOutcome {
+    Hit,
+    Missed,
+    Failed(String),
+    NoEffect,
+    /// まもる などで防がれた
+    Blocked,
+    /// まひ・ねむり・ひるみ などで技を出せなかった
+    Prevented,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveRecord {
+    pub player_id: String,
+    pub move_id: String,
+    pub outcome: MoveOutcome,
+}
+
+/// ログ1行の種類。画面側で色分けするのに使う
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogCategory {
+    /// 技を出した・技の成否
+    Move,
+    /// ダメージと回復
+    Damage,
+    /// 状態異常・能力ランク・場の状態
+    Status,
+    /// 特性の発動
+    Ability,
+    /// ひんし
+    Faint,
+    /// ターンの区切りや行動できなかった理由など、上のどれにも当たらないもの
+    #[default]
+    System,
+}
+
+impl LogCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogCategory::Move => "move",
+            LogCategory::Damage => "damage",
+            LogCategory::Status => "status",
+            LogCategory::Ability => "ability",
+            LogCategory::Faint => "faint",
+            LogCategory::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "move" => Some(LogCategory::Move),
+            "damage" => Some(LogCategory::Damage),
+            "status" => Some(LogCategory::Status),
+            "ability" => Some(LogCategory::Ability),
+            "faint" => Some(LogCategory::Faint),
+            "system" => Some(LogCategory::System),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BattleState {
     pub players: Vec<PlayerState>,
     pub field: FieldState,
     pub turn: u32,
     #[serde(default)]
     pub log: Vec<String>,
+    /// `log` の各行の種類。末尾をそろえて持つので、種類を記録する前の古い行があれば先頭側が短くなる
+    #[serde(default)]
+    pub log_categories: Vec<LogCategory>,
     pub history: Option<BattleHistory>,
 }
 
+impl BattleState {
+    /// 対戦の途中経過を JSON にする。キーは WASM 側の `BattleStateWire` と同じ camelCase
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// `to_json` で書き出した JSON から対戦を復元する
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// ログを1行、種類つきで足す
+    pub fn push_log(&mut self, category: LogCategory, message: impl Into<String>) {
+        self.log.push(message.into());
+        self.log_categories.push(category);
+    }
+
+    /// ログを種類つきで読む。種類が記録されていない古い行は `System`
+    pub fn log_entries(&self) -> Vec<(LogCategory, &str)> {
+        let untagged = self.log.len().saturating_sub(self.log_categories.len());
+        let tagged_from = self.log_categories.len().saturating_sub(self.log.len());
+        self.log
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let category = idx
+                    .checked_sub(untagged)
+                    .map(|idx| self.log_categories[tagged_from + idx])
+                    .unwrap_or_default();
+                (category, line.as_str())
+            })
+            .collect()
+    }
+
+    /// `log` の先頭が削られた後、`log_categories` の余った先頭を落として末尾をそろえ直す
+    pub(crate) fn trim_log_categories(&mut self) {
+        let excess = self.log_categories.len().saturating_sub(self.log.len());
+        self.log_categories.drain(..excess);
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum ActionType {
     Move,
     Switch,
@@ -123,11 +300,15 @@ pub enum ActionType {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Action {
     pub player_id: String,
+    #[serde(rename = "type")]
     pub action_type: ActionType,
+    /// `UseItem` ではバッグの道具ID
     pub move_id: Option<String>,
     pub target_id: Option<String>,
+    /// `Switch` では交代先、`UseItem` では道具を使う手持ちの位置
     pub slot: Option<usize>,
     pub priority: Option<i32>,
 }
@@ -144,9 +325,22 @@ pub fn create_battle_state(players: Vec<PlayerState>) -> BattleState {
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
+
+/// Canonical key for the battle position (players, field, turn). Log and history are ignored,
+/// so two states that only differ in how they were reached share a key.
+pub fn state_key(state: &BattleState) -> String {
+    let value = serde_json::json!({
+        "players": state.players,
+        "field": state.field,
+        "turn": state.turn,
+    });
+    value.to_string()
+}
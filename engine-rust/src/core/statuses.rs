@@ -1,7 +1,12 @@
-use crate::core::effects::{apply_effects, apply_events};
-use crate::core::events::{BattleEvent, EventTransform};
-use crate::core::state::{Action, BattleState, Status};
-use crate::core::utils::get_active_creature;
+use crate::core::abilities::{run_ability_check_hook, weather_suppressed, AbilityCheckContext};
+use crate::core::effects::{apply_effects, apply_events, get_item_id};
+use crate::core::events::{meta_with_move_source, BattleEvent, EventTransform, TOXIC_COUNTER_KEY};
+use crate::core::rules::BattleRules;
+use crate::core::state::{Action, ActionType, BattleState, LogCategory, Status};
+use crate::core::utils::{
+    active_battler_ids, find_battler_mut, get_active_creature, get_active_creature_mut, is_grounded, is_status_move,
+    split_battler_id,
+};
 use crate::data::moves::{Effect, MoveData};
 use crate::data::type_chart::TypeChart;
 use serde_json::{Map, Value};
@@ -21,6 +26,7 @@ pub struct StatusHookContext<'a> {
     pub action: Option<&'a Action>,
     pub move_data: Option<&'a MoveData>,
     pub type_chart: &'a TypeChart,
+    pub rules: &'a BattleRules,
 }
 
 pub fn run_status_hooks(
@@ -46,6 +52,7 @@ pub fn run_status_hooks(
             action: ctx.action,
             move_data: ctx.move_data,
             type_chart: ctx.type_chart,
+            rules: ctx.rules,
         });
         if let Some(next) = result.state {
             working_state = next;
@@ -84,6 +91,7 @@ pub fn run_field_hooks(
             action: ctx.action,
             move_data: ctx.move_data,
             type_chart: ctx.type_chart,
+            rules: ctx.rules,
         });
         if let Some(next) = result.state {
             working_state = next;
@@ -101,6 +109,83 @@ pub fn run_field_hooks(
     }
 }
 
+/// `battler_id` の陣営 (`field.sides`) に張られた効果のフック。交代で出てきた時の まきびし・ステルスロックなど
+pub fn run_side_field_hooks(
+    state: &BattleState,
+    battler_id: &str,
+    hook: &str,
+    ctx: StatusHookContext<'_>,
+) -> StatusHookResult {
+    let Some(effects) = state.field.sides.get(split_battler_id(battler_id).0) else {
+        return StatusHookResult::default();
+    };
+    let mut events = Vec::new();
+    for effect in effects {
+        if hook == "onSwitchIn" {
+            events.extend(entry_hazard_events(state, battler_id, effect, ctx.type_chart));
+        }
+    }
+    StatusHookResult {
+        events,
+        ..Default::default()
+    }
+}
+
+/// ステルスロック (いわ技の相性 × 最大HPの1/8) と まきびし (層ごとに 1/8, 1/6, 1/4)。マジックガードは受けない
+fn entry_hazard_events(
+    state: &BattleState,
+    battler_id: &str,
+    hazard: &crate::core::state::FieldEffect,
+    type_chart: &TypeChart,
+) -> Vec<BattleEvent> {
+    let hazard_id = hazard.id.as_str();
+    let Some(active) = get_active_creature(state, battler_id).filter(|c| c.hp > 0) else {
+        return Vec::new();
+    };
+    let (amount, message) = match hazard_id {
+        "stealth_rock" => {
+            let effectiveness = type_chart.effectiveness("rock", &active.types);
+            let amount = (active.max_hp as f32 * effectiveness / 8.0).floor() as i32;
+            (amount, format!("{}に とがった岩が 食いこんだ！", active.name))
+        }
+        "spikes" => {
+            if !is_grounded(active) {
+                return Vec::new();
+            }
+            let divisor = match hazard.data.get("layers").and_then(|v| v.as_u64()).unwrap_or(1) {
+                0 | 1 => 8,
+                2 => 6,
+                _ => 4,
+            };
+            (active.max_hp / divisor, format!("{}は まきびしの ダメージを 受けた！", active.name))
+        }
+        _ => return Vec::new(),
+    };
+    let immune = run_ability_check_hook(
+        state,
+        battler_id,
+        "onCheckResidualImmunity",
+        AbilityCheckContext {
+            status_id: Some(hazard_id),
+            r#type: None,
+            target_id: None,
+            action: None,
+        },
+        false,
+    );
+    if immune {
+        return Vec::new();
+    }
+    vec![
+        BattleEvent::log_with(LogCategory::Damage, message),
+        BattleEvent::Damage {
+            target_id: battler_id.to_string(),
+            amount: amount.max(1),
+            meta: Map::new(),
+        },
+    ]
+}
+
 fn match_field_effect(
     state: &BattleState,
     hook: &str,
@@ -111,21 +196,16 @@ fn match_field_effect(
     // グラスフィールド回復は特別処理
     if status_id == "grassy_terrain" && hook == "onGrassyTerrainHeal" {
         let mut events = Vec::new();
-        for player in &state.players {
-            let active = get_active_creature(state, &player.id);
+        for battler_id in active_battler_ids(state) {
+            let active = get_active_creature(state, &battler_id);
             if let Some(active) = active {
                 if active.hp > 0 && active.hp < active.max_hp {
-                    // 地面にいるポケモンのみ回復（ひこう・ふゆう除外は簡略化）
-                    let is_flying = active.types.iter().any(|t| t == "flying");
-                    let has_levitate = active.ability.as_deref() == Some("levitate");
-                    if !is_flying && !has_levitate {
+                    // 地面にいるポケモンのみ回復
+                    if is_grounded(active) {
                         let heal = (active.max_hp / 16).max(1);
-                        events.push(BattleEvent::Log {
-                            message: format!("{}は グラスフィールドの 恩恵を 受けている！", active.name),
-                            meta: Map::new(),
-                        });
+                        events.push(BattleEvent::log_with(LogCategory::Damage, format!("{}は グラスフィールドの 恩恵を 受けている！", active.name)));
                         events.push(BattleEvent::Damage {
-                            target_id: player.id.clone(),
+                            target_id: battler_id.clone(),
                             amount: -heal,
                             meta: Map::new(),
                         });
@@ -139,6 +219,52 @@ fn match_field_effect(
         };
     }
 
+    if matches!(status_id, "sandstorm" | "hail") && hook == "onWeatherEnd" {
+        // ノーてんき・エアロックがいる間は天気のダメージなし
+        if weather_suppressed(state) {
+            return StatusHookResult::default();
+        }
+        let (immune_types, weather_name): (&[&str], &str) = if status_id == "sandstorm" {
+            (&["rock", "ground", "steel"], "すなあらし")
+        } else {
+            (&["ice"], "あられ")
+        };
+        let mut events = Vec::new();
+        for battler_id in active_battler_ids(state) {
+            let Some(active) = get_active_creature(state, &battler_id) else {
+                continue;
+            };
+            if active.hp <= 0 || active.types.iter().any(|t| immune_types.contains(&t.as_str())) {
+                continue;
+            }
+            let immune = run_ability_check_hook(
+                state,
+                &battler_id,
+                "onCheckResidualImmunity",
+                AbilityCheckContext {
+                    status_id: Some(status_id),
+                    r#type: None,
+                    target_id: None,
+                    action: None,
+                },
+                false,
+            );
+            if immune {
+                continue;
+            }
+            events.push(BattleEvent::log_with(LogCategory::Damage, format!("{}は {}に 襲われている！", active.name, weather_name)));
+            events.push(BattleEvent::Damage {
+                target_id: battler_id.clone(),
+                amount: (active.max_hp / 16).max(1),
+                meta: Map::new(),
+            });
+        }
+        return StatusHookResult {
+            events,
+            ..Default::default()
+        };
+    }
+
     let pseudo_status = Status {
         id: status_id.to_string(),
         remaining_turns: status.remaining_turns,
@@ -166,10 +292,7 @@ fn match_status(
                             amount: damage,
                             meta: Map::new(),
                         },
-                        BattleEvent::Log {
-                            message: format!("{}は やけどのダメージを 受けている！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("{}は やけどのダメージを 受けている！", active.name)),
                     ],
                     ..Default::default()
                 }
@@ -187,10 +310,7 @@ fn match_status(
                             amount: damage,
                             meta: Map::new(),
                         },
-                        BattleEvent::Log {
-                            message: format!("{}は どくの ダメージを 受けている！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("{}は どくの ダメージを 受けている！", active.name)),
                     ],
                     ..Default::default()
                 }
@@ -204,7 +324,7 @@ fn match_status(
                     .statuses
                     .iter()
                     .find(|s| s.id == "toxic")
-                    .and_then(|s| s.data.get("counter"))
+                    .and_then(|s| s.data.get(TOXIC_COUNTER_KEY))
                     .and_then(|v| v.as_i64())
                     .map(|v| v as i32)
                     .unwrap_or(1)
@@ -212,12 +332,12 @@ fn match_status(
                 let damage = ((active.max_hp * counter) / 16).max(1);
 
                 let mut new_state = state.clone();
-                if let Some(player) = new_state.players.iter_mut().find(|p| p.id == player_id) {
-                    if let Some(active_mut) = player.team.get_mut(player.active_slot) {
+                if let Some((player, slot)) = find_battler_mut(&mut new_state.players, player_id) {
+                    if let Some(active_mut) = player.team.get_mut(slot) {
                         if let Some(toxic) = active_mut.statuses.iter_mut().find(|s| s.id == "toxic") {
                             toxic
                                 .data
-                                .insert("counter".to_string(), Value::Number((counter + 1).into()));
+                                .insert(TOXIC_COUNTER_KEY.to_string(), Value::Number((counter + 1).into()));
                         }
                     }
                 }
@@ -230,10 +350,7 @@ fn match_status(
                             amount: damage,
                             meta: Map::new(),
                         },
-                        BattleEvent::Log {
-                            message: format!("{}は もうどくの ダメージを 受けている！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("{}は もうどくの ダメージを 受けている！", active.name)),
                     ],
                     ..Default::default()
                 }
@@ -245,10 +362,7 @@ fn match_status(
                 if (ctx.rng)() < 0.25 {
                     StatusHookResult {
                         prevent_action: true,
-                        events: vec![BattleEvent::Log {
-                            message: format!("からだが しびれて 動けない！"),
-                            meta: Map::new(),
-                        }],
+                        events: vec![BattleEvent::log_with(LogCategory::Status, format!("からだが しびれて 動けない！"))],
                         ..Default::default()
                     }
                 } else {
@@ -270,8 +384,7 @@ fn match_status(
 
                 if let Some(idx) = status_idx {
                     let mut new_state = state.clone();
-                    let player = new_state.players.iter_mut().find(|p| p.id == player_id).unwrap();
-                    let active = player.team.get_mut(player.active_slot).unwrap();
+                    let active = get_active_creature_mut(&mut new_state, player_id).unwrap();
                     let status = &mut active.statuses[idx];
 
                     // ターン数が設定されていない場合は2-4で設定
@@ -295,10 +408,7 @@ fn match_status(
                                     status_id: "sleep".to_string(),
                                     meta: Map::new(),
                                 },
-                                BattleEvent::Log {
-                                    message: format!("{}は 目を 覚ました！", active.name),
-                                    meta: Map::new(),
-                                },
+                                BattleEvent::log_with(LogCategory::Status, format!("{}は 目を 覚ました！", active.name)),
                             ],
                             ..Default::default()
                         };
@@ -309,10 +419,7 @@ fn match_status(
                         return StatusHookResult {
                             state: Some(new_state),
                             prevent_action: true,
-                            events: vec![BattleEvent::Log {
-                                message: format!("{}は ぐうぐう 眠り続けている。", name),
-                                meta: Map::new(),
-                            }],
+                            events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は ぐうぐう 眠り続けている。", name))],
                             ..Default::default()
                         };
                     }
@@ -324,7 +431,9 @@ fn match_status(
         "freeze" => match hook {
             "onBeforeAction" => {
                 let active = get_active_creature(state, player_id).unwrap();
-                if (ctx.rng)() < 0.2 {
+                // かえんぐるま・ねっとう などは 自分の こおりを とかしてから 技を出す
+                let thaw_user = ctx.move_data.is_some_and(|m| m.tags.iter().any(|t| t == "thaw_user"));
+                if thaw_user || (ctx.rng)() < 0.2 {
                     StatusHookResult {
                         events: vec![
                             BattleEvent::RemoveStatus {
@@ -332,20 +441,14 @@ fn match_status(
                                 status_id: "freeze".to_string(),
                                 meta: Map::new(),
                             },
-                            BattleEvent::Log {
-                                message: format!("{}の こおりが とけた！", active.name),
-                                meta: Map::new(),
-                            },
+                            BattleEvent::log_with(LogCategory::Status, format!("{}の こおりが とけた！", active.name)),
                         ],
                         ..Default::default()
                     }
                 } else {
                     StatusHookResult {
                         prevent_action: true,
-                        events: vec![BattleEvent::Log {
-                            message: format!("{}は 凍りついて 動けない！", active.name),
-                            meta: Map::new(),
-                        }],
+                        events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は 凍りついて 動けない！", active.name))],
                         ..Default::default()
                     }
                 }
@@ -360,10 +463,7 @@ fn match_status(
                     StatusHookResult {
                         prevent_action: true,
                         events: vec![
-                            BattleEvent::Log {
-                                message: format!("わけもわからず 自分を 攻撃した！"),
-                                meta: Map::new(),
-                            },
+                            BattleEvent::log_with(LogCategory::Status, format!("わけもわからず 自分を 攻撃した！")),
                             BattleEvent::Damage {
                                 target_id: player_id.to_string(),
                                 amount: damage.max(1),
@@ -378,16 +478,25 @@ fn match_status(
             }
             _ => StatusHookResult::default(),
         },
+        // おんねん: 次に自分が行動するまで続く
+        "grudge" => match hook {
+            "onBeforeAction" => StatusHookResult {
+                events: vec![BattleEvent::RemoveStatus {
+                    target_id: player_id.to_string(),
+                    status_id: "grudge".to_string(),
+                    meta: Map::new(),
+                }],
+                ..Default::default()
+            },
+            _ => StatusHookResult::default(),
+        },
         "flinch" => match hook {
             "onBeforeAction" => {
                 let active = get_active_creature(state, player_id);
                 let name = active.map(|c| c.name.clone()).unwrap_or_else(|| "誰か".to_string());
                 StatusHookResult {
                     prevent_action: true,
-                    events: vec![BattleEvent::Log {
-                        message: format!("{}は ひるんで 動けない！", name),
-                        meta: Map::new(),
-                    }],
+                    events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は ひるんで 動けない！", name))],
                     ..Default::default()
                 }
             },
@@ -396,9 +505,21 @@ fn match_status(
         "protect" => match hook {
             "onEventTransform" => {
                 let active = get_active_creature(state, player_id).unwrap();
+                let reaction = protect_block_reaction(state, player_id, status, ctx);
                 let mut transforms = Vec::new();
-                let types = ["damage", "apply_status", "modify_stage"];
+                // 技の結果を「防がれた」として記録できるように outcome を付けておく
+                let mut blocked_meta = Map::new();
+                blocked_meta.insert("category".to_string(), Value::String(LogCategory::Move.as_str().to_string()));
+                blocked_meta.insert("outcome".to_string(), Value::String("blocked".to_string()));
+                let types = ["damage", "apply_status", "modify_stage", "set_ability"];
                 for t in types {
+                    let mut to = vec![BattleEvent::Log {
+                        message: format!("{}は 攻撃から 身を 守った！", active.name),
+                        meta: blocked_meta.clone(),
+                    }];
+                    if t == "damage" {
+                        to.extend(reaction.iter().cloned());
+                    }
                     transforms.push(EventTransform {
                         transform_type: "replace_event".to_string(),
                         from: Some(t.to_string()),
@@ -406,10 +527,7 @@ fn match_status(
                         target_id: Some(player_id.to_string()),
                         except_source_id: Some(player_id.to_string()),
                         require_absent_meta: Some("bypassProtect".to_string()),
-                        to: vec![BattleEvent::Log {
-                            message: format!("{}は 攻撃から 身を 守った！", active.name),
-                            meta: Map::new(),
-                        }],
+                        to,
                         priority: 0,
                     });
                 }
@@ -420,6 +538,34 @@ fn match_status(
             }
             _ => StatusHookResult::default(),
         },
+        // フリーフォールで相手をつかんでいる側。次の行動は必ず落としにいき、その間は攻撃を受けない
+        "sky_drop" => match hook {
+            "onBeforeAction" => {
+                let move_id = status.data.get("moveId").and_then(|v| v.as_str()).unwrap_or("sky_drop");
+                let Some(action) = ctx.action else {
+                    return StatusHookResult::default();
+                };
+                let mut new_action = action.clone();
+                new_action.move_id = Some(move_id.to_string());
+                if let Some(target_id) = status.data.get("targetId").and_then(|v| v.as_str()) {
+                    new_action.target_id = Some(target_id.to_string());
+                }
+                StatusHookResult {
+                    override_action: Some(new_action),
+                    ..Default::default()
+                }
+            }
+            "onEventTransform" => sky_transforms(state, player_id, player_id),
+            _ => StatusHookResult::default(),
+        },
+        // フリーフォールで連れ去られた側。つかんでいる相手の攻撃だけが当たる
+        "sky_dropped" => match hook {
+            "onEventTransform" => {
+                let source_id = status.data.get("sourceId").and_then(|v| v.as_str()).unwrap_or(player_id);
+                sky_transforms(state, player_id, source_id)
+            }
+            _ => StatusHookResult::default(),
+        },
         "substitute" => match hook {
             "onEventTransform" => {
                 let active = get_active_creature(state, player_id).unwrap();
@@ -433,10 +579,7 @@ fn match_status(
                         target_id: Some(player_id.to_string()),
                         except_source_id: Some(player_id.to_string()),
                         require_absent_meta: Some("bypassSubstitute".to_string()),
-                        to: vec![BattleEvent::Log {
-                            message: format!("{}の みがわりが 攻撃を 受けた！", active.name),
-                            meta: Map::new(),
-                        }],
+                        to: vec![BattleEvent::log_with(LogCategory::Damage, format!("{}の みがわりが 攻撃を 受けた！", active.name))],
                         priority: 0,
                     });
                 }
@@ -478,10 +621,7 @@ fn match_status(
                             };
                             return StatusHookResult {
                                 override_action: Some(new_action),
-                                events: vec![BattleEvent::Log {
-                                    message,
-                                    meta: Map::new(),
-                                }],
+                                events: vec![BattleEvent::log_with(LogCategory::Status, message)],
                                 ..Default::default()
                             };
                         }
@@ -491,6 +631,39 @@ fn match_status(
             }
             _ => StatusHookResult::default(),
         },
+        "choice_lock" => match hook {
+            "onBeforeAction" => {
+                let active = get_active_creature(state, player_id).unwrap();
+                // はたきおとす・トリック等で こだわり道具を失ったら固定も外れる
+                if !get_item_id(active).is_some_and(|item| item.starts_with("choice_")) {
+                    return StatusHookResult {
+                        events: vec![BattleEvent::RemoveStatus {
+                            target_id: player_id.to_string(),
+                            status_id: "choice_lock".to_string(),
+                            meta: Map::new(),
+                        }],
+                        ..Default::default()
+                    };
+                }
+                let move_id = status.data.get("moveId").and_then(|v| v.as_str());
+                if let (Some(move_id), Some(action)) = (move_id, ctx.action) {
+                    if action.action_type == ActionType::Move && action.move_id.as_deref() != Some(move_id) {
+                        let mut new_action = action.clone();
+                        new_action.move_id = Some(move_id.to_string());
+                        return StatusHookResult {
+                            override_action: Some(new_action),
+                            events: vec![BattleEvent::log_with(
+                                LogCategory::Status,
+                                format!("{}は {}しか 出せなくなっている！", active.name, bound_move_name(status)),
+                            )],
+                            ..Default::default()
+                        };
+                    }
+                }
+                StatusHookResult::default()
+            }
+            _ => StatusHookResult::default(),
+        },
         "disable_move" => match hook {
             "onBeforeAction" => {
                 let move_id = status.data.get("moveId").and_then(|v| v.as_str());
@@ -498,10 +671,7 @@ fn match_status(
                     if action.move_id.as_deref() == Some(move_id) {
                         return StatusHookResult {
                             prevent_action: true,
-                            events: vec![BattleEvent::Log {
-                                message: format!("{}は {}を 出すことができない！", get_active_creature(state, player_id).unwrap().name, move_id),
-                                meta: Map::new(),
-                            }],
+                            events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は {}を 出すことができない！", get_active_creature(state, player_id).unwrap().name, bound_move_name(status)))],
                             ..Default::default()
                         };
                     }
@@ -519,10 +689,7 @@ fn match_status(
                         new_action.move_id = Some(move_id.to_string());
                         return StatusHookResult {
                             override_action: Some(new_action),
-                            events: vec![BattleEvent::Log {
-                                message: format!("{}は アンコールを 受けた！", get_active_creature(state, player_id).unwrap().name),
-                                meta: Map::new(),
-                            }],
+                            events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は アンコールを 受けた！", get_active_creature(state, player_id).unwrap().name))],
                             ..Default::default()
                         };
                     }
@@ -534,13 +701,10 @@ fn match_status(
         "taunt" => match hook {
             "onBeforeAction" => {
                 if let Some(move_data) = ctx.move_data {
-                    if move_data.category.as_deref() == Some("status") {
+                    if is_status_move(move_data) {
                         return StatusHookResult {
                             prevent_action: true,
-                            events: vec![BattleEvent::Log {
-                                message: format!("ちょうはつされて {}を 出すことができない！", move_data.name.clone().unwrap_or_else(|| move_data.id.clone())),
-                                meta: Map::new(),
-                            }],
+                            events: vec![BattleEvent::log_with(LogCategory::Status, format!("ちょうはつされて {}を 出すことができない！", move_data.name.clone().unwrap_or_else(|| move_data.id.clone())))],
                             ..Default::default()
                         };
                     }
@@ -561,10 +725,7 @@ fn match_status(
                 let damage = (active.max_hp / 8).max(1);
                 StatusHookResult {
                     events: vec![
-                        BattleEvent::Log {
-                            message: format!("宿り木の種が {}の 体力を 削る！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("宿り木の種が {}の 体力を 削る！", active.name)),
                         BattleEvent::Damage {
                             target_id: player_id.to_string(),
                             amount: damage,
@@ -581,16 +742,38 @@ fn match_status(
             }
             _ => StatusHookResult::default(),
         },
+        "type_change" => match hook {
+            "onTurnEnd" => {
+                let types = status
+                    .data
+                    .get("originalTypes")
+                    .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
+                    .unwrap_or_default();
+                StatusHookResult {
+                    events: vec![
+                        BattleEvent::SetType {
+                            target_id: player_id.to_string(),
+                            types,
+                            meta: Map::new(),
+                        },
+                        BattleEvent::RemoveStatus {
+                            target_id: player_id.to_string(),
+                            status_id: "type_change".to_string(),
+                            meta: Map::new(),
+                        },
+                    ],
+                    ..Default::default()
+                }
+            }
+            _ => StatusHookResult::default(),
+        },
         "curse" => match hook {
             "onTurnEnd" => {
                 let active = get_active_creature(state, player_id).unwrap();
                 let damage = (active.max_hp / 4).max(1);
                 StatusHookResult {
                     events: vec![
-                        BattleEvent::Log {
-                            message: format!("{}は 呪われている！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("{}は 呪われている！", active.name)),
                         BattleEvent::Damage {
                             target_id: player_id.to_string(),
                             amount: damage,
@@ -611,8 +794,8 @@ fn match_status(
                     .unwrap_or(1);
                 if turns > 0 {
                     let mut new_state = state.clone();
-                    if let Some(player) = new_state.players.iter_mut().find(|p| p.id == player_id) {
-                        if let Some(active) = player.team.get_mut(player.active_slot) {
+                    if let Some((player, slot)) = find_battler_mut(&mut new_state.players, player_id) {
+                        if let Some(active) = player.team.get_mut(slot) {
                             if let Some(status_mut) = active.statuses.iter_mut().find(|s| s.id == "yawn") {
                                 status_mut
                                     .data
@@ -622,16 +805,16 @@ fn match_status(
                     }
                     return StatusHookResult {
                         state: Some(new_state),
-                        events: vec![BattleEvent::Log {
-                            message: format!("{}は 眠たそうだ……", get_active_creature(state, player_id).unwrap().name),
-                            meta: Map::new(),
-                        }],
+                        events: vec![BattleEvent::log_with(LogCategory::Status, format!("{}は 眠たそうだ……", get_active_creature(state, player_id).unwrap().name))],
                         ..Default::default()
                     };
                 }
                 let min = 2;
                 let max = 4;
                 let duration = min + (((ctx.rng)() * ((max - min + 1) as f64)).floor() as i32);
+                // あくびを かけた相手を 眠りの原因として引き継ぐ (ねむりクローズ)
+                let data: HashMap<String, Value> =
+                    status.data.get("sourceId").map(|source| ("sourceId".to_string(), source.clone())).into_iter().collect();
                 StatusHookResult {
                     events: vec![
                         BattleEvent::RemoveStatus {
@@ -644,7 +827,7 @@ fn match_status(
                             status_id: "sleep".to_string(),
                             duration: Some(duration),
                             stack: false,
-                            data: HashMap::new(),
+                            data,
                             meta: Map::new(),
                         },
                     ],
@@ -694,10 +877,7 @@ fn match_status(
                 let active = active.unwrap();
                 StatusHookResult {
                     events: vec![
-                        BattleEvent::Log {
-                            message: format!("{}の ねがいごとが かなった！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Damage, format!("{}の ねがいごとが かなった！", active.name)),
                         BattleEvent::Damage {
                             target_id: player_id.to_string(),
                             amount: -heal_amount,
@@ -718,14 +898,33 @@ fn match_status(
         "bind" => match hook {
             "onBindDamage" => {
                 let active = get_active_creature(state, player_id).unwrap();
-                let damage = (active.max_hp / 8).max(1);
+                if run_ability_check_hook(
+                    state,
+                    player_id,
+                    "onCheckResidualImmunity",
+                    AbilityCheckContext {
+                        status_id: Some("bind"),
+                        r#type: None,
+                        target_id: None,
+                        action: None,
+                    },
+                    false,
+                ) {
+                    return StatusHookResult::default();
+                }
+                // しめつけバンド持ちが締めつけた場合は 1/6
+                let source_has_band = status
+                    .data
+                    .get("sourceId")
+                    .and_then(|v| v.as_str())
+                    .and_then(|source_id| get_active_creature(state, source_id))
+                    .is_some_and(|source| source.item.as_deref() == Some("binding_band"));
+                let divisor = if source_has_band { 6 } else { 8 };
+                let damage = (active.max_hp / divisor).max(1);
                 let move_name = status.data.get("moveName").and_then(|v| v.as_str()).unwrap_or("バインド");
                 StatusHookResult {
                     events: vec![
-                        BattleEvent::Log {
-                            message: format!("{}は {}の ダメージを受けている！", active.name, move_name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Status, format!("{}は {}の ダメージを受けている！", active.name, move_name)),
                         BattleEvent::Damage {
                             target_id: player_id.to_string(),
                             amount: damage,
@@ -751,10 +950,7 @@ fn match_status(
                 let heal = (active.max_hp / 16).max(1);
                 StatusHookResult {
                     events: vec![
-                        BattleEvent::Log {
-                            message: format!("{}は たべのこしで 少し回復した！", active.name),
-                            meta: Map::new(),
-                        },
+                        BattleEvent::log_with(LogCategory::Damage, format!("{}は たべのこしで 少し回復した！", active.name)),
                         BattleEvent::Damage {
                             target_id: player_id.to_string(),
                             amount: -heal,
@@ -782,10 +978,7 @@ fn match_status(
                     let heal = (active.max_hp / 16).max(1);
                     StatusHookResult {
                         events: vec![
-                            BattleEvent::Log {
-                                message: format!("{}は くろいヘドロで 少し回復した！", active.name),
-                                meta: Map::new(),
-                            },
+                            BattleEvent::log_with(LogCategory::Damage, format!("{}は くろいヘドロで 少し回復した！", active.name)),
                             BattleEvent::Damage {
                                 target_id: player_id.to_string(),
                                 amount: -heal,
@@ -798,10 +991,7 @@ fn match_status(
                     let damage = (active.max_hp / 8).max(1);
                     StatusHookResult {
                         events: vec![
-                            BattleEvent::Log {
-                                message: format!("{}は くろいヘドロで ダメージを受けた！", active.name),
-                                meta: Map::new(),
-                            },
+                            BattleEvent::log_with(LogCategory::Damage, format!("{}は くろいヘドロで ダメージを受けた！", active.name)),
                             BattleEvent::Damage {
                                 target_id: player_id.to_string(),
                                 amount: damage,
@@ -852,6 +1042,7 @@ fn handle_delayed(
         rng: ctx.rng,
         turn: state.turn,
         type_chart: ctx.type_chart,
+        rules: ctx.rules,
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -897,6 +1088,7 @@ fn handle_over_time(
         rng: ctx.rng,
         turn: state.turn,
         type_chart: ctx.type_chart,
+        rules: ctx.rules,
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -913,6 +1105,73 @@ fn handle_over_time(
     }
 }
 
+/// ニードルガード・キングシールド: 接触技を防いだときに 攻撃してきた相手へ返す効果。
+/// `onBlockReaction` が `chip` なら最大HPの 1/8 のダメージ、`lower_attack` なら攻撃ランク -1
+fn protect_block_reaction(
+    state: &BattleState,
+    player_id: &str,
+    status: &Status,
+    ctx: &StatusHookContext<'_>,
+) -> Vec<BattleEvent> {
+    let Some(reaction) = status.data.get("onBlockReaction").and_then(|v| v.as_str()) else {
+        return Vec::new();
+    };
+    let (Some(action), Some(move_data)) = (ctx.action, ctx.move_data) else {
+        return Vec::new();
+    };
+    if action.player_id == player_id || !move_data.tags.iter().any(|tag| tag == "contact") {
+        return Vec::new();
+    }
+    let Some(attacker) = get_active_creature(state, &action.player_id) else {
+        return Vec::new();
+    };
+    let mut meta = meta_with_move_source(Some(move_data.id.as_str()), Some(player_id));
+    match reaction {
+        "chip" => {
+            meta.insert("bypassSubstitute".to_string(), Value::Bool(true));
+            vec![
+                BattleEvent::Damage {
+                    target_id: action.player_id.clone(),
+                    amount: (attacker.max_hp / 8).max(1),
+                    meta,
+                },
+                BattleEvent::log_with(LogCategory::Damage, format!("{}は 傷ついた！", attacker.name)),
+            ]
+        }
+        "lower_attack" => vec![BattleEvent::ModifyStage {
+            target_id: action.player_id.clone(),
+            stages: HashMap::from([("atk".to_string(), -1)]),
+            clamp: true,
+            fail_if_no_change: false,
+            show_event: true,
+            meta,
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// 上空にいる間は `allowed_source_id` 以外の技を受けない
+fn sky_transforms(state: &BattleState, player_id: &str, allowed_source_id: &str) -> StatusHookResult {
+    let active = get_active_creature(state, player_id).unwrap();
+    let transforms = ["damage", "apply_status", "modify_stage"]
+        .into_iter()
+        .map(|t| EventTransform {
+            transform_type: "replace_event".to_string(),
+            from: Some(t.to_string()),
+            target_type: None,
+            target_id: Some(player_id.to_string()),
+            except_source_id: Some(allowed_source_id.to_string()),
+            require_absent_meta: None,
+            to: vec![BattleEvent::log_with(LogCategory::Move, format!("{}には 当たらなかった！", active.name))],
+            priority: 0,
+        })
+        .collect();
+    StatusHookResult {
+        event_transforms: transforms,
+        ..Default::default()
+    }
+}
+
 fn matches_timing(hook: &str, timing: &str) -> bool {
     match timing.to_lowercase().as_str() {
         "turn_start" => hook == "onTurnStart",
@@ -931,48 +1190,78 @@ fn effects_from_status(status: &Status) -> Vec<Effect> {
     }
 }
 
+/// かなしばり・こだわりで縛った技の名前。名前が記録されていなければ技ID
+fn bound_move_name(status: &Status) -> &str {
+    status
+        .data
+        .get("moveName")
+        .or_else(|| status.data.get("moveId"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+}
+
+/// 持続ターンが切れて状態が解けたときのログ
+fn status_end_message(status: &Status, name: &str) -> Option<String> {
+    match status.id.as_str() {
+        "taunt" => Some(format!("{}の ちょうはつの 効果が 解けた！", name)),
+        "disable_move" => Some(format!("{}の {}の かなしばりが 解けた！", name, bound_move_name(status))),
+        _ => None,
+    }
+}
+
 pub fn tick_statuses(state: &BattleState) -> BattleState {
     let mut next = state.clone();
+    let mut logs = Vec::new();
     for player in &mut next.players {
-        if let Some(active) = player.team.get_mut(player.active_slot) {
-            // Track statuses that will expire and need special handling
-            let mut apply_confusion = false;
+        for slot in player.active_slots() {
+            if let Some(active) = player.team.get_mut(slot) {
+                // Track statuses that will expire and need special handling
+                let mut apply_confusion = false;
             
-            for status in &mut active.statuses {
-                if let Some(turns) = status.remaining_turns {
-                    let new_turns = turns - 1;
-                    status.remaining_turns = Some(new_turns);
+                for status in &mut active.statuses {
+                    if let Some(turns) = status.remaining_turns {
+                        let new_turns = turns - 1;
+                        status.remaining_turns = Some(new_turns);
+                        if new_turns <= 0 {
+                            if let Some(message) = status_end_message(status, &active.name) {
+                                logs.push(message);
+                            }
+                        }
                     
-                    // Check if lock_move with confuseOnEnd is expiring
-                    if new_turns <= 0 && status.id == "lock_move" {
-                        if let Some(Value::Bool(true)) = status.data.get("confuseOnEnd") {
-                            apply_confusion = true;
+                        // Check if lock_move with confuseOnEnd is expiring
+                        if new_turns <= 0 && status.id == "lock_move" {
+                            if let Some(Value::Bool(true)) = status.data.get("confuseOnEnd") {
+                                apply_confusion = true;
+                            }
                         }
                     }
                 }
-            }
             
-            active
-                .statuses
-                .retain(|s| s.remaining_turns.map(|t| t > 0).unwrap_or(true));
+                active
+                    .statuses
+                    .retain(|s| s.remaining_turns.map(|t| t > 0).unwrap_or(true));
             
-            // Apply confusion if needed (from expiring lock_move with confuseOnEnd)
-            if apply_confusion && active.hp > 0 {
-                // Check if not already confused
-                if !active.statuses.iter().any(|s| s.id == "confusion") {
-                    let rng_data = HashMap::new();
-                    // Duration 2-4 turns (pseudo-random based on turn number)
-                    let duration = 2 + ((state.turn % 3) as i32);
-                    active.statuses.push(Status {
-                        id: "confusion".to_string(),
-                        remaining_turns: Some(duration),
-                        data: rng_data,
-                    });
-                    next.log.push(format!("{}は 混乱してしまった！", active.name));
+                // Apply confusion if needed (from expiring lock_move with confuseOnEnd)
+                if apply_confusion && active.hp > 0 {
+                    // Check if not already confused
+                    if !active.statuses.iter().any(|s| s.id == "confusion") {
+                        let rng_data = HashMap::new();
+                        // Duration 2-4 turns (pseudo-random based on turn number)
+                        let duration = 2 + ((state.turn % 3) as i32);
+                        active.statuses.push(Status {
+                            id: "confusion".to_string(),
+                            remaining_turns: Some(duration),
+                            data: rng_data,
+                        });
+                        logs.push(format!("{}は 混乱してしまった！", active.name));
+                    }
                 }
             }
         }
     }
+    for message in logs {
+        next.push_log(LogCategory::Status, message);
+    }
     next
 }
 
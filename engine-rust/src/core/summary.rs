@@ -0,0 +1,128 @@
+use crate::core::state::{BattleState, CreatureState, FieldEffect, StatStages};
+use serde::{Deserialize, Serialize};
+
+/// Differences between two states, so frontends can animate a turn without parsing the log.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TurnSummary {
+    /// Only creatures that changed in some way.
+    pub creatures: Vec<CreatureChange>,
+    pub field_started: Vec<FieldChange>,
+    pub field_ended: Vec<FieldChange>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CreatureChange {
+    pub player_id: String,
+    pub slot: usize,
+    pub creature_id: String,
+    pub name: String,
+    /// `after.hp - before.hp`; negative for damage.
+    pub hp_delta: i32,
+    pub hp_after: i32,
+    pub statuses_gained: Vec<String>,
+    pub statuses_lost: Vec<String>,
+    pub stage_changes: Vec<StageChange>,
+    pub fainted: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StageChange {
+    pub stat: String,
+    pub delta: i32,
+}
+
+/// `side` is None for global effects such as weather.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldChange {
+    pub side: Option<String>,
+    pub effect_id: String,
+}
+
+pub fn turn_summary(before: &BattleState, after: &BattleState) -> TurnSummary {
+    let mut summary = TurnSummary::default();
+
+    for player in &after.players {
+        let previous = before.players.iter().find(|p| p.id == player.id);
+        for (slot, creature) in player.team.iter().enumerate() {
+            let Some(old) = previous.and_then(|p| p.team.get(slot)) else {
+                continue;
+            };
+            let change = creature_change(&player.id, slot, old, creature);
+            if change.hp_delta != 0
+                || change.fainted
+                || !change.statuses_gained.is_empty()
+                || !change.statuses_lost.is_empty()
+                || !change.stage_changes.is_empty()
+            {
+                summary.creatures.push(change);
+            }
+        }
+    }
+
+    let before_field = field_entries(before);
+    let after_field = field_entries(after);
+    summary.field_started = after_field
+        .iter()
+        .filter(|entry| !before_field.contains(entry))
+        .cloned()
+        .collect();
+    summary.field_ended = before_field
+        .iter()
+        .filter(|entry| !after_field.contains(entry))
+        .cloned()
+        .collect();
+    summary
+}
+
+fn creature_change(player_id: &str, slot: usize, old: &CreatureState, new: &CreatureState) -> CreatureChange {
+    let status_ids = |c: &CreatureState| c.statuses.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+    let old_statuses = status_ids(old);
+    let new_statuses = status_ids(new);
+
+    CreatureChange {
+        player_id: player_id.to_string(),
+        slot,
+        creature_id: new.id.clone(),
+        name: new.name.clone(),
+        hp_delta: new.hp - old.hp,
+        hp_after: new.hp,
+        statuses_gained: new_statuses.iter().filter(|id| !old_statuses.contains(id)).cloned().collect(),
+        statuses_lost: old_statuses.iter().filter(|id| !new_statuses.contains(id)).cloned().collect(),
+        stage_changes: stage_changes(&old.stages, &new.stages),
+        fainted: old.hp > 0 && new.hp <= 0,
+    }
+}
+
+fn stage_changes(old: &StatStages, new: &StatStages) -> Vec<StageChange> {
+    [
+        ("atk", old.atk, new.atk),
+        ("def", old.def, new.def),
+        ("spa", old.spa, new.spa),
+        ("spd", old.spd, new.spd),
+        ("spe", old.spe, new.spe),
+        ("accuracy", old.accuracy, new.accuracy),
+        ("evasion", old.evasion, new.evasion),
+        ("crit", old.crit, new.crit),
+    ]
+    .into_iter()
+    .filter(|(_, before, after)| before != after)
+    .map(|(stat, before, after)| StageChange {
+        stat: stat.to_string(),
+        delta: after - before,
+    })
+    .collect()
+}
+
+fn field_entries(state: &BattleState) -> Vec<FieldChange> {
+    let entry = |side: Option<&String>, effect: &FieldEffect| FieldChange {
+        side: side.cloned(),
+        effect_id: effect.id.clone(),
+    };
+    let mut entries: Vec<FieldChange> = state.field.global.iter().map(|e| entry(None, e)).collect();
+    let mut sides: Vec<_> = state.field.sides.iter().collect();
+    sides.sort_by(|a, b| a.0.cmp(b.0));
+    for (side, effects) in sides {
+        entries.extend(effects.iter().map(|e| entry(Some(side), e)));
+    }
+    entries
+}
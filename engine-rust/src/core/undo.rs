@@ -0,0 +1,90 @@
+use crate::core::state::{BattleState, CreatureState, FieldState};
+
+/// Everything a single turn can change, captured so the turn can be rolled back in place.
+/// The log and history are only truncated, never copied.
+#[derive(Clone, Debug)]
+pub struct UndoToken {
+    turn: u32,
+    log_len: usize,
+    log_categories_len: usize,
+    history_len: Option<usize>,
+    field: FieldState,
+    players: Vec<PlayerUndo>,
+}
+
+#[derive(Clone, Debug)]
+struct PlayerUndo {
+    active_slot: usize,
+    partner_slot: Option<usize>,
+    last_fainted_ability: Option<String>,
+    team: Vec<CreatureState>,
+}
+
+impl BattleState {
+    pub fn snapshot(&self) -> UndoToken {
+        UndoToken {
+            turn: self.turn,
+            log_len: self.log.len(),
+            log_categories_len: self.log_categories.len(),
+            history_len: self.history.as_ref().map(|h| h.turns.len()),
+            field: self.field.clone(),
+            players: self
+                .players
+                .iter()
+                .map(|p| PlayerUndo {
+                    active_slot: p.active_slot,
+                    partner_slot: p.partner_slot,
+                    last_fainted_ability: p.last_fainted_ability.clone(),
+                    team: p.team.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Like `snapshot`, but consumes the state instead of cloning it. The log lengths are
+    /// passed in because `apply_turn` has already moved the log out.
+    pub(crate) fn into_undo_token(self, log_len: usize, log_categories_len: usize) -> UndoToken {
+        UndoToken {
+            turn: self.turn,
+            log_len,
+            log_categories_len,
+            history_len: self.history.as_ref().map(|h| h.turns.len()),
+            field: self.field,
+            players: self
+                .players
+                .into_iter()
+                .map(|p| PlayerUndo {
+                    active_slot: p.active_slot,
+                    partner_slot: p.partner_slot,
+                    last_fainted_ability: p.last_fainted_ability,
+                    team: p.team,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn restore(&mut self, token: UndoToken) {
+        self.turn = token.turn;
+        self.log.truncate(token.log_len);
+        self.log_categories.truncate(token.log_categories_len);
+        match token.history_len {
+            Some(len) => {
+                if let Some(history) = self.history.as_mut() {
+                    history.turns.truncate(len);
+                }
+            }
+            None => self.history = None,
+        }
+        self.field = token.field;
+        for (player, saved) in self.players.iter_mut().zip(token.players) {
+            player.active_slot = saved.active_slot;
+            player.partner_slot = saved.partner_slot;
+            player.last_fainted_ability = saved.last_fainted_ability;
+            player.team = saved.team;
+        }
+    }
+}
+
+pub fn undo(state: &mut BattleState, token: UndoToken) {
+    state.restore(token);
+}
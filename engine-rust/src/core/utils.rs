@@ -1,5 +1,5 @@
-use crate::core::state::{BattleState, CreatureState};
-use crate::data::moves::MoveData;
+use crate::core::state::{BattleState, CreatureState, PlayerState};
+use crate::data::moves::{MoveCategory, MoveData};
 
 pub fn stage_multiplier(stage: i32) -> f32 {
     let s = stage.clamp(-6, 6);
@@ -10,24 +10,86 @@ pub fn stage_multiplier(stage: i32) -> f32 {
     }
 }
 
+/// 命中率・回避率のランク補正（3/3 を基準にした表）
+pub fn accuracy_stage_multiplier(stage: i32) -> f32 {
+    let s = stage.clamp(-6, 6);
+    if s >= 0 {
+        (3.0 + s as f32) / 3.0
+    } else {
+        3.0 / (3.0 - s as f32)
+    }
+}
+
 pub fn is_status_move(move_data: &MoveData) -> bool {
-    match move_data.category.as_deref() {
-        Some("status") => true,
-        Some("physical") | Some("special") => false,
-        _ => !move_data
-            .steps
-            .iter()
-            .any(|effect| effect.effect_type == "damage"),
+    move_data.resolved_category() == MoveCategory::Status
+}
+
+/// 地面にいるか（ひこうタイプ・ふゆうは浮いている。撃ち落とされていれば地面扱い）
+pub fn is_grounded(creature: &CreatureState) -> bool {
+    if creature.statuses.iter().any(|s| s.id == "grounded") {
+        return true;
+    }
+    let is_flying = creature.types.iter().any(|t| t == "flying");
+    let has_levitate = creature.ability.as_deref() == Some("levitate");
+    !is_flying && !has_levitate
+}
+
+/// ダブルバトルの2体目は "p1:1" のように場の位置を付けたIDで指す。1体目はプレイヤーIDのまま
+pub fn battler_id(player_id: &str, position: usize) -> String {
+    if position == 0 {
+        player_id.to_string()
+    } else {
+        format!("{}:{}", player_id, position)
     }
 }
 
+/// バトラーIDをプレイヤーIDと場の位置に分ける
+pub fn split_battler_id(id: &str) -> (&str, usize) {
+    match id.rsplit_once(':').map(|(player_id, position)| (player_id, position.parse())) {
+        Some((player_id, Ok(position))) => (player_id, position),
+        _ => (id, 0),
+    }
+}
+
+/// 同じ側に並んでいるもう1体のバトラーID
+pub fn ally_battler_id(id: &str) -> String {
+    let (player_id, position) = split_battler_id(id);
+    battler_id(player_id, if position == 0 { 1 } else { 0 })
+}
+
+/// 場に出ている全バトラーのID。シングルではプレイヤーIDの並びと同じ
+pub fn active_battler_ids(state: &BattleState) -> Vec<String> {
+    state
+        .players
+        .iter()
+        .flat_map(|p| (0..p.active_slots().len()).map(move |position| battler_id(&p.id, position)))
+        .collect()
+}
+
+pub fn is_doubles(state: &BattleState) -> bool {
+    state.players.iter().any(|p| p.partner_slot.is_some())
+}
+
+/// バトラーIDの持ち主と、その位置に出ている手持ちの位置
+pub fn find_battler<'a>(players: &'a [PlayerState], id: &str) -> Option<(&'a PlayerState, usize)> {
+    let (player_id, position) = split_battler_id(id);
+    let player = players.iter().find(|p| p.id == player_id)?;
+    Some((player, player.active_slot_at(position)?))
+}
+
+pub fn find_battler_mut<'a>(players: &'a mut [PlayerState], id: &str) -> Option<(&'a mut PlayerState, usize)> {
+    let (player_id, position) = split_battler_id(id);
+    let player = players.iter_mut().find(|p| p.id == player_id)?;
+    let slot = player.active_slot_at(position)?;
+    Some((player, slot))
+}
+
 pub fn get_active_creature<'a>(state: &'a BattleState, player_id: &str) -> Option<&'a CreatureState> {
-    let player = state.players.iter().find(|p| p.id == player_id)?;
-    player.team.get(player.active_slot)
+    let (player, slot) = find_battler(&state.players, player_id)?;
+    player.team.get(slot)
 }
 
 pub fn get_active_creature_mut<'a>(state: &'a mut BattleState, player_id: &str) -> Option<&'a mut CreatureState> {
-    let idx = state.players.iter().position(|p| p.id == player_id)?;
-    let active_slot = state.players[idx].active_slot;
-    state.players[idx].team.get_mut(active_slot)
+    let (player, slot) = find_battler_mut(&mut state.players, player_id)?;
+    player.team.get_mut(slot)
 }
@@ -1,8 +1,56 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Map;
 use std::collections::HashMap;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// 技の分類。JSON / YAML では `"physical"` などの小文字文字列
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoveCategory {
+    Physical,
+    Special,
+    Status,
+}
+
+impl MoveCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MoveCategory::Physical => "physical",
+            MoveCategory::Special => "special",
+            MoveCategory::Status => "status",
+        }
+    }
+}
+
+impl FromStr for MoveCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "physical" => Ok(MoveCategory::Physical),
+            "special" => Ok(MoveCategory::Special),
+            "status" => Ok(MoveCategory::Status),
+            other => Err(format!("unknown move category '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for MoveCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// 大文字混じりも受け付けるが、綴りの誤りは読み込み時にエラーにする
+impl<'de> Deserialize<'de> for MoveCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MoveData {
@@ -11,7 +59,8 @@ pub struct MoveData {
     pub name: Option<String>,
     #[serde(rename = "type")]
     pub move_type: Option<String>,
-    pub category: Option<String>,
+    #[serde(default)]
+    pub category: Option<MoveCategory>,
     pub pp: Option<i32>,
     pub power: Option<i32>,
     pub accuracy: Option<f32>,
@@ -26,6 +75,19 @@ pub struct MoveData {
     pub crit_rate: Option<i32>,
 }
 
+impl MoveData {
+    /// 分類が書かれていなければ damage があれば物理、なければ変化技とみなす
+    pub fn resolved_category(&self) -> MoveCategory {
+        self.category.unwrap_or_else(|| {
+            if self.steps.iter().any(|effect| effect.effect_type == "damage") {
+                MoveCategory::Physical
+            } else {
+                MoveCategory::Status
+            }
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Effect {
     #[serde(rename = "type")]
@@ -37,12 +99,15 @@ pub struct Effect {
 #[derive(Clone, Debug)]
 pub struct MoveDatabase {
     moves: HashMap<String, MoveData>,
+    /// 表示名 → 技ID。同じ表示名が複数あれば ID の小さい方
+    names: HashMap<String, String>,
 }
 
 impl MoveDatabase {
     pub fn new() -> Self {
         Self {
             moves: HashMap::new(),
+            names: HashMap::new(),
         }
     }
 
@@ -52,7 +117,7 @@ impl MoveDatabase {
             id: "tackle".to_string(),
             name: Some("Tackle".to_string()),
             move_type: Some("normal".to_string()),
-            category: Some("physical".to_string()),
+            category: Some(MoveCategory::Physical),
             pp: Some(35),
             power: Some(40),
             accuracy: Some(1.0),
@@ -66,7 +131,7 @@ impl MoveDatabase {
             id: "ember".to_string(),
             name: Some("Ember".to_string()),
             move_type: Some("fire".to_string()),
-            category: Some("special".to_string()),
+            category: Some(MoveCategory::Special),
             pp: Some(25),
             power: Some(40),
             accuracy: Some(1.0),
@@ -80,7 +145,7 @@ impl MoveDatabase {
             id: "water_gun".to_string(),
             name: Some("Water Gun".to_string()),
             move_type: Some("water".to_string()),
-            category: Some("special".to_string()),
+            category: Some(MoveCategory::Special),
             pp: Some(25),
             power: Some(40),
             accuracy: Some(1.0),
@@ -94,7 +159,7 @@ impl MoveDatabase {
             id: "vine_whip".to_string(),
             name: Some("Vine Whip".to_string()),
             move_type: Some("grass".to_string()),
-            category: Some("physical".to_string()),
+            category: Some(MoveCategory::Physical),
             pp: Some(25),
             power: Some(45),
             accuracy: Some(1.0),
@@ -108,7 +173,7 @@ impl MoveDatabase {
             id: "thunder_shock".to_string(),
             name: Some("Thunder Shock".to_string()),
             move_type: Some("electric".to_string()),
-            category: Some("special".to_string()),
+            category: Some(MoveCategory::Special),
             pp: Some(30),
             power: Some(40),
             accuracy: Some(1.0),
@@ -122,7 +187,7 @@ impl MoveDatabase {
             id: "growl".to_string(),
             name: Some("Growl".to_string()),
             move_type: Some("normal".to_string()),
-            category: Some("status".to_string()),
+            category: Some(MoveCategory::Status),
             pp: Some(40),
             power: Some(0),
             accuracy: Some(1.0),
@@ -150,13 +215,47 @@ impl MoveDatabase {
     }
 
     pub fn insert(&mut self, move_data: MoveData) {
-        self.moves.insert(move_data.id.clone(), move_data);
+        let id = move_data.id.clone();
+        let name = move_data.name.clone();
+        let replaced = self.moves.insert(id.clone(), move_data);
+        if let Some(old_name) = replaced.and_then(|old| old.name) {
+            if self.names.get(&old_name) == Some(&id) {
+                self.names.remove(&old_name);
+                self.reindex_name(&old_name);
+            }
+        }
+        if let Some(name) = name {
+            match self.names.get(&name) {
+                Some(existing) if *existing <= id => {}
+                _ => {
+                    self.names.insert(name, id);
+                }
+            }
+        }
+    }
+
+    /// 表示名 `name` を持つ技のうち ID が最小のものを索引に入れ直す
+    fn reindex_name(&mut self, name: &str) {
+        let first = self
+            .moves
+            .values()
+            .filter(|m| m.name.as_deref() == Some(name))
+            .map(|m| m.id.as_str())
+            .min();
+        if let Some(id) = first {
+            self.names.insert(name.to_string(), id.to_string());
+        }
     }
 
     pub fn get(&self, move_id: &str) -> Option<&MoveData> {
         self.moves.get(move_id)
     }
 
+    /// 表示名 (「たいあたり」など) から技を引く。同名の技が複数あれば ID の小さい方
+    pub fn by_name(&self, name: &str) -> Option<&MoveData> {
+        self.names.get(name).and_then(|id| self.moves.get(id))
+    }
+
     pub fn as_map(&self) -> &HashMap<String, MoveData> {
         &self.moves
     }
@@ -21,6 +21,9 @@ pub struct SpeciesData {
     pub base_stats: BaseStats,
     #[serde(default)]
     pub abilities: Vec<String>,
+    /// 重さ (kg)。未設定なら重さで判定する技の対象にならない
+    #[serde(default)]
+    pub weight: Option<f32>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Clone, Debug)]
 pub struct TypeEntry {
@@ -7,6 +8,19 @@ pub struct TypeEntry {
     pub weak_to: Vec<String>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTypeEntry {
+    #[serde(default)]
+    super_effective: Vec<String>,
+    #[serde(default)]
+    resists: Vec<String>,
+    #[serde(default)]
+    weak_to: Vec<String>,
+    #[serde(default)]
+    immune_to: Vec<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct TypeChart {
     chart: HashMap<String, TypeEntry>,
@@ -15,71 +29,127 @@ pub struct TypeChart {
 
 impl TypeChart {
     pub fn new() -> Self {
+        Self::load_default().expect("data/type_chart.json must be a valid type chart")
+    }
+
+    /// 防御タイプごとに `superEffective` / `resists` / `weakTo` / `immuneTo` を並べた JSON から読み込む
+    pub fn load_from_json_str(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let raw: HashMap<String, RawTypeEntry> = serde_json::from_str(json)?;
         let mut chart = HashMap::new();
-        let mut add_entry = |type_name: &str, super_effective: &[&str], resists: &[&str], weak_to: &[&str]| {
+        let mut immunities = HashMap::new();
+        for (type_name, entry) in raw {
+            if !entry.immune_to.is_empty() {
+                immunities.insert(type_name.clone(), entry.immune_to);
+            }
             chart.insert(
-                type_name.to_string(),
+                type_name,
                 TypeEntry {
-                    super_effective: super_effective.iter().map(|v| v.to_string()).collect(),
-                    resists: resists.iter().map(|v| v.to_string()).collect(),
-                    weak_to: weak_to.iter().map(|v| v.to_string()).collect(),
+                    super_effective: entry.super_effective,
+                    resists: entry.resists,
+                    weak_to: entry.weak_to,
                 },
             );
-        };
+        }
+        Ok(Self { chart, immunities })
+    }
 
-        add_entry("normal", &[], &[], &["fighting"]);
-        add_entry("fire", &["grass", "ice", "bug", "steel"], &["grass", "ice", "bug", "steel", "fairy"], &["water", "ground", "rock"]);
-        add_entry("water", &["fire", "ground", "rock"], &["steel", "fire", "water"], &["electric", "grass"]);
-        add_entry("electric", &["water", "flying"], &["flying", "steel", "electric"], &["ground"]);
-        add_entry("grass", &["water", "ground", "rock"], &["ground", "water", "grass"], &["fire", "ice", "poison", "flying", "bug"]);
-        add_entry("ice", &["flying", "ground", "grass", "dragon"], &["ice"], &["fire", "fighting", "rock", "steel"]);
-        add_entry("fighting", &["normal", "ice", "rock", "dark", "steel"], &["rock", "bug", "dark"], &["flying", "psychic", "fairy"]);
-        add_entry("poison", &["grass", "fairy"], &["grass", "fighting", "poison", "bug"], &["ground", "psychic"]);
-        add_entry("ground", &["fire", "electric", "poison", "rock", "steel"], &["poison", "rock"], &["water", "grass", "ice"]);
-        add_entry("flying", &["fighting", "bug", "grass"], &["fighting", "bug", "grass"], &["electric", "ice", "rock"]);
-        add_entry("psychic", &["fighting", "poison"], &["fighting", "psychic"], &["bug", "ghost", "dark"]);
-        add_entry("bug", &["grass", "psychic", "dark"], &["grass", "fighting", "ground"], &["fire", "flying", "rock"]);
-        add_entry("rock", &["flying", "bug", "fire", "ice"], &["normal", "flying", "poison", "fire"], &["water", "grass", "fighting", "ground", "steel"]);
-        add_entry("ghost", &["ghost", "psychic"], &["poison", "bug"], &["ghost", "dark"]);
-        add_entry("dragon", &["dragon"], &["grass", "fire", "water", "electric"], &["ice", "dragon", "fairy"]);
-        add_entry("dark", &["ghost", "psychic"], &["ghost", "dark"], &["fighting", "bug", "fairy"]);
-        add_entry("steel", &["ice", "rock", "fairy"], &["normal", "flying", "rock", "bug", "steel", "grass", "psychic", "ice", "dragon", "fairy"], &["fire", "water", "ground"]);
-        add_entry("fairy", &["fighting", "dragon", "dark"], &["fighting", "bug", "dark"], &["poison", "steel"]);
+    pub fn load_default() -> Result<Self, Box<dyn std::error::Error>> {
+        const DEFAULT_TYPE_CHART_JSON: &str = include_str!("../../data/type_chart.json");
+        Self::load_from_json_str(DEFAULT_TYPE_CHART_JSON)
+    }
 
-        let mut immunities = HashMap::new();
-        immunities.insert("normal".to_string(), vec!["ghost".to_string()]);
-        immunities.insert("ghost".to_string(), vec!["normal".to_string(), "fighting".to_string()]);
-        immunities.insert("steel".to_string(), vec!["poison".to_string()]);
-        immunities.insert("flying".to_string(), vec!["ground".to_string()]);
-        immunities.insert("dark".to_string(), vec!["psychic".to_string()]);
-        immunities.insert("ground".to_string(), vec!["electric".to_string()]);
-        immunities.insert("fairy".to_string(), vec!["dragon".to_string()]);
+    pub fn effectiveness(&self, move_type: &str, target_types: &[String]) -> f32 {
+        self.effectiveness_breakdown(move_type, target_types)
+            .iter()
+            .map(|(_, multiplier)| multiplier)
+            .product()
+    }
 
-        Self { chart, immunities }
+    /// 防御側のタイプごとの倍率。`effectiveness` はこれを掛け合わせたもの。
+    /// 例: みず技 → ほのお/じめん なら `[("fire", 2.0), ("ground", 2.0)]`
+    pub fn effectiveness_breakdown(&self, move_type: &str, target_types: &[String]) -> Vec<(String, f32)> {
+        let move_key = move_type.to_lowercase();
+        target_types
+            .iter()
+            .map(|target_type| {
+                let target_key = target_type.to_lowercase();
+                let multiplier = if move_key.is_empty() {
+                    1.0
+                } else {
+                    self.single_effectiveness(&move_key, &target_key)
+                };
+                (target_key, multiplier)
+            })
+            .collect()
     }
 
-    pub fn effectiveness(&self, move_type: &str, target_types: &[String]) -> f32 {
-        if move_type.is_empty() {
-            return 1.0;
+    fn single_effectiveness(&self, move_key: &str, target_key: &str) -> f32 {
+        if self
+            .immunities
+            .get(target_key)
+            .is_some_and(|immune_to| immune_to.iter().any(|t| t == move_key))
+        {
+            return 0.0;
         }
-        let move_key = move_type.to_lowercase();
+        let Some(chart) = self.chart.get(target_key) else {
+            return 1.0;
+        };
         let mut multiplier = 1.0;
-        for target_type in target_types {
-            let target_key = target_type.to_lowercase();
-            if let Some(immunities) = self.immunities.get(&target_key) {
-                if immunities.iter().any(|t| t == &move_key) {
-                    return 0.0;
-                }
+        if chart.weak_to.iter().any(|t| t == move_key) {
+            multiplier *= 2.0;
+        }
+        if chart.resists.iter().any(|t| t == move_key) {
+            multiplier *= 0.5;
+        }
+        multiplier
+    }
+
+    /// さかさバトル用の相性表。抜群と今ひとつが入れ替わり、無効は抜群になる
+    pub fn inverted(&self) -> TypeChart {
+        let mut chart = self.chart.clone();
+        for (type_name, entry) in chart.iter_mut() {
+            std::mem::swap(&mut entry.weak_to, &mut entry.resists);
+            if let Some(immune_to) = self.immunities.get(type_name) {
+                entry.weak_to.extend(immune_to.iter().cloned());
             }
-            if let Some(chart) = self.chart.get(&target_key) {
-                if chart.weak_to.iter().any(|t| t == &move_key) {
-                    multiplier *= 2.0;
-                }
-                if chart.resists.iter().any(|t| t == &move_key) {
-                    multiplier *= 0.5;
-                }
+        }
+        let weak_lists: Vec<(String, Vec<String>)> =
+            chart.iter().map(|(name, entry)| (name.clone(), entry.weak_to.clone())).collect();
+        for (type_name, entry) in chart.iter_mut() {
+            let mut super_effective: Vec<String> = weak_lists
+                .iter()
+                .filter(|(_, weak_to)| weak_to.contains(type_name))
+                .map(|(defending, _)| defending.clone())
+                .collect();
+            super_effective.sort();
+            entry.super_effective = super_effective;
+        }
+        TypeChart {
+            chart,
+            immunities: HashMap::new(),
+        }
+    }
+
+    /// 単タイプ同士の相性表 (攻撃タイプ, 防御タイプ, 倍率)。タイプ名順に並ぶ
+    pub fn effectiveness_table(&self) -> Vec<(String, String, f32)> {
+        let mut types: Vec<&String> = self.chart.keys().collect();
+        types.sort();
+        let mut table = Vec::with_capacity(types.len() * types.len());
+        for attacking in &types {
+            for defending in &types {
+                let multiplier = self.effectiveness(attacking, std::slice::from_ref(*defending));
+                table.push(((*attacking).clone(), (*defending).clone(), multiplier));
             }
         }
-        multiplier
+        table
+    }
+
+    /// `{"攻撃タイプ": {"防御タイプ": 倍率}}` 形式の JSON
+    pub fn to_json(&self) -> String {
+        let mut nested: BTreeMap<String, BTreeMap<String, f32>> = BTreeMap::new();
+        for (attacking, defending, multiplier) in self.effectiveness_table() {
+            nested.entry(attacking).or_default().insert(defending, multiplier);
+        }
+        serde_json::to_string(&nested).unwrap_or_default()
     }
 }
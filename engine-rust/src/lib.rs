@@ -5,12 +5,15 @@ pub mod data;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod tools;
 
-pub use ai::{get_best_move_mcts, get_best_move_minimax, run_auto_battle, choose_highest_power};
+pub use ai::{get_best_move_mcts, get_best_move_mcts_with_policy, get_best_move_minimax, get_best_move_minimax_fog, get_best_move_with_config, run_auto_battle, run_many_battles, choose_highest_power, AiConfig, AiKind, BattleStats, RolloutPolicy};
 pub use core::{
-    battle::{is_battle_over, step_battle, BattleEngine, BattleOptions},
+    battle::{is_battle_over, legal_actions, pending_switch_players, step_battle, team_preview, BattleEngine, BattleOptions, BattleResult, BattleRunner, LogRetention},
+    effect_schema::effect_schema,
     factory::{calc_stat, create_creature, CreateCreatureOptions, EVStats},
-    replay::replay_battle,
+    replay::{battle_timeline, replay_battle, rewind_one_turn, TurnTally},
+    rng::{ReplayRng, SplitMix64Rng},
     state::{create_battle_state, BattleState, PlayerState, CreatureState, FieldState, BattleHistory, BattleTurn, Action},
+    summary::{turn_summary, TurnSummary},
 };
 pub use data::{
     learnsets::LearnsetDatabase,
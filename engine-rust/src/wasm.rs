@@ -1,13 +1,16 @@
 use crate::ai::{get_best_move_mcts, get_best_move_minimax};
-use crate::core::battle::{is_battle_over, step_battle, BattleOptions};
+use crate::core::battle::{is_battle_over, BattleEngine, BattleOptions};
+use crate::core::damage::calc_damage_range_with_crit;
+use crate::core::utils::get_active_creature;
 use crate::core::factory::{create_creature, CreateCreatureOptions, EVStats};
 use crate::core::state::{
     Action, ActionType, BattleHistory, BattleState, BattleTurn, CreatureState, FieldEffect,
-    FieldState, PlayerState, Status,
+    FieldState, LogCategory, MoveOutcome, MoveRecord, PlayerState, Status,
 };
 use crate::data::learnsets::LearnsetDatabase;
 use crate::data::moves::MoveDatabase;
 use crate::data::species::SpeciesDatabase;
+use crate::data::type_chart::TypeChart;
 use crate::core::state::create_battle_state;
 use js_sys::Math;
 use once_cell::sync::Lazy;
@@ -22,6 +25,7 @@ static LEARNSETS_DB: Lazy<LearnsetDatabase> =
     Lazy::new(|| LearnsetDatabase::load_default().unwrap_or_default());
 static MOVE_DB: Lazy<MoveDatabase> =
     Lazy::new(|| MoveDatabase::load_default().unwrap_or_else(|_| MoveDatabase::minimal()));
+static ENGINE: Lazy<BattleEngine> = Lazy::new(|| BattleEngine::new(MOVE_DB.clone(), TypeChart::new()));
 
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -104,6 +108,16 @@ struct CreatureStateWire {
     ability_data: HashMap<String, Value>,
     #[serde(default)]
     volatile_data: HashMap<String, Value>,
+    #[serde(default)]
+    revealed_moves: Vec<String>,
+    #[serde(default)]
+    species_revealed: bool,
+    #[serde(default)]
+    weight: Option<f32>,
+    #[serde(default)]
+    last_damage_dealt: i32,
+    #[serde(default)]
+    last_damage_taken: i32,
     attack: i32,
     defense: i32,
     sp_attack: i32,
@@ -121,6 +135,8 @@ struct PlayerStateWire {
     active_slot: usize,
     #[serde(default)]
     last_fainted_ability: Option<String>,
+    #[serde(default)]
+    partner_slot: Option<usize>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -130,6 +146,8 @@ struct FieldStateWire {
     global: Vec<FieldEffectWire>,
     #[serde(default)]
     sides: HashMap<String, Vec<FieldEffectWire>>,
+    #[serde(default)]
+    last_move: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -151,6 +169,16 @@ struct BattleTurnWire {
     actions: Vec<ActionWire>,
     log: Vec<String>,
     rng: Vec<f64>,
+    #[serde(default)]
+    outcomes: Vec<MoveRecordWire>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveRecordWire {
+    player_id: String,
+    move_id: String,
+    outcome: MoveOutcome,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -167,13 +195,33 @@ struct BattleStateWire {
     turn: u32,
     #[serde(default)]
     log: Vec<String>,
+    #[serde(default)]
+    log_categories: Vec<LogCategory>,
     history: Option<BattleHistoryWire>,
 }
 
+#[derive(Clone, Debug, Serialize)]
+struct LogEntryWire {
+    category: LogCategory,
+    message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DamageRangeWire {
+    min: i32,
+    max: i32,
+    rolls: [i32; 16],
+    type_effectiveness: f32,
+    is_crit: bool,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct StepBattleOptionsWire {
     record_history: Option<bool>,
+    doubles: Option<bool>,
+    team_preview: Option<bool>,
 }
 
 fn js_err(message: impl ToString) -> JsValue {
@@ -267,6 +315,11 @@ impl From<CreatureState> for CreatureStateWire {
             move_pp: creature.move_pp,
             ability_data: creature.ability_data,
             volatile_data: creature.volatile_data,
+            revealed_moves: creature.revealed_moves,
+            species_revealed: creature.species_revealed,
+            weight: creature.weight,
+            last_damage_dealt: creature.last_damage_dealt,
+            last_damage_taken: creature.last_damage_taken,
             attack: creature.attack,
             defense: creature.defense,
             sp_attack: creature.sp_attack,
@@ -294,6 +347,11 @@ impl From<CreatureStateWire> for CreatureState {
             move_pp: creature.move_pp,
             ability_data: creature.ability_data,
             volatile_data: creature.volatile_data,
+            revealed_moves: creature.revealed_moves,
+            species_revealed: creature.species_revealed,
+            weight: creature.weight,
+            last_damage_dealt: creature.last_damage_dealt,
+            last_damage_taken: creature.last_damage_taken,
             attack: creature.attack,
             defense: creature.defense,
             sp_attack: creature.sp_attack,
@@ -311,6 +369,7 @@ impl From<PlayerState> for PlayerStateWire {
             team: player.team.into_iter().map(CreatureStateWire::from).collect(),
             active_slot: player.active_slot,
             last_fainted_ability: player.last_fainted_ability,
+            partner_slot: player.partner_slot,
         }
     }
 }
@@ -323,6 +382,7 @@ impl From<PlayerStateWire> for PlayerState {
             team: player.team.into_iter().map(CreatureState::from).collect(),
             active_slot: player.active_slot,
             last_fainted_ability: player.last_fainted_ability,
+            partner_slot: player.partner_slot,
         }
     }
 }
@@ -336,6 +396,7 @@ impl From<FieldState> for FieldStateWire {
                 .into_iter()
                 .map(|(k, v)| (k, v.into_iter().map(FieldEffectWire::from).collect()))
                 .collect(),
+            last_move: field.last_move,
         }
     }
 }
@@ -349,6 +410,7 @@ impl From<FieldStateWire> for FieldState {
                 .into_iter()
                 .map(|(k, v)| (k, v.into_iter().map(FieldEffect::from).collect()))
                 .collect(),
+            last_move: field.last_move,
         }
     }
 }
@@ -388,6 +450,7 @@ impl From<BattleTurn> for BattleTurnWire {
             actions: turn.actions.into_iter().map(ActionWire::from).collect(),
             log: turn.log,
             rng: turn.rng,
+            outcomes: turn.outcomes.into_iter().map(MoveRecordWire::from).collect(),
         }
     }
 }
@@ -405,10 +468,31 @@ impl TryFrom<BattleTurnWire> for BattleTurn {
                 .collect::<Result<_, _>>()?,
             log: turn.log,
             rng: turn.rng,
+            outcomes: turn.outcomes.into_iter().map(MoveRecord::from).collect(),
         })
     }
 }
 
+impl From<MoveRecord> for MoveRecordWire {
+    fn from(record: MoveRecord) -> Self {
+        Self {
+            player_id: record.player_id,
+            move_id: record.move_id,
+            outcome: record.outcome,
+        }
+    }
+}
+
+impl From<MoveRecordWire> for MoveRecord {
+    fn from(record: MoveRecordWire) -> Self {
+        Self {
+            player_id: record.player_id,
+            move_id: record.move_id,
+            outcome: record.outcome,
+        }
+    }
+}
+
 impl From<BattleHistory> for BattleHistoryWire {
     fn from(history: BattleHistory) -> Self {
         Self {
@@ -438,6 +522,7 @@ impl From<BattleState> for BattleStateWire {
             field: FieldStateWire::from(state.field),
             turn: state.turn,
             log: state.log,
+            log_categories: state.log_categories,
             history: state.history.map(BattleHistoryWire::from),
         }
     }
@@ -452,6 +537,7 @@ impl TryFrom<BattleStateWire> for BattleState {
             field: FieldState::from(state.field),
             turn: state.turn,
             log: state.log,
+            log_categories: state.log_categories,
             history: match state.history {
                 Some(history) => Some(BattleHistory::try_from(history)?),
                 None => None,
@@ -539,8 +625,11 @@ pub fn step_battle_wasm(
     let mut rng = || Math::random();
     let options = BattleOptions {
         record_history: options_wire.record_history.unwrap_or(true),
+        doubles: options_wire.doubles.unwrap_or(false),
+        team_preview: options_wire.team_preview.unwrap_or(false),
+        ..Default::default()
     };
-    let next_state = step_battle(&state, &actions, &mut rng, options);
+    let next_state = ENGINE.step_battle(&state, &actions, &mut rng, options);
     serde_wasm_bindgen::to_value(&BattleStateWire::from(next_state)).map_err(js_err)
 }
 
@@ -551,6 +640,51 @@ pub fn is_battle_over_wasm(state: JsValue) -> Result<bool, JsValue> {
     Ok(is_battle_over(&state))
 }
 
+#[wasm_bindgen(js_name = getLogEntries)]
+pub fn get_log_entries_wasm(state: JsValue) -> Result<JsValue, JsValue> {
+    let state_wire: BattleStateWire = serde_wasm_bindgen::from_value(state).map_err(js_err)?;
+    let state = BattleState::try_from(state_wire).map_err(js_err)?;
+    let entries: Vec<LogEntryWire> = state
+        .log_entries()
+        .into_iter()
+        .map(|(category, message)| LogEntryWire {
+            category,
+            message: message.to_string(),
+        })
+        .collect();
+    serde_wasm_bindgen::to_value(&entries).map_err(js_err)
+}
+
+#[wasm_bindgen(js_name = calcDamageRange)]
+pub fn calc_damage_range_wasm(
+    state: JsValue,
+    attacker_id: String,
+    target_id: String,
+    move_id: String,
+    crit: Option<bool>,
+) -> Result<JsValue, JsValue> {
+    let crit = crit.unwrap_or(false);
+    let state_wire: BattleStateWire = serde_wasm_bindgen::from_value(state).map_err(js_err)?;
+    let state = BattleState::try_from(state_wire).map_err(js_err)?;
+    ENGINE
+        .move_db
+        .get(&move_id)
+        .ok_or_else(|| js_err(format!("Unknown move id: {}", move_id)))?;
+    get_active_creature(&state, &attacker_id)
+        .ok_or_else(|| js_err(format!("Unknown attacker id: {}", attacker_id)))?;
+    get_active_creature(&state, &target_id)
+        .ok_or_else(|| js_err(format!("Unknown target id: {}", target_id)))?;
+    let range = calc_damage_range_with_crit(&state, &attacker_id, &target_id, &move_id, &ENGINE, crit);
+    let wire = DamageRangeWire {
+        min: range.min,
+        max: range.max,
+        rolls: range.rolls,
+        type_effectiveness: range.effectiveness,
+        is_crit: crit,
+    };
+    serde_wasm_bindgen::to_value(&wire).map_err(js_err)
+}
+
 #[wasm_bindgen(js_name = getBestMoveMinimax)]
 pub fn get_best_move_minimax_wasm(
     state: JsValue,
@@ -1,7 +1,7 @@
 use engine_rust::core::abilities::{run_ability_check_hook, run_ability_value_hook, AbilityCheckContext, AbilityValueContext};
 use engine_rust::core::battle::{BattleEngine, BattleOptions};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages};
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -31,6 +31,11 @@ fn make_creature(id: &str, name: &str, ability: Option<&str>, moves: Vec<String>
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -42,27 +47,17 @@ fn make_creature(id: &str, name: &str, ability: Option<&str>, moves: Vec<String>
 fn make_state(p1: CreatureState, p2: CreatureState) -> BattleState {
     BattleState {
         players: vec![
-            PlayerState {
-                id: "p1".to_string(),
-                name: "P1".to_string(),
-                team: vec![p1],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
-            PlayerState {
-                id: "p2".to_string(),
-                name: "P2".to_string(),
-                team: vec![p2],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
+            PlayerState::new("p1", "P1", vec![p1]),
+            PlayerState::new("p2", "P2", vec![p2]),
         ],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -74,7 +69,7 @@ fn soundproof_blocks_sound_tagged_damage() {
         id: "echo".to_string(),
         name: Some("Echo".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("special".to_string()),
+        category: Some(MoveCategory::Special),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -88,7 +83,7 @@ fn soundproof_blocks_sound_tagged_damage() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
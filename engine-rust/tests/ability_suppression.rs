@@ -0,0 +1,55 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, switch_action, CreatureBuilder};
+
+fn intimidate_state(foe_ability: &str) -> BattleState {
+    let lead = CreatureBuilder::new("p1-lead", "Lead").moves(&["harden"]).build();
+    let intimidator = CreatureBuilder::new("p1-bench", "Intimidator")
+        .ability("intimidate")
+        .moves(&["harden"])
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .ability(foe_ability)
+        .moves(&["harden"])
+        .build();
+    battle_state(vec![
+        player("p1", "P1", vec![lead, intimidator]),
+        player("p2", "P2", vec![foe]),
+    ])
+}
+
+#[test]
+fn neutralizing_gas_blocks_switch_in_intimidate() {
+    let engine = BattleEngine::default();
+    let actions = vec![switch_action("p1", 1), move_action("p2", "harden", "p2")];
+
+    let control = run_turn_with_seed(&engine, &intimidate_state("run_away"), &actions, 1);
+    assert_eq!(control.players[1].team[0].stages.atk, -1);
+
+    let gassed = run_turn_with_seed(&engine, &intimidate_state("neutralizing_gas"), &actions, 1);
+    assert_eq!(gassed.players[0].active_slot, 1);
+    assert_eq!(gassed.players[1].team[0].stages.atk, 0);
+}
+
+#[test]
+fn simple_beam_changes_target_ability_to_simple() {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["simple_beam"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .ability("intimidate")
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "simple_beam", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(next.players[1].team[0].ability.as_deref(), Some("simple"));
+    // Harden is used after the ability change, so Simple doubles it.
+    assert_eq!(next.players[1].team[0].stages.def, 2);
+}
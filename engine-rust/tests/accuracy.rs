@@ -0,0 +1,73 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use engine_rust::core::utils::accuracy_stage_multiplier;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+const TRIALS: u64 = 60;
+
+fn accuracy_state(attacker_accuracy: i32, target_evasion: i32) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle", "swift"])
+        .hp(200, 200)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    attacker.stages.accuracy = attacker_accuracy;
+    let mut target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(200, 200)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    target.stages.evasion = target_evasion;
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])])
+}
+
+fn count_hits(state: &BattleState) -> u64 {
+    count_move_hits(state, "tackle")
+}
+
+fn count_move_hits(state: &BattleState, move_id: &str) -> u64 {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    (0..TRIALS)
+        .filter(|seed| run_turn_with_seed(&engine, state, &actions, *seed).players[1].team[0].hp < 200)
+        .count() as u64
+}
+
+#[test]
+fn accuracy_stage_table_uses_thirds() {
+    assert_eq!(accuracy_stage_multiplier(0), 1.0);
+    assert_eq!(accuracy_stage_multiplier(1), 4.0 / 3.0);
+    assert_eq!(accuracy_stage_multiplier(6), 3.0);
+    assert_eq!(accuracy_stage_multiplier(-6), 1.0 / 3.0);
+    assert_eq!(accuracy_stage_multiplier(9), 3.0);
+}
+
+#[test]
+fn max_evasion_target_dodges_most_sure_hits() {
+    assert_eq!(count_hits(&accuracy_state(0, 0)), TRIALS);
+    let hits = count_hits(&accuracy_state(0, 6));
+    assert!(hits < TRIALS / 2, "{hits} of {TRIALS} hits landed against +6 evasion");
+    // 命中ランクで相殺すれば必中に戻る
+    assert_eq!(count_hits(&accuracy_state(6, 6)), TRIALS);
+}
+
+#[test]
+fn minimum_accuracy_attacker_whiffs() {
+    let hits = count_hits(&accuracy_state(-6, 0));
+    assert!(hits < TRIALS / 2, "{hits} of {TRIALS} hits landed at -6 accuracy");
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let missed = (0..TRIALS).find_map(|seed| {
+        let next = run_turn_with_seed(&engine, &accuracy_state(-6, 0), &actions, seed);
+        (next.players[1].team[0].hp == 200).then_some(next)
+    });
+    assert!(missed.unwrap().log.iter().any(|line| line == "しかし はずれた！"));
+}
+
+#[test]
+fn moves_without_accuracy_ignore_evasion_and_accuracy_stages() {
+    assert_eq!(count_move_hits(&accuracy_state(0, 6), "swift"), TRIALS);
+    assert_eq!(count_move_hits(&accuracy_state(-6, 0), "swift"), TRIALS);
+}
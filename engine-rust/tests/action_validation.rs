@@ -0,0 +1,66 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::state::{Action, BattleState};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder, SeededRng};
+
+fn duel() -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Eager").moves(&["tackle"]).hp(200, 200).build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target").moves(&["splash"]).hp(200, 200).build()],
+        ),
+    ])
+}
+
+fn try_step(state: &BattleState, actions: &[Action]) -> Result<BattleState, String> {
+    let mut rng = SeededRng::new(3);
+    let mut rng_fn = || rng.next_f64();
+    BattleEngine::default().try_step_battle(state, actions, &mut rng_fn, BattleOptions::default())
+}
+
+#[test]
+fn lenient_step_only_runs_one_action_per_player() {
+    let engine = BattleEngine::default();
+    let once = vec![move_action("p1", "tackle", "p2"), move_action("p2", "splash", "p2")];
+    let twice = vec![
+        move_action("p1", "tackle", "p2"),
+        move_action("p1", "tackle", "p2"),
+        move_action("p2", "splash", "p2"),
+    ];
+    let single = run_turn_with_seed(&engine, &duel(), &once, 3);
+    let doubled = run_turn_with_seed(&engine, &duel(), &twice, 3);
+    assert_eq!(doubled.players[1].team[0].hp, single.players[1].team[0].hp);
+    assert!(doubled.players[1].team[0].hp < 200);
+}
+
+#[test]
+fn try_step_battle_rejects_two_actions_from_one_player() {
+    let actions = vec![
+        move_action("p1", "tackle", "p2"),
+        move_action("p1", "tackle", "p2"),
+        move_action("p2", "splash", "p2"),
+    ];
+    let err = try_step(&duel(), &actions).unwrap_err();
+    assert_eq!(err, "p1 submitted more than one action");
+}
+
+#[test]
+fn try_step_battle_rejects_unknown_players() {
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p3", "splash", "p2")];
+    assert_eq!(try_step(&duel(), &actions).unwrap_err(), "p3 is not a player in this battle");
+}
+
+#[test]
+fn try_step_battle_runs_valid_actions_like_step_battle() {
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "splash", "p2")];
+    let checked = try_step(&duel(), &actions).unwrap();
+    let lenient = run_turn_with_seed(&BattleEngine::default(), &duel(), &actions, 3);
+    assert_eq!(checked.players[1].team[0].hp, lenient.players[1].team[0].hp);
+}
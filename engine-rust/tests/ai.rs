@@ -0,0 +1,264 @@
+mod support;
+
+use engine_rust::core::battle::{is_battle_over, step_battle, BattleEngine, BattleOptions};
+use engine_rust::core::rng::SplitMix64Rng;
+use engine_rust::core::state::{ActionType, BattleState};
+use engine_rust::{
+    choose_highest_power, get_best_move_mcts_with_policy, get_best_move_minimax, get_best_move_minimax_fog,
+    get_best_move_with_config, run_many_battles, AiConfig, AiKind, RolloutPolicy,
+};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder, SeededRng};
+
+fn hidden_close_combat_state() -> BattleState {
+    let frail = CreatureBuilder::new("p1-normal", "Frail")
+        .types(&["normal"])
+        .moves(&["tackle"])
+        .hp(60, 100)
+        .stats(50, 50, 50, 50, 60)
+        .build();
+    let flyer = CreatureBuilder::new("p1-flying", "Flyer")
+        .types(&["flying"])
+        .moves(&["tackle"])
+        .stats(50, 80, 50, 50, 40)
+        .build();
+    let brawler = CreatureBuilder::new("p2-normal", "Brawler")
+        .types(&["normal"])
+        .moves(&["tackle", "close_combat"])
+        .revealed_moves(&["tackle"])
+        .stats(80, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![
+        player("p1", "P1", vec![frail, flyer]),
+        player("p2", "P2", vec![brawler]),
+    ])
+}
+
+#[test]
+fn fog_minimax_ignores_unrevealed_moves() {
+    let state = hidden_close_combat_state();
+
+    let informed = get_best_move_minimax(&state, "p1", 1).expect("informed action");
+    assert_eq!(informed.action_type, ActionType::Switch);
+
+    let fogged = get_best_move_minimax_fog(&state, "p1", 1).expect("fog action");
+    assert_eq!(fogged.action_type, ActionType::Move);
+    assert_eq!(fogged.move_id.as_deref(), Some("tackle"));
+}
+
+#[test]
+fn using_a_move_reveals_it() {
+    let engine = BattleEngine::default();
+    let state = hidden_close_combat_state();
+    let actions = vec![
+        move_action("p1", "tackle", "p2"),
+        move_action("p2", "close_combat", "p1"),
+    ];
+    let next = run_turn_with_seed(&engine, &state, &actions, 7);
+
+    let brawler = &next.players[1].team[0];
+    assert_eq!(brawler.revealed_moves, vec!["tackle", "close_combat"]);
+}
+
+fn action_key(action: &engine_rust::core::state::Action) -> String {
+    format!("{:?}:{:?}:{:?}", action.action_type, action.move_id, action.slot)
+}
+
+#[test]
+fn zero_skill_picks_uniformly_random_legal_actions() {
+    let state = hidden_close_combat_state();
+    let config = AiConfig { skill: 0.0 };
+    let mut counts = std::collections::HashMap::new();
+    for seed in 0..400 {
+        let mut rng = SeededRng::new(seed);
+        let mut rng_fn = || rng.next_f64();
+        let action = get_best_move_with_config(&state, "p1", 1, config, &mut rng_fn).expect("action");
+        *counts.entry(action_key(&action)).or_insert(0) += 1;
+    }
+
+    // 技 tackle と 控えへの交代の 2 択
+    assert_eq!(counts.len(), 2);
+    for count in counts.values() {
+        assert!((140..=260).contains(count), "{counts:?}");
+    }
+}
+
+#[test]
+fn full_skill_matches_minimax() {
+    let state = hidden_close_combat_state();
+    let expected = action_key(&get_best_move_minimax(&state, "p1", 1).expect("minimax action"));
+    for seed in 0..20 {
+        let mut rng = SeededRng::new(seed);
+        let mut rng_fn = || rng.next_f64();
+        let action = get_best_move_with_config(&state, "p1", 1, AiConfig::default(), &mut rng_fn).expect("action");
+        assert_eq!(action_key(&action), expected);
+    }
+}
+
+#[test]
+fn minimax_switches_into_resisting_teammate() {
+    let fire = CreatureBuilder::new("p1-fire", "Ember")
+        .types(&["fire"])
+        .moves(&["tackle"])
+        .stats(40, 50, 50, 50, 50)
+        .build();
+    let grass = CreatureBuilder::new("p1-grass", "Sprout")
+        .types(&["grass"])
+        .moves(&["tackle"])
+        .stats(40, 50, 50, 100, 40)
+        .build();
+    let water = CreatureBuilder::new("p2-water", "Splash")
+        .types(&["water"])
+        .moves(&["surf"])
+        .stats(50, 50, 90, 50, 60)
+        .build();
+    let state = battle_state(vec![
+        player("p1", "P1", vec![fire, grass]),
+        player("p2", "P2", vec![water]),
+    ]);
+
+    let action = get_best_move_minimax(&state, "p1", 1).expect("action");
+    assert_eq!(action.action_type, ActionType::Switch);
+    assert_eq!(action.slot, Some(1));
+}
+
+#[test]
+fn highest_power_prefers_super_effective_over_resisted() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["normal"])
+        .moves(&["wood_hammer", "flame_wheel"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .types(&["grass"])
+        .moves(&["tackle"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])]);
+
+    let action = choose_highest_power(&BattleEngine::default(), &state, "p1").expect("action");
+    assert_eq!(action.move_id.as_deref(), Some("flame_wheel"));
+}
+
+#[test]
+fn highest_power_uses_the_engines_type_chart() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["normal"])
+        .moves(&["wood_hammer", "flame_wheel"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .types(&["grass"])
+        .moves(&["tackle"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])]);
+
+    let engine = BattleEngine::default().inverse_battle();
+    let action = choose_highest_power(&engine, &state, "p1").expect("action");
+    assert_eq!(action.move_id.as_deref(), Some("wood_hammer"));
+}
+
+#[test]
+fn highest_power_breaks_ties_by_move_id() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["fire"])
+        .moves(&["tackle", "quick_attack"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .moves(&["tackle"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])]);
+
+    let action = choose_highest_power(&BattleEngine::default(), &state, "p1").expect("action");
+    assert_eq!(action.move_id.as_deref(), Some("quick_attack"));
+}
+
+fn mirror_match_state() -> BattleState {
+    let mon = |id: &str, name: &str| {
+        CreatureBuilder::new(id, name)
+            .moves(&["tackle", "quick_attack"])
+            .hp(60, 60)
+            .stats(50, 50, 50, 50, 50)
+            .build()
+    };
+    battle_state(vec![
+        player("p1", "P1", vec![mon("p1-a", "A1"), mon("p1-b", "B1")]),
+        player("p2", "P2", vec![mon("p2-a", "A2"), mon("p2-b", "B2")]),
+    ])
+}
+
+#[test]
+fn run_many_battles_aggregates_reproducibly() {
+    let state = mirror_match_state();
+    let seeds = [1, 2];
+    let stats = run_many_battles(&state, 2, &seeds, AiKind::HighestPower, AiKind::Minimax { depth: 1 });
+
+    assert_eq!(stats.games, 2);
+    assert_eq!(stats.wins[0] + stats.wins[1] + stats.draws, 2);
+    assert!(stats.average_turns >= 1.0);
+    // 決着した試合では 負けた側が2体とも倒れている
+    assert!(stats.faints[0] >= 2 * stats.wins[1]);
+    assert!(stats.faints[1] >= 2 * stats.wins[0]);
+
+    let again = run_many_battles(&state, 2, &seeds, AiKind::HighestPower, AiKind::Minimax { depth: 1 });
+    assert_eq!(stats, again);
+}
+
+#[test]
+fn run_many_battles_counts_turns_but_not_forced_switches() {
+    // 先頭どうしは どく で同じターンに倒れるので、交代だけの手番が挟まる
+    let mon = |id: &str, name: &str, hp: i32| {
+        let builder = CreatureBuilder::new(id, name)
+            .moves(&["tackle"])
+            .hp(hp, 60)
+            .stats(50, 50, 50, 50, 50);
+        if hp == 1 {
+            builder.with_status(status("poison", None)).build()
+        } else {
+            builder.build()
+        }
+    };
+    let state = battle_state(vec![
+        player("p1", "P1", vec![mon("p1-a", "A1", 1), mon("p1-b", "B1", 60)]),
+        player("p2", "P2", vec![mon("p2-a", "A2", 1), mon("p2-b", "B2", 60)]),
+    ]);
+    let stats = run_many_battles(&state, 1, &[3], AiKind::HighestPower, AiKind::HighestPower);
+
+    // 同じシードで1試合を手で進めて、手番の数とターン数を比べる
+    let mut rng = SplitMix64Rng::new(3);
+    let mut rng_fn = rng.as_fn();
+    let mut next = state.clone();
+    let mut steps = 0;
+    while !is_battle_over(&next) {
+        let actions: Vec<_> = next
+            .players
+            .iter()
+            .filter_map(|p| AiKind::HighestPower.choose(&next, &p.id))
+            .collect();
+        next = step_battle(&next, &actions, &mut rng_fn, BattleOptions::default());
+        steps += 1;
+    }
+    let turns = next.turn - state.turn;
+    assert!(steps > turns, "倒れた後の交代だけの手番がある");
+    assert_eq!(stats.average_turns, turns as f64);
+}
+
+#[test]
+fn run_many_battles_falls_back_to_game_index_seeds() {
+    let state = mirror_match_state();
+    let stats = run_many_battles(&state, 3, &[7], AiKind::HighestPower, AiKind::HighestPower);
+    let explicit = run_many_battles(&state, 3, &[7, 1, 2], AiKind::HighestPower, AiKind::HighestPower);
+    assert_eq!(stats, explicit);
+    assert_eq!(stats.wins[0] + stats.wins[1] + stats.draws, 3);
+    assert_eq!(run_many_battles(&state, 0, &[], AiKind::HighestPower, AiKind::HighestPower).average_turns, 0.0);
+}
+
+#[test]
+fn mcts_with_the_same_seed_and_policy_is_reproducible() {
+    let state = mirror_match_state();
+    let run = |seed: u64, policy: RolloutPolicy| {
+        let mut rng = SeededRng::new(seed);
+        let mut rng_fn = || rng.next_f64();
+        let action = get_best_move_mcts_with_policy(&state, "p1", 2, policy, &mut rng_fn).expect("action");
+        action_key(&action)
+    };
+    for policy in [RolloutPolicy::Random, RolloutPolicy::HighestDamage, RolloutPolicy::Heuristic] {
+        assert_eq!(run(7, policy), run(7, policy), "{policy:?}");
+    }
+}
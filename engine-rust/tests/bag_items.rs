@@ -0,0 +1,42 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, item_action, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn bench_state(bench_hp: i32) -> BattleState {
+    let lead = CreatureBuilder::new("p1-lead", "Lead").moves(&["harden"]).build();
+    let bench = CreatureBuilder::new("p1-bench", "Bench")
+        .moves(&["harden"])
+        .hp(bench_hp, 100)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    battle_state(vec![player("p1", "P1", vec![lead, bench]), player("p2", "P2", vec![foe])])
+}
+
+#[test]
+fn potion_heals_benched_creature() {
+    let engine = BattleEngine::default();
+    let actions = vec![item_action("p1", "potion", 1), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &bench_state(50), &actions, 1);
+
+    assert_eq!(next.players[0].team[1].hp, 70);
+    assert_eq!(next.players[0].active_slot, 0);
+    assert_eq!(next.players[0].team[0].hp, 100);
+    assert!(next.log.iter().any(|line| line == "Benchの HPが 20回復した！"));
+}
+
+#[test]
+fn revive_restores_fainted_bench_and_potion_fails_on_it() {
+    let engine = BattleEngine::default();
+
+    let potion = vec![item_action("p1", "potion", 1), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &bench_state(0), &potion, 1);
+    assert_eq!(next.players[0].team[1].hp, 0);
+    assert!(next.log.iter().any(|line| line == "しかし 効果が なかった！"));
+
+    let revive = vec![item_action("p1", "revive", 1), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &bench_state(0), &revive, 1);
+    assert_eq!(next.players[0].team[1].hp, 50);
+}
@@ -0,0 +1,34 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleResult, BattleRunner};
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+#[test]
+fn on_battle_end_fires_once_with_winner() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(100, 50, 50, 50, 80)
+        .build();
+    let frail = CreatureBuilder::new("p2-mon", "Frail")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![frail])]);
+    let engine = BattleEngine::default();
+
+    let mut results: Vec<BattleResult> = Vec::new();
+    {
+        let mut runner = BattleRunner::new(&engine, state).on_battle_end(|_, result| results.push(result.clone()));
+        let mut rng = SeededRng::new(1);
+        let mut next_f64 = || rng.next_f64();
+        let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+        runner.step(&actions, &mut next_f64);
+        assert!(runner.is_finished());
+        // 決着後に進めても もう呼ばれない
+        runner.step(&actions, &mut next_f64);
+    }
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].winner.as_deref(), Some("p1"));
+    assert_eq!(results[0].turn, 1);
+}
@@ -1,6 +1,6 @@
 use engine_rust::core::battle::{BattleEngine, BattleOptions};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages, Status};
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -30,6 +30,11 @@ fn make_creature(id: &str, name: &str, types: Vec<String>, moves: Vec<String>) -
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -41,27 +46,17 @@ fn make_creature(id: &str, name: &str, types: Vec<String>, moves: Vec<String>) -
 fn make_state(p1: CreatureState, p2: CreatureState) -> BattleState {
     BattleState {
         players: vec![
-            PlayerState {
-                id: "p1".to_string(),
-                name: "P1".to_string(),
-                team: vec![p1],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
-            PlayerState {
-                id: "p2".to_string(),
-                name: "P2".to_string(),
-                team: vec![p2],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
+            PlayerState::new("p1", "P1", vec![p1]),
+            PlayerState::new("p2", "P2", vec![p2]),
         ],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -73,7 +68,7 @@ fn bypass_protect_allows_damage() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -90,7 +85,7 @@ fn bypass_protect_allows_damage() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -144,7 +139,7 @@ fn protect_blocks_damage_without_bypass() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -158,7 +153,7 @@ fn protect_blocks_damage_without_bypass() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -212,7 +207,7 @@ fn ignore_immunity_allows_damage() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -229,7 +224,7 @@ fn ignore_immunity_allows_damage() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -277,7 +272,7 @@ fn immunity_blocks_damage_without_ignore() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -291,7 +286,7 @@ fn immunity_blocks_damage_without_ignore() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -339,7 +334,7 @@ fn substitute_blocks_damage_without_bypass() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -353,7 +348,7 @@ fn substitute_blocks_damage_without_bypass() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -407,7 +402,7 @@ fn bypass_substitute_allows_damage_by_tag() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -421,7 +416,7 @@ fn bypass_substitute_allows_damage_by_tag() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -475,7 +470,7 @@ fn ignore_substitute_allows_damage_by_effect() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: Some(40),
         accuracy: Some(1.0),
@@ -492,7 +487,7 @@ fn ignore_substitute_allows_damage_by_effect() {
         id: "poke".to_string(),
         name: Some("Poke".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
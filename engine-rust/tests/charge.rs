@@ -0,0 +1,65 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use serde_json::Value;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn charge_state() -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Charger")
+                .moves(&["charge", "thunderbolt", "tackle"])
+                .hp(300, 300)
+                .stats(50, 50, 50, 50, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target")
+                .moves(&["harden"])
+                .hp(500, 500)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn use_move(engine: &BattleEngine, state: &BattleState, move_id: &str, seed: u64) -> BattleState {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(engine, state, &actions, seed)
+}
+
+fn charged(state: &BattleState) -> Option<bool> {
+    state.players[0].team[0].volatile_data.get("charged").and_then(Value::as_bool)
+}
+
+#[test]
+fn charge_doubles_the_next_electric_move_and_is_consumed() {
+    let engine = BattleEngine::default();
+    let charged_state = use_move(&engine, &charge_state(), "charge", 1);
+    assert_eq!(charged(&charged_state), Some(true));
+    assert_eq!(charged_state.players[0].team[0].stages.spd, 1);
+    assert!(charged_state.log.iter().any(|line| line == "Chargerは じゅうでんを はじめた！"));
+
+    let plain = use_move(&engine, &charge_state(), "thunderbolt", 2);
+    let boosted = use_move(&engine, &charged_state, "thunderbolt", 2);
+    let plain_damage = 500 - plain.players[1].team[0].hp;
+    let boosted_damage = 500 - boosted.players[1].team[0].hp;
+    assert!(boosted_damage >= plain_damage * 2 - 4, "{boosted_damage} vs {plain_damage}");
+    assert_eq!(charged(&boosted), Some(false));
+
+    let again = use_move(&engine, &boosted, "thunderbolt", 2);
+    assert_eq!(boosted.players[1].team[0].hp - again.players[1].team[0].hp, plain_damage);
+}
+
+#[test]
+fn charge_is_kept_through_non_electric_moves() {
+    let engine = BattleEngine::default();
+    let charged_state = use_move(&engine, &charge_state(), "charge", 1);
+    let next = use_move(&engine, &charged_state, "tackle", 2);
+    assert_eq!(charged(&next), Some(true));
+}
@@ -1,6 +1,6 @@
 use engine_rust::core::battle::{BattleEngine, BattleOptions};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages, Status};
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -30,6 +30,11 @@ fn make_creature(id: &str, name: &str, moves: Vec<String>) -> CreatureState {
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -41,27 +46,17 @@ fn make_creature(id: &str, name: &str, moves: Vec<String>) -> CreatureState {
 fn make_state(p1_team: Vec<CreatureState>, p2_team: Vec<CreatureState>) -> BattleState {
     BattleState {
         players: vec![
-            PlayerState {
-                id: "p1".to_string(),
-                name: "P1".to_string(),
-                team: p1_team,
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
-            PlayerState {
-                id: "p2".to_string(),
-                name: "P2".to_string(),
-                team: p2_team,
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
+            PlayerState::new("p1", "P1", p1_team),
+            PlayerState::new("p2", "P2", p2_team),
         ],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -73,7 +68,7 @@ fn pending_switch_blocks_non_switch_action() {
         id: "hit".to_string(),
         name: Some("Hit".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -87,7 +82,7 @@ fn pending_switch_blocks_non_switch_action() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -139,7 +134,7 @@ fn self_switch_requires_choice_and_clears_after_switch() {
         id: "pivot".to_string(),
         name: Some("Pivot".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -153,7 +148,7 @@ fn self_switch_requires_choice_and_clears_after_switch() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -231,7 +226,7 @@ fn manual_switch_effect_sets_pending_switch() {
         id: "teleport".to_string(),
         name: Some("Teleport".to_string()),
         move_type: Some("psychic".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -248,7 +243,7 @@ fn manual_switch_effect_sets_pending_switch() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -0,0 +1,161 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use engine_rust::data::moves::{MoveCategory, MoveData};
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, switch_action, CreatureBuilder};
+
+fn choice_state(item: Option<&str>) -> BattleState {
+    let mut holder = CreatureBuilder::new("p1-mon", "Holder")
+        .moves(&["tackle", "harden", "swift"])
+        .hp(300, 300)
+        .stats(100, 100, 100, 100, 100);
+    if let Some(item) = item {
+        holder = holder.item(item);
+    }
+    let bench = CreatureBuilder::new("p1-bench", "Bench")
+        .moves(&["harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["splash"])
+        .hp(1000, 1000)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![
+        player("p1", "P1", vec![holder.build(), bench]),
+        player("p2", "P2", vec![target]),
+    ])
+}
+
+fn use_move(engine: &BattleEngine, state: &BattleState, move_id: &str, seed: u64) -> BattleState {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(engine, state, &actions, seed)
+}
+
+fn switch_to(engine: &BattleEngine, state: &BattleState, slot: usize) -> BattleState {
+    let actions = vec![switch_action("p1", slot), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(engine, state, &actions, 1)
+}
+
+#[test]
+fn choice_holder_is_locked_into_its_first_move() {
+    let engine = BattleEngine::default();
+    let first = use_move(&engine, &choice_state(Some("choice_scarf")), "tackle", 1);
+    let second = use_move(&engine, &first, "harden", 2);
+    assert_eq!(second.players[0].team[0].stages.def, 0);
+    assert!(second.players[1].team[0].hp < first.players[1].team[0].hp);
+    assert!(second.log.iter().any(|line| line == "Holderは たいあたりしか 出せなくなっている！"));
+}
+
+#[test]
+fn choice_locked_holder_can_only_choose_the_locked_move() {
+    let engine = BattleEngine::default();
+    let move_ids = |state: &BattleState| -> Vec<String> {
+        engine.legal_actions(state, "p1").into_iter().filter_map(|a| a.move_id).collect()
+    };
+    let mut locked = use_move(&engine, &choice_state(Some("choice_scarf")), "tackle", 1);
+    assert_eq!(move_ids(&locked), vec!["tackle".to_string()]);
+    assert!(engine.legal_actions(&locked, "p1").iter().any(|a| a.slot == Some(1)));
+
+    locked.players[0].team[0].item = None;
+    assert_eq!(move_ids(&locked).len(), 3);
+}
+
+#[test]
+fn switching_out_releases_the_choice_lock() {
+    let engine = BattleEngine::default();
+    let locked = use_move(&engine, &choice_state(Some("choice_scarf")), "tackle", 1);
+    let benched = switch_to(&engine, &locked, 1);
+    assert!(!benched.players[0].team[0].statuses.iter().any(|s| s.id == "choice_lock"));
+    let back = switch_to(&engine, &benched, 0);
+    let next = use_move(&engine, &back, "harden", 2);
+    assert_eq!(next.players[0].team[0].stages.def, 1);
+}
+
+fn rampage() -> MoveData {
+    MoveData {
+        id: "rampage".to_string(),
+        name: Some("Rampage".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Physical),
+        pp: Some(10),
+        power: Some(40),
+        accuracy: Some(1.0),
+        priority: Some(0),
+        description: None,
+        steps: vec![
+            serde_json::from_value(json!({ "type": "damage", "power": 40 })).unwrap(),
+            serde_json::from_value(json!({
+                "type": "lock_move",
+                "target": "self",
+                "duration": 2,
+                "data": { "mode": "force_last_move", "confuseOnEnd": true }
+            }))
+            .unwrap(),
+        ],
+        tags: Vec::new(),
+        crit_rate: None,
+    }
+}
+
+#[test]
+fn choice_lock_does_not_block_a_rampage_lock() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(rampage());
+    let mut state = choice_state(Some("choice_scarf"));
+    state.players[0].team[0].moves.push("rampage".to_string());
+    let next = use_move(&engine, &state, "rampage", 1);
+    let statuses = &next.players[0].team[0].statuses;
+    assert!(statuses.iter().any(|s| s.id == "choice_lock"));
+    assert!(statuses.iter().any(|s| s.id == "lock_move" && s.data.contains_key("confuseOnEnd")));
+    assert!(!next.log.iter().any(|line| line.contains("すでに")));
+}
+
+#[test]
+fn encore_still_lands_on_a_choice_locked_target() {
+    let engine = BattleEngine::default();
+    let mut state = choice_state(None);
+    state.players[0].team[0].moves.push("encore".to_string());
+    state.players[1].team[0].item = Some("choice_scarf".to_string());
+    let first = use_move(&engine, &state, "tackle", 1);
+    assert!(first.players[1].team[0].statuses.iter().any(|s| s.id == "choice_lock"));
+    let encored = use_move(&engine, &first, "encore", 2);
+    assert!(encored.players[1].team[0].statuses.iter().any(|s| s.id == "lock_move"));
+}
+
+#[test]
+fn losing_the_choice_item_releases_the_lock() {
+    let engine = BattleEngine::default();
+    let mut locked = use_move(&engine, &choice_state(Some("choice_scarf")), "tackle", 1);
+    locked.players[0].team[0].item = None;
+    let next = use_move(&engine, &locked, "harden", 2);
+    assert_eq!(next.players[0].team[0].stages.def, 1);
+    assert!(!next.players[0].team[0].statuses.iter().any(|s| s.id == "choice_lock"));
+}
+
+#[test]
+fn choice_band_boosts_physical_damage_only() {
+    let engine = BattleEngine::default();
+    let damage = |item: Option<&str>, move_id: &str| {
+        1000 - use_move(&engine, &choice_state(item), move_id, 4).players[1].team[0].hp
+    };
+    let plain = damage(None, "tackle");
+    let banded = damage(Some("choice_band"), "tackle");
+    assert!((banded as f64 - plain as f64 * 1.5).abs() <= 3.0, "{banded} vs {plain}");
+    assert_eq!(damage(Some("choice_band"), "swift"), damage(None, "swift"));
+}
+
+#[test]
+fn choice_specs_boosts_special_damage_only() {
+    let engine = BattleEngine::default();
+    let damage = |item: Option<&str>, move_id: &str| {
+        1000 - use_move(&engine, &choice_state(item), move_id, 4).players[1].team[0].hp
+    };
+    let plain = damage(None, "swift");
+    let specs = damage(Some("choice_specs"), "swift");
+    assert!((specs as f64 - plain as f64 * 1.5).abs() <= 3.0, "{specs} vs {plain}");
+    assert_eq!(damage(Some("choice_specs"), "tackle"), damage(None, "tackle"));
+}
@@ -0,0 +1,75 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn contact_state(ability: &str) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Attacker")
+                .moves(&["tackle", "swift"])
+                .hp(160, 160)
+                .stats(100, 100, 100, 100, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Holder")
+                .moves(&["splash"])
+                .ability(ability)
+                .hp(500, 500)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn attack(state: &BattleState, move_id: &str, seed: u64) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, seed)
+}
+
+fn attacker_statused(ability: &str, move_id: &str, status_id: &str) -> usize {
+    (0..40)
+        .filter(|&seed| {
+            attack(&contact_state(ability), move_id, seed).players[0].team[0]
+                .statuses
+                .iter()
+                .any(|s| s.id == status_id)
+        })
+        .count()
+}
+
+#[test]
+fn static_sometimes_paralyzes_contact_attackers_only() {
+    let contact = attacker_statused("static", "tackle", "paralysis");
+    assert!(contact > 0 && contact < 40, "{contact}");
+    assert_eq!(attacker_statused("static", "swift", "paralysis"), 0);
+}
+
+#[test]
+fn flame_body_sometimes_burns_contact_attackers_only() {
+    let contact = attacker_statused("flame_body", "tackle", "burn");
+    assert!(contact > 0 && contact < 40, "{contact}");
+    assert_eq!(attacker_statused("flame_body", "swift", "burn"), 0);
+}
+
+#[test]
+fn rough_skin_chips_contact_attackers_only() {
+    let next = attack(&contact_state("rough_skin"), "tackle", 1);
+    assert_eq!(next.players[0].team[0].hp, 140);
+    assert!(next.log.iter().any(|line| line == "Holderの さめはだ！"));
+    assert_eq!(attack(&contact_state("rough_skin"), "swift", 1).players[0].team[0].hp, 160);
+}
+
+#[test]
+fn rough_skin_does_not_trigger_through_a_substitute() {
+    let mut state = contact_state("rough_skin");
+    state.players[1].team[0].statuses.push(status("substitute", None));
+    assert_eq!(attack(&state, "tackle", 1).players[0].team[0].hp, 160);
+}
@@ -0,0 +1,92 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn copy_state(copier_move: &str, copier_speed: i32) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Copier")
+                .moves(&[copier_move])
+                .hp(200, 200)
+                .stats(50, 50, 50, 50, copier_speed)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Tackler")
+                .moves(&["tackle"])
+                .hp(200, 200)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+#[test]
+fn mirror_move_fails_before_the_opponent_has_moved_then_replays_its_attack() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "mirror_move", "p2"), move_action("p2", "tackle", "p1")];
+
+    let first = run_turn_with_seed(&engine, &copy_state("mirror_move", 100), &actions, 1);
+    assert!(first.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+    assert_eq!(first.players[1].team[0].hp, 200);
+
+    let second = run_turn_with_seed(&engine, &first, &actions, 2);
+    assert!(second.log.iter().any(|line| line == "Copierの たいあたり！"));
+    assert!(second.players[1].team[0].hp < 200);
+    // 呼び出した技の PP は減らない
+    assert!(!second.players[0].team[0].move_pp.contains_key("tackle"));
+}
+
+#[test]
+fn copycat_repeats_the_move_just_used_this_turn() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "copycat", "p2"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &copy_state("copycat", 10), &actions, 1);
+    assert!(next.log.iter().any(|line| line == "Copierの たいあたり！"));
+    assert!(next.players[1].team[0].hp < 200);
+    assert_eq!(next.field.last_move.as_deref(), Some("copycat"));
+}
+
+#[test]
+fn copy_moves_refuse_uncopyable_moves() {
+    let engine = BattleEngine::default();
+    let mut state = copy_state("mirror_move", 100);
+    state.players[1].team[0]
+        .volatile_data
+        .insert("lastMove".to_string(), "protect".into());
+    let actions = vec![move_action("p1", "mirror_move", "p2"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+    assert_eq!(next.players[1].team[0].hp, 200);
+}
+
+#[test]
+fn assist_calls_a_move_known_by_a_benched_teammate() {
+    let engine = BattleEngine::default();
+    let mut state = copy_state("assist", 100);
+    state.players[0].team.push(
+        CreatureBuilder::new("p1-bench", "Helper")
+            .moves(&["tackle", "protect"])
+            .build(),
+    );
+    let actions = vec![move_action("p1", "assist", "p2"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    // まもるは呼び出せないので たいあたりだけが候補
+    assert!(next.log.iter().any(|line| line == "Copierの たいあたり！"));
+    assert!(next.players[1].team[0].hp < 200);
+}
+
+#[test]
+fn assist_fails_without_benched_teammates() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "assist", "p2"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &copy_state("assist", 100), &actions, 1);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+    assert_eq!(next.players[1].team[0].hp, 200);
+}
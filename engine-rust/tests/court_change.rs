@@ -0,0 +1,81 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, FieldEffect};
+use serde_json::Value;
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn field_effect(id: &str, remaining_turns: Option<i32>, layers: Option<i64>) -> FieldEffect {
+    let mut data = HashMap::new();
+    if let Some(layers) = layers {
+        data.insert("layers".to_string(), Value::from(layers));
+    }
+    FieldEffect {
+        id: id.to_string(),
+        remaining_turns,
+        data,
+    }
+}
+
+fn side_ids(state: &BattleState, side: &str) -> Vec<String> {
+    state
+        .field
+        .sides
+        .get(side)
+        .map(|effects| effects.iter().map(|e| e.id.clone()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn court_change_swaps_side_effects_between_players() {
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![CreatureBuilder::new("p1-mon", "Swapper").moves(&["court_change"]).build()]),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "Setter").moves(&["harden"]).build()]),
+    ]);
+    state
+        .field
+        .sides
+        .insert("p1".to_string(), vec![field_effect("stealth_rock", None, None)]);
+    state.field.sides.insert(
+        "p2".to_string(),
+        vec![field_effect("spikes", None, Some(2)), field_effect("reflect", Some(3), None)],
+    );
+
+    let engine = BattleEngine::default();
+    let next = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "court_change", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+
+    assert_eq!(side_ids(&next, "p1"), vec!["spikes", "reflect"]);
+    assert_eq!(side_ids(&next, "p2"), vec!["stealth_rock"]);
+    let p1_side = &next.field.sides["p1"];
+    assert_eq!(p1_side[0].data.get("layers"), Some(&Value::from(2)));
+    assert_eq!(p1_side[1].remaining_turns, Some(3));
+    assert!(next.log.iter().any(|line| line == "Swapperは おたがいの 場の 効果を 入れ替えた！"));
+}
+
+#[test]
+fn court_change_moves_effects_onto_an_empty_side() {
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![CreatureBuilder::new("p1-mon", "Swapper").moves(&["court_change"]).build()]),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "Setter").moves(&["harden"]).build()]),
+    ]);
+    state
+        .field
+        .sides
+        .insert("p1".to_string(), vec![field_effect("stealth_rock", None, None)]);
+
+    let next = run_turn_with_seed(
+        &BattleEngine::default(),
+        &state,
+        &[move_action("p1", "court_change", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+
+    assert!(side_ids(&next, "p1").is_empty());
+    assert_eq!(side_ids(&next, "p2"), vec!["stealth_rock"]);
+}
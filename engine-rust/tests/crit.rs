@@ -0,0 +1,108 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::damage::ko_probability;
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn crit_state(attacker_item: Option<&str>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Striker")
+        .moves(&["focus_energy", "slash", "tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    attacker.item = attacker_item.map(str::to_string);
+    let target = CreatureBuilder::new("p2-mon", "Wall")
+        .moves(&["harden"])
+        .hp(999, 999)
+        .stats(50, 200, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])])
+}
+
+fn crits_after_focus_energy(state: BattleState, move_id: &str, turns: u64) -> usize {
+    let engine = BattleEngine::default();
+    let focus = vec![move_action("p1", "focus_energy", "p2"), move_action("p2", "harden", "p1")];
+    let mut state = run_turn_with_seed(&engine, &state, &focus, 1);
+    assert_eq!(state.players[0].team[0].stages.crit, 2);
+
+    let log_start = state.log.len();
+    let attack = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p1")];
+    for seed in 0..turns {
+        state = run_turn_with_seed(&engine, &state, &attack, 100 + seed);
+    }
+    state.log[log_start..]
+        .iter()
+        .filter(|line| *line == "急所に あたった！")
+        .count()
+}
+
+#[test]
+fn focus_energy_with_high_crit_move_always_crits() {
+    assert_eq!(crits_after_focus_energy(crit_state(None), "slash", 8), 8);
+}
+
+#[test]
+fn scope_lens_adds_a_crit_stage() {
+    assert!(crits_after_focus_energy(crit_state(None), "tackle", 8) < 8);
+    assert_eq!(crits_after_focus_energy(crit_state(Some("scope_lens")), "tackle", 8), 8);
+    assert_eq!(crits_after_focus_energy(crit_state(Some("razor_claw")), "tackle", 8), 8);
+}
+
+#[test]
+fn generation_presets_set_crit_multiplier() {
+    assert_eq!(BattleRules::gen(6).crit_multiplier, 1.5);
+    assert_eq!(BattleRules::gen(3).crit_multiplier, 2.0);
+
+    // 毎回急所に当たるようにして 倍率の差だけを比べる
+    let crit_damage = |rules: BattleRules| {
+        let engine = BattleEngine::default().with_rules(rules.with_crit_chances(vec![1.0]));
+        let state = crit_state(None);
+        let attack = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p1")];
+        let next = run_turn_with_seed(&engine, &state, &attack, 1);
+        assert!(next.log.iter().any(|line| line == "急所に あたった！"));
+        state.players[1].team[0].hp - next.players[1].team[0].hp
+    };
+    let gen6 = crit_damage(BattleRules::gen(6));
+    let gen3 = crit_damage(BattleRules::gen(3));
+    assert!(gen3 > gen6, "gen3 {} vs gen6 {}", gen3, gen6);
+}
+
+#[test]
+fn crit_ignores_attacker_attack_drops_and_target_defense_boosts() {
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![1.0]));
+    let attack = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p1")];
+    let crit_damage = |state: &BattleState| 999 - run_turn_with_seed(&engine, state, &attack, 1).players[1].team[0].hp;
+
+    let neutral = crit_state(None);
+    let mut weakened = crit_state(None);
+    weakened.players[0].team[0].stages.atk = -2;
+    weakened.players[1].team[0].stages.def = 2;
+    assert_eq!(crit_damage(&weakened), crit_damage(&neutral));
+
+    // 攻撃側の上昇ランクはそのまま乗る
+    let mut boosted = crit_state(None);
+    boosted.players[0].team[0].stages.atk = 2;
+    assert!(crit_damage(&boosted) > crit_damage(&neutral));
+}
+
+#[test]
+fn ko_odds_use_the_same_crit_stage_rules() {
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![1.0]));
+    let neutral = crit_state(None);
+    let mut weakened = crit_state(None);
+    weakened.players[0].team[0].stages.atk = -2;
+    weakened.players[1].team[0].stages.def = 2;
+    // 急所の乱数幅をまたぐ HP で比べる
+    for hp in 6..=16 {
+        let mut neutral = neutral.clone();
+        let mut weakened = weakened.clone();
+        neutral.players[1].team[0].hp = hp;
+        weakened.players[1].team[0].hp = hp;
+        assert_eq!(
+            ko_probability(&weakened, "p1", "p2", "tackle", &engine, 1),
+            ko_probability(&neutral, "p1", "p2", "tackle", &engine, 1),
+            "hp {hp}"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::damage::{calc_damage_range, calc_damage_range_with_crit, ko_probability};
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, player, CreatureBuilder};
+
+fn ko_state(hp: i32) -> BattleState {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["normal"])
+        .moves(&["tackle"])
+        .stats(100, 50, 50, 50, 50)
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .types(&["normal"])
+        .hp(hp, hp)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])])
+}
+
+#[test]
+fn ko_probability_matches_exhaustive_two_hit_enumeration() {
+    // Without crits every hit is one of the 16 rolls calc_damage_range reports.
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![0.0]));
+    let rolls = calc_damage_range(&ko_state(1), "p1", "p2", "tackle", &engine).rolls;
+    // Pick HP inside the 2-hit spread so the answer is neither 0 nor 1.
+    let hp = rolls[0] + rolls[15];
+    let state = ko_state(hp);
+
+    let mut expected = 0.0;
+    for first in &rolls {
+        for second in &rolls {
+            if first + second >= hp {
+                expected += 1.0 / 256.0;
+            }
+        }
+    }
+
+    let actual = ko_probability(&state, "p1", "p2", "tackle", &engine, 2);
+    assert!(actual > 0.0 && actual < 1.0, "expected a partial KO chance, got {actual}");
+    assert!((actual as f64 - expected).abs() < 1e-6, "{actual} vs {expected}");
+    assert_eq!(ko_probability(&state, "p1", "p2", "tackle", &engine, 4), 1.0);
+}
+
+#[test]
+fn ko_probability_counts_crits_from_the_engines_rules() {
+    let state = ko_state(1);
+    let max = calc_damage_range(&state, "p1", "p2", "tackle", &BattleEngine::default()).max;
+    // One point more than the best non-crit roll: only a crit can KO.
+    let state = ko_state(max + 1);
+    let no_crits = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![0.0]));
+    let always_crits = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![1.0]));
+    assert_eq!(ko_probability(&state, "p1", "p2", "tackle", &no_crits, 1), 0.0);
+    assert_eq!(ko_probability(&state, "p1", "p2", "tackle", &always_crits, 1), 1.0);
+}
+
+fn range_state(attacker_ability: Option<&str>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["fire"])
+        .moves(&["tackle", "flamethrower", "growl"])
+        .stats(150, 100, 150, 100, 100);
+    if let Some(ability) = attacker_ability {
+        attacker = attacker.ability(ability);
+    }
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .types(&["normal"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker.build()]), player("p2", "P2", vec![defender])])
+}
+
+#[test]
+fn damage_range_min_is_85_percent_of_max() {
+    let engine = BattleEngine::default();
+    let range = calc_damage_range(&range_state(None), "p1", "p2", "flamethrower", &engine);
+    assert_eq!(range.min, range.rolls[0]);
+    assert_eq!(range.max, range.rolls[15]);
+    assert!(range.rolls.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", range.rolls);
+    assert!((range.min - range.max * 85 / 100).abs() <= 1, "{} vs {}", range.min, range.max);
+}
+
+#[test]
+fn damage_range_applies_ability_modifiers() {
+    let engine = BattleEngine::default();
+    let plain = calc_damage_range(&range_state(None), "p1", "p2", "tackle", &engine);
+    let boosted = calc_damage_range(&range_state(Some("pure_power")), "p1", "p2", "tackle", &engine);
+    assert!(boosted.max > plain.max * 3 / 2, "{} vs {}", boosted.max, plain.max);
+    assert!((boosted.min - boosted.max * 85 / 100).abs() <= 1);
+}
+
+#[test]
+fn damage_range_is_zero_for_status_moves() {
+    let engine = BattleEngine::default();
+    let range = calc_damage_range(&range_state(None), "p1", "p2", "growl", &engine);
+    assert_eq!((range.min, range.max, range.rolls), (0, 0, [0; 16]));
+}
+
+#[test]
+fn damage_range_reports_effectiveness_as_the_battle_sees_it() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["earthquake", "struggle", "flamethrower"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Floater")
+        .types(&["ghost"])
+        .ability("levitate")
+        .hp(300, 300)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])]);
+    let engine = BattleEngine::default();
+
+    let effectiveness = |move_id: &str| calc_damage_range(&state, "p1", "p2", move_id, &engine).effectiveness;
+    assert_eq!(effectiveness("earthquake"), 0.0);
+    // Struggle is typeless, so even a Ghost takes it neutrally.
+    assert_eq!(effectiveness("struggle"), 1.0);
+    assert_eq!(effectiveness("flamethrower"), 1.0);
+}
+
+#[test]
+fn psycho_shock_is_taken_on_the_targets_defense() {
+    let engine = BattleEngine::default();
+    let state_with = |def: i32, spd: i32| {
+        let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+            .types(&["normal"])
+            .moves(&["psycho_shock"])
+            .stats(50, 50, 150, 50, 50)
+            .build();
+        let defender = CreatureBuilder::new("p2-mon", "Defender")
+            .types(&["normal"])
+            .hp(300, 300)
+            .stats(50, def, 50, spd, 50)
+            .build();
+        battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])])
+    };
+    let soft = calc_damage_range(&state_with(50, 200), "p1", "p2", "psycho_shock", &engine);
+    let hard = calc_damage_range(&state_with(200, 50), "p1", "p2", "psycho_shock", &engine);
+    assert!(soft.max > hard.max * 2, "{} vs {}", soft.max, hard.max);
+}
+
+#[test]
+fn crit_damage_range_is_one_and_a_half_times_the_plain_range() {
+    let engine = BattleEngine::default();
+    let state = range_state(None);
+    let plain = calc_damage_range(&state, "p1", "p2", "tackle", &engine);
+    assert_eq!(calc_damage_range_with_crit(&state, "p1", "p2", "tackle", &engine, false), plain);
+    let crit = calc_damage_range_with_crit(&state, "p1", "p2", "tackle", &engine, true);
+    assert!((crit.max - plain.max * 3 / 2).abs() <= 1, "{} vs {}", crit.max, plain.max);
+    assert_eq!(crit.effectiveness, plain.effectiveness);
+}
@@ -0,0 +1,72 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn tracking_state() -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Hitter")
+                .moves(&["tackle", "harden"])
+                .hp(200, 200)
+                .stats(100, 100, 100, 100, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target")
+                .moves(&["harden"])
+                .hp(300, 300)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn logged_damage(state: &BattleState) -> i32 {
+    state
+        .log
+        .iter()
+        .find_map(|line| line.strip_prefix("Targetは ")?.strip_suffix("ダメージ 受けた！")?.parse().ok())
+        .expect("damage line")
+}
+
+#[test]
+fn damage_is_recorded_on_both_sides() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &tracking_state(), &actions, 1);
+    let amount = logged_damage(&next);
+    assert_eq!(next.players[1].team[0].hp, 300 - amount);
+    assert_eq!(next.players[0].team[0].last_damage_dealt, amount);
+    assert_eq!(next.players[1].team[0].last_damage_taken, amount);
+    assert_eq!(next.players[0].team[0].last_damage_taken, 0);
+    assert_eq!(next.players[1].team[0].last_damage_dealt, 0);
+}
+
+#[test]
+fn damage_totals_reset_at_the_start_of_each_turn() {
+    let engine = BattleEngine::default();
+    let hit = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let after_hit = run_turn_with_seed(&engine, &tracking_state(), &hit, 1);
+    let idle = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &after_hit, &idle, 2);
+    assert_eq!(next.players[0].team[0].last_damage_dealt, 0);
+    assert_eq!(next.players[1].team[0].last_damage_taken, 0);
+}
+
+#[test]
+fn hits_on_a_substitute_count_as_dealt_but_not_taken() {
+    let engine = BattleEngine::default();
+    let mut state = tracking_state();
+    state.players[1].team[0].statuses.push(status("substitute", None));
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    assert_eq!(next.players[1].team[0].hp, 300);
+    assert_eq!(next.players[1].team[0].last_damage_taken, 0);
+    assert!(next.players[0].team[0].last_damage_dealt > 0);
+}
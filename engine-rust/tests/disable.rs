@@ -0,0 +1,75 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn disable_state() -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["disable", "harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["tackle", "harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn disabled_move(state: &BattleState) -> Option<String> {
+    state.players[1].team[0]
+        .statuses
+        .iter()
+        .find(|s| s.id == "disable_move")
+        .and_then(|s| s.data.get("moveId"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+#[test]
+fn disable_blocks_last_move_for_four_turns_then_reenables() {
+    let engine = BattleEngine::default();
+    // 相手が先に たいあたりを出し、その直後に かなしばり
+    let mut state = run_turn_with_seed(
+        &engine,
+        &disable_state(),
+        &[move_action("p1", "disable", "p2"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert_eq!(disabled_move(&state).as_deref(), Some("tackle"));
+    assert!(!engine
+        .legal_actions(&state, "p2")
+        .iter()
+        .any(|a| a.move_id.as_deref() == Some("tackle")));
+
+    let attack = || vec![move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")];
+    for turn in 2..=4 {
+        let before = state.players[0].team[0].hp;
+        state = run_turn_with_seed(&engine, &state, &attack(), turn);
+        assert_eq!(state.players[0].team[0].hp, before, "turn {turn}");
+        assert!(state.log.iter().any(|line| line == "Targetは たいあたりを 出すことができない！"));
+    }
+    assert_eq!(disabled_move(&state), None);
+    assert!(state.log.iter().any(|line| line == "Targetの たいあたりの かなしばりが 解けた！"));
+
+    let before = state.players[0].team[0].hp;
+    state = run_turn_with_seed(&engine, &state, &attack(), 5);
+    assert!(state.players[0].team[0].hp < before);
+}
+
+#[test]
+fn disable_fails_without_a_last_move() {
+    let engine = BattleEngine::default();
+    let mut state = disable_state();
+    state.players[1].team[0].speed = 10;
+    let next = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "disable", "p2"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert_eq!(disabled_move(&next), None);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+}
@@ -0,0 +1,169 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::{Action, BattleState};
+use engine_rust::data::moves::{MoveCategory, MoveData};
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+fn doubles_options() -> BattleOptions {
+    BattleOptions {
+        doubles: true,
+        ..Default::default()
+    }
+}
+
+fn doubles_state() -> BattleState {
+    let mon = |id: &str, name: &str, speed: i32| {
+        CreatureBuilder::new(id, name)
+            .moves(&["tackle", "earthquake", "harden"])
+            .hp(200, 200)
+            .stats(50, 50, 50, 50, speed)
+            .build()
+    };
+    battle_state(vec![
+        player("p1", "P1", vec![mon("p1-a", "A1", 90), mon("p1-b", "B1", 80)]),
+        player("p2", "P2", vec![mon("p2-a", "A2", 70), mon("p2-b", "B2", 60)]),
+    ])
+}
+
+fn run(engine: &BattleEngine, state: &BattleState, actions: &[Action], seed: u64) -> BattleState {
+    let mut rng = SeededRng::new(seed);
+    let mut rng_fn = || rng.next_f64();
+    engine.step_battle(state, actions, &mut rng_fn, doubles_options())
+}
+
+#[test]
+fn doubles_option_fills_second_slot_and_both_actives_act() {
+    let engine = BattleEngine::default();
+    let actions = vec![
+        move_action("p1", "harden", "p1"),
+        move_action("p1:1", "tackle", "p2:1"),
+        move_action("p2", "harden", "p2"),
+        move_action("p2:1", "harden", "p2:1"),
+    ];
+    let next = run(&engine, &doubles_state(), &actions, 3);
+
+    assert_eq!(next.players[0].partner_slot, Some(1));
+    assert_eq!(next.players[1].partner_slot, Some(1));
+    assert_eq!(next.players[0].team[0].stages.def, 1);
+    assert_eq!(next.players[1].team[0].stages.def, 1);
+    assert_eq!(next.players[1].team[1].stages.def, 1);
+    // たいあたりは 2体目の相手にだけ当たる
+    assert_eq!(next.players[1].team[0].hp, 200);
+    assert!(next.players[1].team[1].hp < 200);
+}
+
+#[test]
+fn singles_callers_ignore_the_bench() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "earthquake", "p2"), move_action("p2", "harden", "p2")];
+    let mut rng = SeededRng::new(3);
+    let mut rng_fn = || rng.next_f64();
+    let next = engine.step_battle(&doubles_state(), &actions, &mut rng_fn, BattleOptions::default());
+
+    assert_eq!(next.players[0].partner_slot, None);
+    assert!(next.players[1].team[0].hp < 200);
+    assert_eq!(next.players[0].team[1].hp, 200);
+    assert_eq!(next.players[1].team[1].hp, 200);
+}
+
+#[test]
+fn spread_move_hits_all_adjacent_at_three_quarters_damage() {
+    let actions = vec![
+        move_action("p1", "earthquake", "p2"),
+        move_action("p1:1", "harden", "p1:1"),
+        move_action("p2", "harden", "p2"),
+        move_action("p2:1", "harden", "p2:1"),
+    ];
+    let full = BattleEngine::default().with_rules(BattleRules::default().with_spread_multiplier(1.0));
+    let spread = BattleEngine::default();
+    let full_next = run(&full, &doubles_state(), &actions, 9);
+    let spread_next = run(&spread, &doubles_state(), &actions, 9);
+
+    let damage = |state: &BattleState, player: usize, slot: usize| 200 - state.players[player].team[slot].hp;
+    // じしんは 味方にも当たる。自分には当たらない
+    assert_eq!(damage(&spread_next, 0, 0), 0);
+    for (player, slot) in [(0, 1), (1, 0), (1, 1)] {
+        let full_damage = damage(&full_next, player, slot);
+        assert!(full_damage > 0);
+        assert_eq!(damage(&spread_next, player, slot), ((full_damage as f32 * 0.75).floor() as i32).max(1));
+    }
+}
+
+#[test]
+fn fainted_target_is_redirected_to_the_other_foe() {
+    let engine = BattleEngine::default();
+    let mut state = doubles_state();
+    state.players[0].partner_slot = Some(1);
+    state.players[1].partner_slot = Some(1);
+    state.players[1].team[0].hp = 0;
+    let actions = vec![
+        move_action("p1", "tackle", "p2"),
+        move_action("p1:1", "harden", "p1:1"),
+        move_action("p2:1", "harden", "p2:1"),
+    ];
+    let next = run(&engine, &state, &actions, 5);
+
+    assert_eq!(next.players[1].team[0].hp, 0);
+    assert!(next.players[1].team[1].hp < 200);
+}
+
+#[test]
+fn ally_target_resolves_to_partner() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(MoveData {
+        id: "cheer".to_string(),
+        name: Some("Cheer".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Status),
+        pp: Some(10),
+        power: None,
+        accuracy: None,
+        priority: Some(0),
+        description: None,
+        steps: vec![serde_json::from_value(json!({
+            "type": "modify_stage",
+            "target": "ally",
+            "stages": { "atk": 1 }
+        }))
+        .unwrap()],
+        tags: Vec::new(),
+        crit_rate: None,
+    });
+    let mut state = doubles_state();
+    state.players[0].team[1].moves.push("cheer".to_string());
+    let actions = vec![
+        move_action("p1", "harden", "p1"),
+        move_action("p1:1", "cheer", "p1"),
+        move_action("p2", "harden", "p2"),
+        move_action("p2:1", "harden", "p2:1"),
+    ];
+    let next = run(&engine, &state, &actions, 7);
+
+    assert_eq!(next.players[0].team[0].stages.atk, 1);
+    assert_eq!(next.players[0].team[1].stages.atk, 0);
+}
+
+#[test]
+fn flower_shield_reaches_grass_type_partners() {
+    let engine = BattleEngine::default();
+    let mut state = doubles_state();
+    state.players[0].team[0].moves.push("flower_shield".to_string());
+    state.players[0].team[1].types = vec!["grass".to_string()];
+    state.players[1].team[1].types = vec!["grass".to_string()];
+    let actions = vec![
+        move_action("p1", "flower_shield", "p1"),
+        move_action("p1:1", "harden", "p1:1"),
+        move_action("p2", "tackle", "p1"),
+        move_action("p2:1", "tackle", "p1"),
+    ];
+    let next = run(&engine, &state, &actions, 4);
+
+    // 2体目に並んでいる くさタイプにも届く
+    assert_eq!(next.players[0].team[0].stages.def, 0);
+    assert_eq!(next.players[0].team[1].stages.def, 2);
+    assert_eq!(next.players[1].team[0].stages.def, 0);
+    assert_eq!(next.players[1].team[1].stages.def, 1);
+}
@@ -0,0 +1,72 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn drain_state(move_id: &str, target_hp: i32, target_types: &[&str], item: Option<&str>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Drainer")
+        .moves(&[move_id])
+        .hp(100, 200)
+        .stats(100, 100, 100, 100, 100);
+    if let Some(item) = item {
+        attacker = attacker.item(item);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .types(target_types)
+        .hp(target_hp, 300)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker.build()]), player("p2", "P2", vec![target])])
+}
+
+fn run(state: &BattleState, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn giga_drain_heals_half_of_damage_dealt() {
+    let state = drain_state("giga_drain", 300, &["normal"], None);
+    let next = run(&state, "giga_drain");
+    let dealt = 300 - next.players[1].team[0].hp;
+    assert!(dealt > 1);
+    assert_eq!(next.players[0].team[0].hp - 100, dealt / 2);
+    assert!(next.log.iter().any(|line| line == "Targetから 体力を 吸い取った！"));
+}
+
+#[test]
+fn drain_counts_only_hp_the_target_had_left() {
+    // 残り 40 の相手を倒せば、計算上のダメージに関わらず 20 回復
+    let next = run(&drain_state("giga_drain", 40, &["normal"], None), "giga_drain");
+    assert_eq!(next.players[1].team[0].hp, 0);
+    assert_eq!(next.players[0].team[0].hp, 120);
+}
+
+#[test]
+fn drain_heals_nothing_against_immune_target() {
+    let next = run(&drain_state("dream_eater", 300, &["dark"], None), "dream_eater");
+    assert_eq!(next.players[1].team[0].hp, 300);
+    assert_eq!(next.players[0].team[0].hp, 100);
+    assert!(!next.log.iter().any(|line| line.contains("吸い取った")));
+}
+
+#[test]
+fn big_root_boosts_drain_heal() {
+    let next = run(&drain_state("giga_drain", 40, &["normal"], Some("big_root")), "giga_drain");
+    assert_eq!(next.players[0].team[0].hp, 126);
+}
+
+#[test]
+fn drain_through_substitute_counts_only_what_the_substitute_absorbed() {
+    let mut state = drain_state("giga_drain", 40, &["normal"], None);
+    let target = &mut state.players[1].team[0];
+    target.max_hp = 40;
+    target.statuses.push(status("substitute", None));
+    let next = run(&state, "giga_drain");
+    // みがわりの HP は最大HPの 1/4 = 10
+    assert_eq!(next.players[1].team[0].hp, 40);
+    assert_eq!(next.players[0].team[0].hp, 105);
+}
@@ -0,0 +1,97 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::effect_schema::{effect_schema, effect_spec, EFFECT_SPECS};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
+use serde_json::{Map, Value};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn single_step_move(effect_type: &str) -> MoveData {
+    MoveData {
+        id: "probe".to_string(),
+        name: Some("Probe".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Status),
+        pp: Some(10),
+        power: None,
+        accuracy: None,
+        priority: Some(0),
+        description: None,
+        steps: vec![Effect {
+            effect_type: effect_type.to_string(),
+            data: Map::new(),
+        }],
+        tags: Vec::new(),
+        crit_rate: None,
+    }
+}
+
+/// 一覧にある効果を一つずつ撃つ。apply_effects が扱っていなければデバッグビルドで止まる
+#[test]
+fn every_listed_effect_is_dispatched() {
+    let mut engine = BattleEngine::default();
+    for spec in EFFECT_SPECS {
+        engine.move_db.insert(single_step_move(spec.effect_type));
+        let user = CreatureBuilder::new("p1-mon", "User")
+            .moves(&["probe"])
+            .stats(50, 50, 50, 50, 100)
+            .build();
+        let target = CreatureBuilder::new("p2-mon", "Target")
+            .moves(&["splash"])
+            .stats(50, 50, 50, 50, 50)
+            .build();
+        let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])]);
+        let actions = vec![move_action("p1", "probe", "p2"), move_action("p2", "splash", "p2")];
+        run_turn_with_seed(&engine, &state, &actions, 1);
+    }
+}
+
+#[test]
+fn effect_schema_lists_fields_per_effect() {
+    let schema = effect_schema();
+    assert_eq!(schema["effects"]["damage"]["fields"]["power"], "number");
+    assert_eq!(schema["effects"]["apply_status"]["fields"]["target"], "target");
+    assert_eq!(schema["effects"]["protect"]["fields"]["stanceForme"], "string");
+    assert_eq!(schema["effects"]["cure_team_status"]["fields"], serde_json::json!({}));
+    assert_eq!(schema["effects"].as_object().unwrap().len(), EFFECT_SPECS.len());
+}
+
+/// 効果と、`effects` 型のフィールドに入れ子で書かれた効果が一覧に沿っているか調べる
+fn check_effect(move_id: &str, effect: &Effect, problems: &mut Vec<String>) {
+    let Some(spec) = effect_spec(&effect.effect_type) else {
+        problems.push(format!("{}: unknown effect {}", move_id, effect.effect_type));
+        return;
+    };
+    for (key, value) in &effect.data {
+        match spec.fields.iter().find(|(name, _)| name == key) {
+            None => problems.push(format!("{}: {} has unlisted field {}", move_id, effect.effect_type, key)),
+            Some((_, "effects")) => {
+                let nested: Vec<Effect> = match value {
+                    Value::Array(_) => serde_json::from_value(value.clone()).unwrap_or_default(),
+                    Value::Object(_) => serde_json::from_value(value.clone()).map(|e| vec![e]).unwrap_or_default(),
+                    _ => Vec::new(),
+                };
+                for inner in &nested {
+                    check_effect(move_id, inner, problems);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+#[test]
+fn shipped_moves_only_use_listed_effects_and_fields() {
+    let dir = MoveDatabase::load_from_yaml_dir(std::path::Path::new("data/moves")).expect("data/moves");
+    let bundled = MoveDatabase::load_from_yaml_str(include_str!("../data/moves.yaml")).expect("data/moves.yaml");
+    let mut problems = Vec::new();
+    for db in [&dir, &bundled] {
+        for (move_id, data) in db.as_map() {
+            for effect in &data.steps {
+                check_effect(move_id, effect, &mut problems);
+            }
+        }
+    }
+    problems.sort();
+    assert!(problems.is_empty(), "{}", problems.join("\n"));
+}
@@ -2,6 +2,7 @@ use engine_rust::core::effects::{apply_effects, apply_events, EffectContext};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages, Status};
 use engine_rust::core::statuses::{run_status_hooks, StatusHookContext};
 use engine_rust::data::moves::Effect;
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -23,6 +24,11 @@ fn make_creature(id: &str, name: &str) -> CreatureState {
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -32,28 +38,18 @@ fn make_creature(id: &str, name: &str) -> CreatureState {
 }
 
 fn make_state() -> BattleState {
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "P1".to_string(),
-        team: vec![make_creature("c1", "Alpha")],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "P2".to_string(),
-        team: vec![make_creature("c2", "Beta")],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let p1 = PlayerState::new("p1", "P1", vec![make_creature("c1", "Alpha")]);
+    let p2 = PlayerState::new("p2", "P2", vec![make_creature("c2", "Beta")]);
     BattleState {
         players: vec![p1, p2],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -78,6 +74,7 @@ fn modify_damage_scales_last_damage_event() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -110,6 +107,7 @@ fn crit_scales_last_damage_event() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -155,6 +153,7 @@ fn cure_all_status_clears_statuses() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -182,6 +181,7 @@ fn lock_move_forces_specific_move() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -214,6 +214,7 @@ fn lock_move_forces_specific_move() {
             action: Some(&action),
             move_data: None,
             type_chart: &type_chart,
+            rules: &BattleRules::default(),
         },
     );
     let override_action = result.override_action.expect("override action");
@@ -232,6 +233,7 @@ fn self_switch_marks_pending_switch() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -250,28 +252,22 @@ fn self_switch_marks_pending_switch() {
 #[test]
 fn force_switch_randomly_switches_target() {
     // Create state with target having 2 Pokémon
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "P1".to_string(),
-        team: vec![make_creature("c1", "Alpha")],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "P2".to_string(),
-        team: vec![make_creature("c2", "Beta"), make_creature("c3", "Gamma")],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let p1 = PlayerState::new("p1", "P1", vec![make_creature("c1", "Alpha")]);
+    let p2 = PlayerState::new(
+        "p2",
+        "P2",
+        vec![make_creature("c2", "Beta"), make_creature("c3", "Gamma")],
+    );
     let state = BattleState {
         players: vec![p1, p2],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     };
 
@@ -284,6 +280,7 @@ fn force_switch_randomly_switches_target() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -318,6 +315,7 @@ fn force_switch_with_only_one_pokemon_logs_failure() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -333,3 +331,80 @@ fn force_switch_with_only_one_pokemon_logs_failure() {
     let log_event = events.iter().find(|e| matches!(e, engine_rust::core::events::BattleEvent::Log { .. }));
     assert!(log_event.is_some(), "Expected Log event when no switch available");
 }
+
+fn count_crits(state: &BattleState, rules: &BattleRules, samples: usize) -> usize {
+    let type_chart = TypeChart::new();
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut rng = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed >> 11) as f64 / (1u64 << 53) as f64
+    };
+    let effects = vec![effect("damage", json!({ "power": 40 }))];
+    let mut crits = 0;
+    for _ in 0..samples {
+        let mut ctx = EffectContext {
+            attacker_player_id: "p1".to_string(),
+            target_player_id: "p2".to_string(),
+            move_data: None,
+            rng: &mut rng,
+            turn: 0,
+            type_chart: &type_chart,
+            rules,
+            bypass_protect: false,
+            ignore_immunity: false,
+            bypass_substitute: false,
+            ignore_substitute: false,
+            is_sound: false,
+            last_damage: None,
+        };
+        let events = apply_effects(state, &effects, &mut ctx);
+        if events.iter().any(|event| {
+            matches!(event, engine_rust::core::events::BattleEvent::Log { message, .. } if message == "急所に あたった！")
+        }) {
+            crits += 1;
+        }
+    }
+    crits
+}
+
+#[test]
+fn crit_rate_follows_configured_rules_table() {
+    let rules = BattleRules {
+        crit_chances: vec![1.0 / 16.0, 1.0 / 8.0, 1.0 / 2.0, 1.0],
+        ..Default::default()
+    };
+    let samples = 8000;
+    let rate = count_crits(&make_state(), &rules, samples) as f64 / samples as f64;
+    assert!((rate - 1.0 / 16.0).abs() < 0.012, "observed crit rate {rate}");
+}
+
+#[test]
+fn super_luck_moves_up_one_crit_table_index() {
+    let rules = BattleRules {
+        crit_chances: vec![0.0, 1.0],
+        ..Default::default()
+    };
+    let mut state = make_state();
+    assert_eq!(count_crits(&state, &rules, 50), 0);
+    state.players[0].team[0].ability = Some("super_luck".to_string());
+    assert_eq!(count_crits(&state, &rules, 50), 50);
+}
+
+#[test]
+fn merciless_always_crits_poisoned_target_even_with_short_table() {
+    let rules = BattleRules {
+        crit_chances: vec![0.0, 0.0],
+        ..Default::default()
+    };
+    let mut state = make_state();
+    state.players[0].team[0].ability = Some("merciless".to_string());
+    assert_eq!(count_crits(&state, &rules, 50), 0);
+    state.players[1].team[0].statuses.push(Status {
+        id: "poison".to_string(),
+        remaining_turns: None,
+        data: HashMap::new(),
+    });
+    assert_eq!(count_crits(&state, &rules, 50), 50);
+}
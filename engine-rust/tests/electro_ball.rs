@@ -0,0 +1,76 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::effects::effective_speed;
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::BattleState;
+use engine_rust::data::moves::{MoveCategory, MoveData};
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn probe_move(power: i32) -> MoveData {
+    MoveData {
+        id: format!("probe_{power}"),
+        name: Some(format!("Probe {power}")),
+        move_type: Some("electric".to_string()),
+        category: Some(MoveCategory::Special),
+        pp: Some(10),
+        power: Some(power),
+        accuracy: Some(1.0),
+        priority: Some(0),
+        description: None,
+        steps: vec![serde_json::from_value(json!({ "type": "damage", "power": power, "accuracy": 1.0 })).unwrap()],
+        tags: Vec::new(),
+        crit_rate: None,
+    }
+}
+
+fn matchup(scarf_and_paralysis: bool) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["electro_ball", "probe_60", "probe_120"])
+        .hp(300, 300)
+        .stats(50, 50, 80, 50, 100);
+    let mut target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 80, 100);
+    if scarf_and_paralysis {
+        attacker = attacker.item("choice_scarf");
+        target = target.with_status(status("paralysis", None));
+    }
+    battle_state(vec![
+        player("p1", "P1", vec![attacker.build()]),
+        player("p2", "P2", vec![target.build()]),
+    ])
+}
+
+fn damage_dealt(engine: &BattleEngine, state: &BattleState, move_id: &str) -> i32 {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    300 - run_turn_with_seed(engine, state, &actions, 4).players[1].team[0].hp
+}
+
+#[test]
+fn effective_speed_includes_choice_scarf_and_paralysis() {
+    let rules = BattleRules::default();
+    let state = matchup(true);
+    assert_eq!(effective_speed(&state, "p1", &rules), 150.0);
+    assert_eq!(effective_speed(&state, "p2", &rules), 50.0);
+}
+
+#[test]
+fn electro_ball_power_follows_effective_speed_ratio() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(probe_move(60));
+    engine.move_db.insert(probe_move(120));
+
+    // 素の素早さは同じなので 1倍 → 威力60
+    let even = matchup(false);
+    assert_eq!(damage_dealt(&engine, &even, "electro_ball"), damage_dealt(&engine, &even, "probe_60"));
+
+    // スカーフで 150、まひで 50 → 3倍 → 威力120
+    let boosted = matchup(true);
+    assert_eq!(
+        damage_dealt(&engine, &boosted, "electro_ball"),
+        damage_dealt(&engine, &boosted, "probe_120")
+    );
+}
@@ -0,0 +1,87 @@
+mod support;
+
+use engine_rust::core::battle::{default_end_turn_phases, BattleEngine, EndTurnPhase};
+use engine_rust::core::state::{BattleState, Status};
+use serde_json::Value;
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn faint_order(state: &BattleState) -> Vec<String> {
+    state
+        .log
+        .iter()
+        .filter(|line| line.ends_with("は たおれた！"))
+        .cloned()
+        .collect()
+}
+
+fn seeded_and_poisoned_state() -> BattleState {
+    let mut seed_data = HashMap::new();
+    seed_data.insert("sourceId".to_string(), Value::String("p1".to_string()));
+    let poisoned = CreatureBuilder::new("p1-mon", "Poisoned")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .with_status(status("poison", None))
+        .build();
+    let seeded = CreatureBuilder::new("p2-mon", "Seeded")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .with_status(Status {
+            id: "leech_seed".to_string(),
+            remaining_turns: None,
+            data: seed_data,
+        })
+        .build();
+    battle_state(vec![
+        player("p1", "P1", vec![poisoned]),
+        player("p2", "P2", vec![seeded]),
+    ])
+}
+
+#[test]
+fn end_turn_phase_order_decides_which_residual_faints_first() {
+    let state = seeded_and_poisoned_state();
+    let actions = vec![move_action("p1", "harden", "p2"), move_action("p2", "harden", "p1")];
+
+    let engine = BattleEngine::default();
+    let next = run_turn_with_seed(&engine, &state, &actions, 3);
+    assert_eq!(faint_order(&next), vec!["Seededは たおれた！"]);
+
+    let mut reordered = BattleEngine::default();
+    let leech = reordered
+        .end_turn_phases
+        .iter()
+        .position(|p| *p == EndTurnPhase::LeechSeed)
+        .unwrap();
+    let poison = reordered
+        .end_turn_phases
+        .iter()
+        .position(|p| *p == EndTurnPhase::StatusDamage)
+        .unwrap();
+    reordered.end_turn_phases.swap(leech, poison);
+    assert_ne!(reordered.end_turn_phases, default_end_turn_phases());
+
+    let next = run_turn_with_seed(&reordered, &state, &actions, 3);
+    assert_eq!(faint_order(&next), vec!["Poisonedは たおれた！"]);
+}
+
+#[test]
+fn residual_damage_runs_in_speed_order() {
+    let slow = CreatureBuilder::new("p1-mon", "Slow")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 30)
+        .with_status(status("poison", None))
+        .build();
+    let fast = CreatureBuilder::new("p2-mon", "Fast")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 90)
+        .with_status(status("poison", None))
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![slow]), player("p2", "P2", vec![fast])]);
+    let actions = vec![move_action("p1", "harden", "p2"), move_action("p2", "harden", "p1")];
+
+    let next = run_turn_with_seed(&BattleEngine::default(), &state, &actions, 5);
+    assert_eq!(faint_order(&next), vec!["Fastは たおれた！", "Slowは たおれた！"]);
+}
@@ -0,0 +1,69 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn endure_state(holder_hp: i32) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Endurer")
+                .moves(&["endure", "harden"])
+                .hp(holder_hp, 200)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Hitter")
+                .moves(&["tackle"])
+                .hp(200, 200)
+                .stats(200, 200, 200, 200, 100)
+                .build()],
+        ),
+    ])
+}
+
+fn endure_fails(state: &BattleState, seed: u64) -> bool {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "endure", "p1"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, state, &actions, seed);
+    next.log.iter().any(|line| line == "しかし うまく 決まらなかった！")
+}
+
+#[test]
+fn endure_survives_a_lethal_hit_at_one_hp() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "endure", "p1"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &endure_state(10), &actions, 1);
+    assert_eq!(next.players[0].team[0].hp, 1);
+    assert!(next.log.iter().any(|line| line == "Endurerは こらえた！"));
+    assert!(!next.log.iter().any(|line| line == "Endurerは たおれた！"));
+}
+
+#[test]
+fn endure_only_lasts_for_the_turn() {
+    let engine = BattleEngine::default();
+    let first = vec![move_action("p1", "endure", "p1"), move_action("p2", "tackle", "p1")];
+    let after = run_turn_with_seed(&engine, &endure_state(10), &first, 1);
+    let second = vec![move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")];
+    let next = run_turn_with_seed(&engine, &after, &second, 2);
+    assert_eq!(next.players[0].team[0].hp, 0);
+}
+
+#[test]
+fn repeated_endure_fails_more_often() {
+    let fresh = endure_state(200);
+    let mut chained = endure_state(200);
+    chained.players[0].team[0]
+        .volatile_data
+        .insert("protectSuccessCount".to_string(), json!(2));
+    let fresh_failures = (0..30).filter(|&seed| endure_fails(&fresh, seed)).count();
+    let chained_failures = (0..30).filter(|&seed| endure_fails(&chained, seed)).count();
+    assert_eq!(fresh_failures, 0);
+    assert!(chained_failures > 20, "{chained_failures}");
+}
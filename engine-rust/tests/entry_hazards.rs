@@ -0,0 +1,152 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, CreatureState};
+use engine_rust::data::moves::{MoveCategory, MoveData};
+use serde_json::{json, Value};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, switch_action, CreatureBuilder};
+
+fn bench(types: &[&str], ability: Option<&str>) -> CreatureState {
+    let mut builder = CreatureBuilder::new("p2-bench", "Bench")
+        .moves(&["splash"])
+        .types(types)
+        .hp(160, 160);
+    if let Some(ability) = ability {
+        builder = builder.ability(ability);
+    }
+    builder.build()
+}
+
+fn hazard_state(incoming: CreatureState) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Setter")
+                .moves(&["stealth_rock", "spikes", "splash"])
+                .stats(50, 50, 50, 50, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![
+                CreatureBuilder::new("p2-mon", "Lead")
+                    .moves(&["splash"])
+                    .stats(50, 50, 50, 50, 50)
+                    .build(),
+                incoming,
+            ],
+        ),
+    ])
+}
+
+fn set_hazard(engine: &BattleEngine, state: &BattleState, move_id: &str) -> BattleState {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(engine, state, &actions, 1)
+}
+
+fn switch_in_bench(engine: &BattleEngine, state: &BattleState) -> BattleState {
+    let actions = vec![move_action("p1", "splash", "p1"), switch_action("p2", 1)];
+    run_turn_with_seed(engine, state, &actions, 1)
+}
+
+#[test]
+fn stealth_rock_takes_half_from_a_four_times_weak_switch_in() {
+    let engine = BattleEngine::default();
+    let set = set_hazard(&engine, &hazard_state(bench(&["fire", "flying"], None)), "stealth_rock");
+    let ids: Vec<&str> = set.field.sides["p2"].iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids, vec!["stealth_rock"]);
+    assert!(set.field.global.iter().all(|e| e.id != "stealth_rock"));
+
+    let next = switch_in_bench(&engine, &set);
+    assert_eq!(next.players[1].team[1].hp, 80);
+    assert!(next.log.iter().any(|line| line == "Benchに とがった岩が 食いこんだ！"));
+}
+
+#[test]
+fn stealth_rock_scales_with_rock_effectiveness() {
+    let engine = BattleEngine::default();
+    let neutral = set_hazard(&engine, &hazard_state(bench(&["normal"], None)), "stealth_rock");
+    assert_eq!(switch_in_bench(&engine, &neutral).players[1].team[1].hp, 140);
+    let resisted = set_hazard(&engine, &hazard_state(bench(&["steel"], None)), "stealth_rock");
+    assert_eq!(switch_in_bench(&engine, &resisted).players[1].team[1].hp, 150);
+}
+
+#[test]
+fn spikes_stack_up_to_three_layers() {
+    let engine = BattleEngine::default();
+    let mut state = hazard_state(bench(&["normal"], None));
+    for _ in 0..3 {
+        state = set_hazard(&engine, &state, "spikes");
+    }
+    let spikes = &state.field.sides["p2"];
+    assert_eq!(spikes.len(), 1);
+    assert_eq!(spikes[0].data.get("layers"), Some(&Value::from(3)));
+
+    let again = set_hazard(&engine, &state, "spikes");
+    assert_eq!(again.field.sides["p2"][0].data.get("layers"), Some(&Value::from(3)));
+    assert!(again.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+
+    let next = switch_in_bench(&engine, &state);
+    assert_eq!(next.players[1].team[1].hp, 120);
+}
+
+#[test]
+fn single_layer_of_spikes_deals_an_eighth() {
+    let engine = BattleEngine::default();
+    let set = set_hazard(&engine, &hazard_state(bench(&["normal"], None)), "spikes");
+    let next = switch_in_bench(&engine, &set);
+    assert_eq!(next.players[1].team[1].hp, 140);
+    assert!(next.log.iter().any(|line| line == "Benchは まきびしの ダメージを 受けた！"));
+}
+
+#[test]
+fn airborne_switch_ins_ignore_spikes() {
+    let engine = BattleEngine::default();
+    for incoming in [bench(&["flying"], None), bench(&["normal"], Some("levitate"))] {
+        let set = set_hazard(&engine, &hazard_state(incoming), "spikes");
+        assert_eq!(switch_in_bench(&engine, &set).players[1].team[1].hp, 160);
+    }
+}
+
+#[test]
+fn magic_guard_ignores_entry_hazards() {
+    let engine = BattleEngine::default();
+    let mut state = hazard_state(bench(&["fire", "flying"], Some("magic_guard")));
+    state = set_hazard(&engine, &state, "stealth_rock");
+    state = set_hazard(&engine, &state, "spikes");
+    let next = switch_in_bench(&engine, &state);
+    assert_eq!(next.players[1].team[1].hp, 160);
+}
+
+#[test]
+fn remove_field_status_clears_a_side_hazard() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(MoveData {
+        id: "clear_spikes".to_string(),
+        name: Some("Clear Spikes".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Status),
+        pp: Some(10),
+        power: None,
+        accuracy: None,
+        priority: Some(0),
+        description: None,
+        steps: vec![serde_json::from_value(json!({
+            "type": "remove_field_status",
+            "statusId": "spikes",
+            "side": "target",
+        }))
+        .unwrap()],
+        tags: Vec::new(),
+        crit_rate: None,
+    });
+    let mut state = hazard_state(bench(&["normal"], None));
+    state.players[0].team[0].moves.push("clear_spikes".to_string());
+    let set = set_hazard(&engine, &state, "spikes");
+    assert_eq!(set.field.sides["p2"].len(), 1);
+
+    let cleared = set_hazard(&engine, &set, "clear_spikes");
+    assert!(cleared.field.sides["p2"].is_empty());
+}
@@ -29,6 +29,11 @@ fn create_creature(
         item: None,
         ability: ability.map(|s| s.to_string()),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         ability_data: HashMap::new(),
         move_pp: HashMap::new(),
         attack: atk,
@@ -40,28 +45,18 @@ fn create_creature(
 }
 
 fn create_battle(p1_team: Vec<CreatureState>, p2_team: Vec<CreatureState>) -> BattleState {
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "Player 1".to_string(),
-        team: p1_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "Player 2".to_string(),
-        team: p2_team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let p1 = PlayerState::new("p1", "Player 1", p1_team);
+    let p2 = PlayerState::new("p2", "Player 2", p2_team);
     BattleState {
         players: vec![p1, p2],
         turn: 0,
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -45,3 +45,17 @@ fn create_creature_rejects_invalid_move() {
 
     assert!(err.contains("Unknown move id"));
 }
+
+#[test]
+fn create_creature_takes_weight_from_species() {
+    let species_db = SpeciesDatabase::load_default().expect("load species");
+    let learnsets = LearnsetDatabase::load_default().expect("load learnsets");
+    let move_db = MoveDatabase::load_default().expect("load moves");
+
+    for species in species_db.as_map().values() {
+        assert!(species.weight.is_some(), "{} has no weight", species.id);
+        let creature = create_creature(species, CreateCreatureOptions::default(), &learnsets, &move_db)
+            .expect("create creature");
+        assert_eq!(creature.weight, species.weight);
+    }
+}
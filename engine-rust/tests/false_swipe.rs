@@ -0,0 +1,63 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn swipe_state(target_hp: i32) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Swiper")
+                .moves(&["false_swipe", "hold_back"])
+                .hp(200, 200)
+                .stats(200, 200, 200, 200, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target")
+                .moves(&["splash"])
+                .hp(target_hp, 200)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn swipe(state: &BattleState, move_id: &str, seed: u64) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, seed)
+}
+
+#[test]
+fn false_swipe_leaves_the_target_at_one_hp() {
+    for seed in 0..10 {
+        let next = swipe(&swipe_state(20), "false_swipe", seed);
+        assert_eq!(next.players[1].team[0].hp, 1);
+        assert!(!next.log.iter().any(|line| line == "Targetは たおれた！"));
+    }
+    let again = swipe(&swipe(&swipe_state(20), "hold_back", 1), "hold_back", 2);
+    assert_eq!(again.players[1].team[0].hp, 1);
+}
+
+#[test]
+fn false_swipe_deals_normal_damage_when_not_lethal() {
+    let next = swipe(&swipe_state(200), "false_swipe", 1);
+    let hp = next.players[1].team[0].hp;
+    assert!(hp > 1 && hp < 200, "{hp}");
+}
+
+#[test]
+fn false_swipe_can_break_a_substitute() {
+    let mut state = swipe_state(200);
+    let mut substitute = status("substitute", None);
+    substitute.data.insert("hp".to_string(), serde_json::json!(5));
+    state.players[1].team[0].statuses.push(substitute);
+    let next = swipe(&state, "false_swipe", 1);
+    assert_eq!(next.players[1].team[0].hp, 200);
+    assert!(!next.players[1].team[0].statuses.iter().any(|s| s.id == "substitute"));
+}
@@ -0,0 +1,73 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn sash_state(target_hp: i32, ability: Option<&str>, item: Option<&str>) -> BattleState {
+    let mut target = CreatureBuilder::new("p2-mon", "Holder")
+        .moves(&["splash"])
+        .hp(target_hp, 40)
+        .stats(50, 50, 50, 50, 50);
+    if let Some(ability) = ability {
+        target = target.ability(ability);
+    }
+    if let Some(item) = item {
+        target = target.item(item);
+    }
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Attacker")
+                .moves(&["tackle", "double_kick"])
+                .stats(250, 100, 100, 100, 100)
+                .build()],
+        ),
+        player("p2", "P2", vec![target.build()]),
+    ])
+}
+
+fn attack(state: &BattleState, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn focus_sash_survives_a_lethal_hit_from_full_hp_and_is_consumed() {
+    let next = attack(&sash_state(40, None, Some("focus_sash")), "tackle");
+    let holder = &next.players[1].team[0];
+    assert_eq!(holder.hp, 1);
+    assert_eq!(holder.item, None);
+    assert!(next.log.iter().any(|line| line == "Holderは きあいのタスキで 持ちこたえた！"));
+    assert_eq!(attack(&next, "tackle").players[1].team[0].hp, 0);
+}
+
+#[test]
+fn sturdy_survives_a_lethal_hit_from_full_hp_every_time() {
+    let next = attack(&sash_state(40, Some("sturdy"), None), "tackle");
+    assert_eq!(next.players[1].team[0].hp, 1);
+    assert!(next.log.iter().any(|line| line == "Holderは がんじょうで 持ちこたえた！"));
+
+    let mut healed = next.clone();
+    healed.players[1].team[0].hp = 40;
+    let again = attack(&healed, "tackle");
+    assert_eq!(again.players[1].team[0].hp, 1);
+    assert_eq!(again.players[1].team[0].ability.as_deref(), Some("sturdy"));
+}
+
+#[test]
+fn focus_sash_does_not_trigger_below_max_hp() {
+    let next = attack(&sash_state(39, Some("sturdy"), Some("focus_sash")), "tackle");
+    let holder = &next.players[1].team[0];
+    assert_eq!(holder.hp, 0);
+    assert_eq!(holder.item.as_deref(), Some("focus_sash"));
+}
+
+#[test]
+fn focus_sash_does_not_stop_the_second_strike_of_a_multi_hit_move() {
+    let next = attack(&sash_state(40, None, Some("focus_sash")), "double_kick");
+    assert_eq!(next.players[1].team[0].hp, 0);
+    assert!(next.log.iter().any(|line| line == "Holderは きあいのタスキで 持ちこたえた！"));
+}
@@ -0,0 +1,88 @@
+mod support;
+
+use engine_rust::core::battle::{pending_switch_players, BattleEngine, BattleOptions};
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, switch_action, CreatureBuilder, SeededRng};
+
+fn fainted_lead_state(engine: &BattleEngine) -> BattleState {
+    let lead = CreatureBuilder::new("p1-lead", "Lead")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 40)
+        .build();
+    let bench = CreatureBuilder::new("p1-bench", "Bench").moves(&["harden"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .moves(&["tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let foe_bench = CreatureBuilder::new("p2-bench", "FoeBench").moves(&["tackle"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![lead, bench]), player("p2", "P2", vec![foe, foe_bench])]);
+    run_turn_with_seed(
+        engine,
+        &state,
+        &[move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")],
+        1,
+    )
+}
+
+#[test]
+fn invalid_forced_switch_does_not_start_a_turn() {
+    let engine = BattleEngine::default();
+    let fainted = fainted_lead_state(&engine);
+    let next = run_turn_with_seed(&engine, &fainted, &[switch_action("p1", 0)], 2);
+    assert_eq!(next.turn, fainted.turn);
+    assert_eq!(pending_switch_players(&next), vec!["p1".to_string()]);
+    assert_eq!(next.log.last().map(String::as_str), Some("p1 cannot switch to slot 0"));
+}
+
+#[test]
+fn switch_from_a_player_without_a_fainted_creature_is_rejected() {
+    let engine = BattleEngine::default();
+    let fainted = fainted_lead_state(&engine);
+    let actions = [switch_action("p1", 1), switch_action("p2", 1)];
+    let mut rng = SeededRng::new(2);
+    let mut rng_fn = || rng.next_f64();
+    let err = engine
+        .try_step_battle(&fainted, &actions, &mut rng_fn, BattleOptions::default())
+        .unwrap_err();
+    assert_eq!(err, "p2 has no fainted creature to replace");
+    let next = run_turn_with_seed(&engine, &fainted, &actions, 2);
+    assert_eq!(next.players[1].active_slot, 0);
+    assert_eq!(pending_switch_players(&next), vec!["p1".to_string()]);
+}
+
+#[test]
+fn replacement_switch_after_faint_brings_in_bench() {
+    let lead = CreatureBuilder::new("p1-lead", "Lead")
+        .moves(&["harden"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 40)
+        .build();
+    let bench = CreatureBuilder::new("p1-bench", "Bench").moves(&["harden"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .moves(&["tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![lead, bench]), player("p2", "P2", vec![foe])]);
+    let engine = BattleEngine::default();
+
+    let fainted = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert_eq!(fainted.players[0].team[0].hp, 0);
+    assert_eq!(pending_switch_players(&fainted), vec!["p1".to_string()]);
+
+    let mut rng = SeededRng::new(2);
+    let mut rng_fn = || rng.next_f64();
+    let missing = engine.resolve_forced_switches(&fainted, &[], &mut rng_fn);
+    assert!(missing.is_err());
+
+    let replaced = run_turn_with_seed(&engine, &fainted, &[switch_action("p1", 1)], 2);
+    assert_eq!(replaced.players[0].active_slot, 1);
+    assert_eq!(replaced.turn, fainted.turn);
+    assert!(pending_switch_players(&replaced).is_empty());
+    assert_eq!(replaced.history.as_ref().unwrap().turns.len(), 2);
+}
@@ -0,0 +1,80 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn is_frozen(state: &BattleState, player_index: usize) -> bool {
+    state.players[player_index].team[0].statuses.iter().any(|s| s.id == "freeze")
+}
+
+fn frozen_target_state() -> BattleState {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["flame_wheel", "tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(400, 400)
+        .with_status(status("freeze", None))
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])])
+}
+
+#[test]
+fn fire_move_thaws_frozen_target() {
+    let engine = BattleEngine::default();
+    let state = frozen_target_state();
+
+    let control = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+    assert!(is_frozen(&control, 1));
+
+    let next = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "flame_wheel", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+    assert!(!is_frozen(&next, 1));
+    assert!(next.log.iter().any(|line| line == "Targetの こおりが とけた！"));
+    // とけた後は そのターンに 行動できる
+    assert_eq!(next.players[1].team[0].stages.def, 1);
+}
+
+#[test]
+fn scald_thaws_frozen_user() {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["scald", "tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .with_status(status("freeze", None))
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(400, 400)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let control = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+    assert!(is_frozen(&control, 0));
+    assert_eq!(control.players[1].team[0].hp, 400);
+
+    let next = run_turn_with_seed(
+        &engine,
+        &state,
+        &[move_action("p1", "scald", "p2"), move_action("p2", "harden", "p2")],
+        1,
+    );
+    assert!(!is_frozen(&next, 0));
+    assert!(next.players[1].team[0].hp < 400);
+}
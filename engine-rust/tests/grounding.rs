@@ -0,0 +1,132 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn flying_target_state(ability: &str) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .types(&["normal"])
+        .moves(&["thousand_arrows", "earthquake"])
+        .stats(80, 50, 50, 50, 80)
+        .build();
+    let bird = CreatureBuilder::new("p2-mon", "Bird")
+        .types(&["flying"])
+        .ability(ability)
+        .moves(&["harden"])
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![bird])])
+}
+
+#[test]
+fn earthquake_misses_flying_target() {
+    let engine = BattleEngine::default();
+    let state = flying_target_state("run_away");
+    let actions = vec![move_action("p1", "earthquake", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    let bird = &next.players[1].team[0];
+    assert_eq!(bird.hp, bird.max_hp);
+}
+
+#[test]
+fn thousand_arrows_grounds_flying_target_for_earthquake() {
+    let engine = BattleEngine::default();
+    let state = flying_target_state("run_away");
+    let arrows = vec![move_action("p1", "thousand_arrows", "p2"), move_action("p2", "harden", "p2")];
+    let after_arrows = run_turn_with_seed(&engine, &state, &arrows, 1);
+
+    let bird = &after_arrows.players[1].team[0];
+    assert!(bird.hp < bird.max_hp);
+    assert!(bird.statuses.iter().any(|s| s.id == "grounded"));
+    assert!(after_arrows.log.iter().any(|l| l.contains("Birdは 地面に 落とされた！")));
+
+    let quake = vec![move_action("p1", "earthquake", "p2"), move_action("p2", "harden", "p2")];
+    let after_quake = run_turn_with_seed(&engine, &after_arrows, &quake, 2);
+    assert!(after_quake.players[1].team[0].hp < after_arrows.players[1].team[0].hp);
+}
+
+#[test]
+fn thousand_arrows_hits_levitate_target() {
+    let engine = BattleEngine::default();
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["thousand_arrows"])
+        .stats(80, 50, 50, 50, 80)
+        .build();
+    let floater = CreatureBuilder::new("p2-mon", "Floater")
+        .types(&["normal"])
+        .ability("levitate")
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![floater])]);
+    let actions = vec![move_action("p1", "thousand_arrows", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    let floater = &next.players[1].team[0];
+    assert!(floater.hp < floater.max_hp);
+    assert!(floater.statuses.iter().any(|s| s.id == "grounded"));
+}
+
+#[test]
+fn roost_drops_flying_type_until_turn_end() {
+    let engine = BattleEngine::default();
+    let bird = CreatureBuilder::new("p1-mon", "Bird")
+        .types(&["flying"])
+        .moves(&["roost", "harden"])
+        .hp(50, 200)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let digger = CreatureBuilder::new("p2-mon", "Digger")
+        .types(&["normal"])
+        .moves(&["earthquake"])
+        .stats(80, 50, 50, 50, 30)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![bird]), player("p2", "P2", vec![digger])]);
+
+    let roost = vec![move_action("p1", "roost", "p1"), move_action("p2", "earthquake", "p1")];
+    let roosted = run_turn_with_seed(&engine, &state, &roost, 1);
+    let bird = &roosted.players[0].team[0];
+    // 回復後の 150 から じしんの分だけ減っている
+    assert!(bird.hp < 150);
+    assert_eq!(bird.types, vec!["flying".to_string()]);
+    assert!(!bird.statuses.iter().any(|s| s.id == "type_change"));
+
+    let harden = vec![move_action("p1", "harden", "p1"), move_action("p2", "earthquake", "p1")];
+    let next = run_turn_with_seed(&engine, &roosted, &harden, 2);
+    assert_eq!(next.players[0].team[0].hp, roosted.players[0].team[0].hp);
+}
+
+#[test]
+fn roost_at_full_hp_keeps_flying_type() {
+    let engine = BattleEngine::default();
+    let bird = CreatureBuilder::new("p1-mon", "Bird")
+        .types(&["flying"])
+        .moves(&["roost"])
+        .hp(200, 200)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let digger = CreatureBuilder::new("p2-mon", "Digger")
+        .types(&["normal"])
+        .moves(&["earthquake"])
+        .stats(80, 50, 50, 50, 30)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![bird]), player("p2", "P2", vec![digger])]);
+
+    let actions = vec![move_action("p1", "roost", "p1"), move_action("p2", "earthquake", "p1")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    // 回復に失敗したので ひこうタイプは外れず、じしんは当たらない
+    assert!(next.log.iter().any(|line| line.contains("満タン")));
+    assert_eq!(next.players[0].team[0].hp, 200);
+}
+
+#[test]
+fn inverse_battle_earthquake_is_super_effective_on_flying() {
+    let engine = BattleEngine::default().inverse_battle();
+    let state = flying_target_state("run_away");
+    let actions = vec![move_action("p1", "earthquake", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    let bird = &next.players[1].team[0];
+    assert!(bird.hp < bird.max_hp);
+    assert!(next.log.iter().any(|l| l.contains("効果は 抜群だ！")));
+}
@@ -0,0 +1,50 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn grudge_state() -> BattleState {
+    let grudger = CreatureBuilder::new("p1-mon", "Grudger")
+        .moves(&["grudge", "harden"])
+        .hp(5, 100)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let bench = CreatureBuilder::new("p1-bench", "Bench").moves(&["harden"]).build();
+    let attacker = CreatureBuilder::new("p2-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(100, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![grudger, bench]), player("p2", "P2", vec![attacker])])
+}
+
+fn tackle_pp(state: &BattleState) -> Option<i32> {
+    state.players[1].team[0].move_pp.get("tackle").copied()
+}
+
+#[test]
+fn fainting_under_grudge_drains_the_finishing_move() {
+    let engine = BattleEngine::default();
+    let next = run_turn_with_seed(
+        &engine,
+        &grudge_state(),
+        &[move_action("p1", "grudge", "p1"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert_eq!(next.players[0].team[0].hp, 0);
+    assert_eq!(tackle_pp(&next), Some(0));
+    assert!(next.log.iter().any(|line| line == "Attackerの たいあたりは おんねんで PPが 0に なった！"));
+}
+
+#[test]
+fn fainting_without_grudge_only_spends_one_pp() {
+    let engine = BattleEngine::default();
+    let next = run_turn_with_seed(
+        &engine,
+        &grudge_state(),
+        &[move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert_eq!(next.players[0].team[0].hp, 0);
+    assert_eq!(tackle_pp(&next), Some(34));
+}
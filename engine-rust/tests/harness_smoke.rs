@@ -1,7 +1,7 @@
 mod support;
 
 use engine_rust::core::battle::BattleEngine;
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use support::harness::{
@@ -24,7 +24,7 @@ fn harness_seeded_run_is_reproducible() {
         id: "chip".to_string(),
         name: Some("Chip".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -38,7 +38,7 @@ fn harness_seeded_run_is_reproducible() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -0,0 +1,52 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::state::{BattleState, MoveOutcome};
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+fn recover_state(hp: i32) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Healer").moves(&["recover"]).hp(hp, 200).build()],
+        ),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "Idle").moves(&["harden"]).build()]),
+    ])
+}
+
+fn run(state: &BattleState) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "recover", "p1"), move_action("p2", "harden", "p2")];
+    let mut rng = SeededRng::new(1);
+    let mut rng_fn = || rng.next_f64();
+    let options = BattleOptions {
+        record_history: true,
+        ..Default::default()
+    };
+    engine.step_battle(state, &actions, &mut rng_fn, options)
+}
+
+#[test]
+fn recover_restores_half_of_max_hp() {
+    let next = run(&recover_state(100));
+    assert_eq!(next.players[0].team[0].hp, 200);
+    assert!(next.log.iter().any(|line| line == "Healerの HPが 100回復した！"));
+}
+
+#[test]
+fn heal_never_exceeds_max_hp() {
+    let next = run(&recover_state(150));
+    assert_eq!(next.players[0].team[0].hp, 200);
+    assert!(next.log.iter().any(|line| line == "Healerの HPが 50回復した！"));
+}
+
+#[test]
+fn recover_fails_at_full_hp() {
+    let next = run(&recover_state(200));
+    assert!(next.log.iter().any(|line| line == "Healerの HPは 満タンだ！"));
+    assert!(!next.log.iter().any(|line| line.contains("回復した")));
+    let outcomes = &next.history.as_ref().unwrap().turns[0].outcomes;
+    let recover = outcomes.iter().find(|record| record.move_id == "recover").unwrap();
+    assert_eq!(recover.outcome, MoveOutcome::Failed("hp_full".to_string()));
+}
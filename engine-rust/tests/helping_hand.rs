@@ -0,0 +1,52 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn helped_state(helped_on_turn: Option<u32>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Helped")
+        .moves(&["tackle", "helping_hand"])
+        .hp(200, 200)
+        .stats(200, 50, 50, 50, 100)
+        .build();
+    if let Some(turn) = helped_on_turn {
+        attacker.volatile_data.insert("helpingHand".to_string(), json!(turn));
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(1000, 1000)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])])
+}
+
+fn tackle_damage(state: &BattleState) -> i32 {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    1000 - run_turn_with_seed(&engine, state, &actions, 4).players[1].team[0].hp
+}
+
+#[test]
+fn helping_hand_volatile_boosts_damage_by_half() {
+    let plain = tackle_damage(&helped_state(None));
+    // 最初のターンは turn 1
+    let helped = tackle_damage(&helped_state(Some(1)));
+    let expected = plain as f64 * 1.5;
+    assert!((helped as f64 - expected).abs() <= 3.0, "{helped} vs {plain}");
+}
+
+#[test]
+fn helping_hand_only_lasts_for_the_turn_it_was_used() {
+    assert_eq!(tackle_damage(&helped_state(Some(0))), tackle_damage(&helped_state(None)));
+}
+
+#[test]
+fn helping_hand_fails_without_an_ally() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "helping_hand", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &helped_state(None), &actions, 1);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+    assert!(!next.players[0].team[0].volatile_data.contains_key("helpingHand"));
+}
@@ -2,15 +2,16 @@ use engine_rust::core::state::{BattleState, PlayerState, CreatureState, FieldSta
 use engine_rust::core::events::{apply_event, BattleEvent};
 use engine_rust::core::effects::{apply_effects, EffectContext};
 use engine_rust::data::moves::MoveDatabase;
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use engine_rust::core::abilities::{run_ability_hooks, AbilityHookContext};
 use std::collections::HashMap;
 
 fn create_test_state() -> BattleState {
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "Player 1".to_string(),
-        team: vec![CreatureState {
+    let p1 = PlayerState::new(
+        "p1",
+        "Player 1",
+        vec![CreatureState {
             id: "c1".to_string(),
             species_id: "test_mon".to_string(),
             name: "Mon1".to_string(),
@@ -24,6 +25,11 @@ fn create_test_state() -> BattleState {
             item: None,
             ability: None,
             volatile_data: HashMap::new(),
+            revealed_moves: Vec::new(),
+            species_revealed: false,
+            weight: None,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
             ability_data: HashMap::new(),
             move_pp: HashMap::new(),
             attack: 10,
@@ -32,13 +38,11 @@ fn create_test_state() -> BattleState {
             sp_defense: 10,
             speed: 10,
         }],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "Player 2".to_string(),
-        team: vec![CreatureState {
+    );
+    let p2 = PlayerState::new(
+        "p2",
+        "Player 2",
+        vec![CreatureState {
             id: "c2".to_string(),
             species_id: "test_mon2".to_string(),
             name: "Mon2".to_string(),
@@ -52,6 +56,11 @@ fn create_test_state() -> BattleState {
             item: None,
             ability: None,
             volatile_data: HashMap::new(),
+            revealed_moves: Vec::new(),
+            species_revealed: false,
+            weight: None,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
             ability_data: HashMap::new(),
             move_pp: HashMap::new(),
             attack: 10,
@@ -60,17 +69,17 @@ fn create_test_state() -> BattleState {
             sp_defense: 10,
             speed: 10,
         }],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    );
     BattleState {
         players: vec![p1, p2],
         turn: 1,
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -91,6 +100,7 @@ fn test_morning_sun_healing() {
         rng: &mut rng,
         turn: 1,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -0,0 +1,39 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn last_respects_damage(fainted: usize) -> i32 {
+    let mut team = vec![CreatureBuilder::new("p1-mon", "Mourner")
+        .moves(&["last_respects"])
+        .hp(200, 200)
+        .stats(100, 100, 100, 100, 100)
+        .build()];
+    for i in 0..2 {
+        let hp = if i < fainted { 0 } else { 100 };
+        team.push(CreatureBuilder::new(&format!("p1-bench{i}"), "Bench").hp(hp, 100).build());
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .types(&["psychic"])
+        .hp(2000, 2000)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let state: BattleState = battle_state(vec![player("p1", "P1", team), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "last_respects", "p2"), move_action("p2", "harden", "p2")];
+    2000 - run_turn_with_seed(&engine, &state, &actions, 3).players[1].team[0].hp
+}
+
+#[test]
+fn last_respects_power_grows_with_fainted_teammates() {
+    let none = last_respects_damage(0);
+    let one = last_respects_damage(1);
+    let two = last_respects_damage(2);
+    assert!(none > 0);
+    assert!(one > none && two > one, "{none} {one} {two}");
+    // 威力 50 → 100 → 150
+    assert!((one as f64 / none as f64 - 2.0).abs() < 0.15, "{none} {one}");
+    assert!((two as f64 / none as f64 - 3.0).abs() < 0.2, "{none} {two}");
+}
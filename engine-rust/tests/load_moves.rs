@@ -1,4 +1,4 @@
-use engine_rust::data::moves::MoveDatabase;
+use engine_rust::data::moves::{MoveCategory, MoveDatabase};
 use std::path::Path;
 
 #[test]
@@ -7,4 +7,36 @@ fn load_full_move_database() {
     let db = MoveDatabase::load_from_yaml_file(path).expect("load moves.yaml");
     assert!(!db.as_map().is_empty(), "move database should not be empty");
     assert!(db.get("tackle").is_some(), "expected tackle in full database");
+    assert_eq!(db.get("tackle").unwrap().category, Some(MoveCategory::Physical));
+}
+
+#[test]
+fn move_category_parses_known_values_and_rejects_typos() {
+    let yaml = "id: beam\nname: Beam\ntype: normal\ncategory: Special\nsteps: []\n";
+    let db = MoveDatabase::load_from_yaml_str(yaml).expect("load single move");
+    assert_eq!(db.get("beam").unwrap().category, Some(MoveCategory::Special));
+
+    let typo = "id: beam\nname: Beam\ntype: normal\ncategory: phyiscal\nsteps: []\n";
+    let err = MoveDatabase::load_from_yaml_str(typo).expect_err("typo should fail");
+    assert!(err.to_string().contains("phyiscal"), "{err}");
+}
+
+#[test]
+fn by_name_resolves_display_names_to_moves() {
+    let db = MoveDatabase::load_from_yaml_file(Path::new("data/moves.yaml")).expect("load moves.yaml");
+    assert_eq!(db.by_name("たいあたり").map(|m| m.id.as_str()), Some("tackle"));
+    assert!(db.by_name("tackle").is_none());
+}
+
+#[test]
+fn by_name_prefers_the_smallest_id_for_duplicate_names() {
+    let yaml = "- {id: zeta, name: Twin, type: normal, steps: []}\n- {id: alpha, name: Twin, type: normal, steps: []}\n";
+    let mut db = MoveDatabase::load_from_yaml_str(yaml).expect("load moves");
+    assert_eq!(db.by_name("Twin").map(|m| m.id.as_str()), Some("alpha"));
+
+    let mut renamed = db.get("alpha").unwrap().clone();
+    renamed.name = Some("Solo".to_string());
+    db.insert(renamed);
+    assert_eq!(db.by_name("Twin").map(|m| m.id.as_str()), Some("zeta"));
+    assert_eq!(db.by_name("Solo").map(|m| m.id.as_str()), Some("alpha"));
 }
@@ -0,0 +1,111 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions, LogRetention};
+use engine_rust::core::events::BattleEvent;
+use engine_rust::core::rng::SplitMix64Rng;
+use engine_rust::core::state::{Action, BattleState, LogCategory};
+use serde_json::json;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn tackle_state(target_hp: i32) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Hitter")
+                .moves(&["tackle"])
+                .hp(200, 200)
+                .stats(100, 100, 100, 100, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target")
+                .moves(&["harden"])
+                .hp(target_hp, 200)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn tackle_actions() -> Vec<Action> {
+    vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")]
+}
+
+fn category_of(state: &BattleState, predicate: impl Fn(&str) -> bool) -> LogCategory {
+    state
+        .log_entries()
+        .into_iter()
+        .find(|(_, line)| predicate(line))
+        .map(|(category, _)| category)
+        .expect("log line")
+}
+
+#[test]
+fn turn_lines_carry_their_categories() {
+    let engine = BattleEngine::default();
+    let next = run_turn_with_seed(&engine, &tackle_state(1), &tackle_actions(), 1);
+    assert_eq!(next.log.len(), next.log_categories.len());
+    assert_eq!(category_of(&next, |line| line.starts_with("--- Turn ")), LogCategory::System);
+    assert_eq!(category_of(&next, |line| line == "P1の たいあたり！"), LogCategory::Move);
+    assert_eq!(category_of(&next, |line| line.contains("ダメージ 受けた")), LogCategory::Damage);
+    assert_eq!(category_of(&next, |line| line == "Targetは たおれた！"), LogCategory::Faint);
+}
+
+#[test]
+fn substitute_and_status_end_lines_carry_their_categories() {
+    let engine = BattleEngine::default();
+    let mut state = tackle_state(200);
+    let mut substitute = status("substitute", None);
+    substitute.data.insert("hp".to_string(), json!(150));
+    state.players[1].team[0].statuses.push(substitute);
+    state.players[1].team[0].statuses.push(status("taunt", Some(1)));
+    let next = run_turn_with_seed(&engine, &state, &tackle_actions(), 1);
+    assert_eq!(next.log.len(), next.log_categories.len());
+    assert_eq!(category_of(&next, |line| line == "Targetの みがわりが 攻撃を 受けた！"), LogCategory::Damage);
+    assert_eq!(category_of(&next, |line| line == "Targetの ちょうはつの 効果が 解けた！"), LogCategory::Status);
+}
+
+#[test]
+fn log_with_records_the_category_in_meta() {
+    let event = BattleEvent::log_with(LogCategory::Ability, "test");
+    match event {
+        BattleEvent::Log { message, meta } => {
+            assert_eq!(message, "test");
+            assert_eq!(meta.get("category"), Some(&json!("ability")));
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+}
+
+#[test]
+fn log_retention_keeps_categories_aligned() {
+    let engine = BattleEngine::default();
+    let options = BattleOptions {
+        log_retention: LogRetention::LastNTurns(1),
+        ..Default::default()
+    };
+    let mut rng = SplitMix64Rng::new(3);
+    let mut rng_fn = rng.as_fn();
+    let first = engine.step_battle(&tackle_state(200), &tackle_actions(), &mut rng_fn, options.clone());
+    let second = engine.step_battle(&first, &tackle_actions(), &mut rng_fn, options);
+    assert_eq!(second.log.len(), second.log_categories.len());
+    assert_eq!(second.log_entries()[0], (LogCategory::System, "--- Turn 2 ---"));
+}
+
+#[test]
+fn states_saved_without_categories_read_as_system() {
+    let mut state = tackle_state(200);
+    state.log = vec!["old line".to_string()];
+    let mut value: serde_json::Value = serde_json::from_str(&state.to_json().unwrap()).unwrap();
+    value.as_object_mut().unwrap().remove("logCategories");
+    let mut restored = BattleState::from_json(&value.to_string()).unwrap();
+    assert!(restored.log_categories.is_empty());
+    restored.push_log(LogCategory::Damage, "new line");
+    assert_eq!(
+        restored.log_entries(),
+        vec![(LogCategory::System, "old line"), (LogCategory::Damage, "new line")]
+    );
+}
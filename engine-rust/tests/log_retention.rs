@@ -0,0 +1,31 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions, LogRetention};
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+#[test]
+fn last_n_turns_keeps_only_latest_turn_in_log() {
+    let lead = CreatureBuilder::new("p1-mon", "Lead").moves(&["harden"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    let mut state = battle_state(vec![player("p1", "P1", vec![lead]), player("p2", "P2", vec![foe])]);
+    let engine = BattleEngine::default();
+    let options = BattleOptions {
+        log_retention: LogRetention::LastNTurns(1),
+        ..Default::default()
+    };
+
+    let mut rng = SeededRng::new(3);
+    let mut rng_fn = || rng.next_f64();
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+    for _ in 0..3 {
+        state = engine.step_battle(&state, &actions, &mut rng_fn, options.clone());
+    }
+    engine.apply_turn(&mut state, &actions, &mut rng_fn, options);
+
+    assert_eq!(state.log.first().map(String::as_str), Some("--- Turn 4 ---"));
+    assert_eq!(state.log.iter().filter(|line| line.starts_with("--- Turn")).count(), 1);
+    // ターン別ログは履歴側に残る
+    let history = state.history.as_ref().unwrap();
+    assert_eq!(history.turns.len(), 4);
+    assert_eq!(history.turns[0].log.first().map(String::as_str), Some("--- Turn 1 ---"));
+}
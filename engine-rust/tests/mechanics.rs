@@ -3,15 +3,16 @@ use engine_rust::core::statuses::{run_status_hooks, StatusHookContext};
 use engine_rust::core::events::{apply_event, BattleEvent};
 use engine_rust::core::effects::{apply_effects, EffectContext};
 use engine_rust::data::moves::Effect;
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use std::collections::HashMap;
 use serde_json::{Map, Value};
 
 fn create_test_state() -> BattleState {
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "Player 1".to_string(),
-        team: vec![CreatureState {
+    let p1 = PlayerState::new(
+        "p1",
+        "Player 1",
+        vec![CreatureState {
             id: "c1".to_string(),
             species_id: "test_mon".to_string(),
             name: "Mon1".to_string(),
@@ -25,6 +26,11 @@ fn create_test_state() -> BattleState {
             item: None,
             ability: None,
             volatile_data: HashMap::new(),
+            revealed_moves: Vec::new(),
+            species_revealed: false,
+            weight: None,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
             ability_data: HashMap::new(),
             move_pp: HashMap::new(),
             attack: 10,
@@ -33,17 +39,17 @@ fn create_test_state() -> BattleState {
             sp_defense: 10,
             speed: 10,
         }],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    );
     BattleState {
         players: vec![p1],
         turn: 1,
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: Some(BattleHistory { turns: Vec::new() }),
     }
 }
@@ -66,6 +72,7 @@ fn test_lock_move_force_last_move() {
         actions: vec![action.clone()],
         log: vec![],
         rng: vec![],
+        outcomes: vec![],
     });
 
     // Add lock_move status
@@ -89,6 +96,7 @@ fn test_lock_move_force_last_move() {
         }),
         move_data: None,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
     };
 
     let result = run_status_hooks(&state, "p1", "onBeforeAction", ctx);
@@ -159,6 +167,7 @@ fn test_protect_event_transform() {
         action: None,
         move_data: None,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
     };
 
     let result = run_status_hooks(&state, "p1", "onEventTransform", ctx);
@@ -186,6 +195,7 @@ fn test_protect_reset_on_failure() {
         rng: &mut rng,
         turn: 1,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -216,10 +226,10 @@ fn test_parental_bond() {
     state.players[0].team[0].ability = Some("parental_bond".to_string());
     
     // Add a dummy target player
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "Player 2".to_string(),
-        team: vec![CreatureState {
+    let p2 = PlayerState::new(
+        "p2",
+        "Player 2",
+        vec![CreatureState {
             id: "c2".to_string(),
             species_id: "test_mon_2".to_string(),
             name: "Mon2".to_string(),
@@ -233,6 +243,11 @@ fn test_parental_bond() {
             item: None,
             ability: None,
             volatile_data: HashMap::new(),
+            revealed_moves: Vec::new(),
+            species_revealed: false,
+            weight: None,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
             ability_data: HashMap::new(),
             move_pp: HashMap::new(),
             attack: 10,
@@ -241,9 +256,7 @@ fn test_parental_bond() {
             sp_defense: 10,
             speed: 10,
         }],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    );
     state.players.push(p2);
 
     let mut rng = || 0.5;
@@ -255,6 +268,7 @@ fn test_parental_bond() {
         rng: &mut rng,
         turn: 1,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -0,0 +1,66 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn metronome_state(item: Option<&str>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Ticker")
+        .moves(&["tackle", "quick_attack"])
+        .hp(200, 200)
+        .stats(100, 50, 50, 50, 100);
+    if let Some(item) = item {
+        attacker = attacker.item(item);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["splash"])
+        .hp(2000, 2000)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker.build()]), player("p2", "P2", vec![target])])
+}
+
+fn hit(engine: &BattleEngine, state: &BattleState, move_id: &str) -> (BattleState, i32) {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    let next = run_turn_with_seed(engine, state, &actions, 3);
+    let dealt = state.players[1].team[0].hp - next.players[1].team[0].hp;
+    (next, dealt)
+}
+
+fn assert_scaled(actual: i32, plain: i32, factor: f64) {
+    let expected = plain as f64 * factor;
+    assert!((actual as f64 - expected).abs() <= 2.0, "{actual} vs {expected}");
+}
+
+#[test]
+fn metronome_item_escalates_consecutive_uses_and_resets_on_a_different_move() {
+    let engine = BattleEngine::default();
+    let (_, plain) = hit(&engine, &metronome_state(None), "tackle");
+    assert!(plain > 5);
+
+    let mut state = metronome_state(Some("metronome"));
+    for factor in [1.0, 1.2, 1.4] {
+        let (next, dealt) = hit(&engine, &state, "tackle");
+        assert_scaled(dealt, plain, factor);
+        state = next;
+    }
+
+    let (next, dealt) = hit(&engine, &state, "quick_attack");
+    assert_scaled(dealt, plain, 1.0);
+    let (_, dealt) = hit(&engine, &next, "quick_attack");
+    assert_scaled(dealt, plain, 1.2);
+}
+
+#[test]
+fn metronome_item_boost_is_capped_at_double() {
+    let engine = BattleEngine::default();
+    let (_, plain) = hit(&engine, &metronome_state(None), "tackle");
+    let mut state = metronome_state(Some("metronome"));
+    let mut dealt = 0;
+    for _ in 0..8 {
+        let (next, last) = hit(&engine, &state, "tackle");
+        dealt = last;
+        state = next;
+    }
+    assert_scaled(dealt, plain, 2.0);
+}
@@ -2,6 +2,7 @@ use engine_rust::core::effects::{apply_effects, EffectContext};
 use engine_rust::core::events::BattleEvent;
 use engine_rust::core::state::{BattleState, CreatureState, FieldEffect, FieldState, PlayerState, StatStages, Status};
 use engine_rust::data::moves::{Effect, MoveDatabase};
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -92,6 +93,11 @@ fn create_creature(id: &str, name: &str, types: Vec<String>) -> CreatureState {
         item: None,
         ability: None,
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         ability_data: HashMap::new(),
         move_pp: HashMap::new(),
         attack: 50,
@@ -142,20 +148,8 @@ fn build_state(requirements: &Requirements) -> BattleState {
 
     BattleState {
         players: vec![
-            PlayerState {
-                id: "p1".to_string(),
-                name: "Player 1".to_string(),
-                team: vec![attacker, bench_attacker],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
-            PlayerState {
-                id: "p2".to_string(),
-                name: "Player 2".to_string(),
-                team: vec![target, bench_target],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
+            PlayerState::new("p1", "Player 1", vec![attacker, bench_attacker]),
+            PlayerState::new("p2", "Player 2", vec![target, bench_target]),
         ],
         turn: 1,
         field: FieldState {
@@ -170,8 +164,10 @@ fn build_state(requirements: &Requirements) -> BattleState {
                 })
                 .collect(),
             sides: HashMap::new(),
+            last_move: None,
         },
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -423,6 +419,7 @@ fn sampled_move_effects_match_expected_events() {
             rng: &mut rng,
             turn: state.turn,
             type_chart: &type_chart,
+            rules: &BattleRules::default(),
             bypass_protect: false,
             ignore_immunity: false,
             bypass_substitute: false,
@@ -0,0 +1,119 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::state::{BattleState, MoveOutcome};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn outcomes(state: &BattleState) -> Vec<(String, MoveOutcome)> {
+    let turn = state.history.as_ref().unwrap().turns.last().unwrap();
+    turn.outcomes
+        .iter()
+        .map(|record| (record.move_id.clone(), record.outcome.clone()))
+        .collect()
+}
+
+#[test]
+fn type_immune_hit_is_no_effect() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let ghost = CreatureBuilder::new("p2-mon", "Ghost")
+        .types(&["ghost"])
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![ghost])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(
+        outcomes(&next),
+        vec![("tackle".to_string(), MoveOutcome::NoEffect), ("harden".to_string(), MoveOutcome::Hit)]
+    );
+    assert_eq!(next.players[1].team[0].hp, next.players[1].team[0].max_hp);
+    assert!(next.log.iter().any(|line| line == "Ghostには 効果が ないようだ……"));
+}
+
+#[test]
+fn failed_accuracy_roll_is_missed() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["zap_cannon"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target").moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "zap_cannon", "p2"), move_action("p2", "harden", "p2")];
+    let mut rng = || 0.99;
+    let next = engine.step_battle(&state, &actions, &mut rng, BattleOptions::default());
+
+    assert_eq!(outcomes(&next)[0], ("zap_cannon".to_string(), MoveOutcome::Missed));
+    assert_eq!(next.players[1].team[0].hp, next.players[1].team[0].max_hp);
+}
+
+#[test]
+fn redundant_status_move_fails() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["thunder_wave"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .with_status(status("paralysis", None))
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "thunder_wave", "p2"), move_action("p2", "harden", "p2")];
+    let mut rng = || 0.0;
+    let next = engine.step_battle(&state, &actions, &mut rng, BattleOptions::default());
+
+    assert_eq!(
+        outcomes(&next)[0],
+        ("thunder_wave".to_string(), MoveOutcome::Failed("already_has_status".to_string()))
+    );
+}
+
+#[test]
+fn protected_target_is_blocked() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["protect"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "protect", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(
+        outcomes(&next),
+        vec![("protect".to_string(), MoveOutcome::Hit), ("tackle".to_string(), MoveOutcome::Blocked)]
+    );
+    assert_eq!(next.players[1].team[0].hp, next.players[1].team[0].max_hp);
+}
+
+#[test]
+fn status_that_stops_the_move_is_prevented() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(50, 50, 50, 50, 80)
+        .with_status(status("flinch", Some(1)))
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target").moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(outcomes(&next)[0], ("tackle".to_string(), MoveOutcome::Prevented));
+    assert_eq!(next.players[1].team[0].hp, next.players[1].team[0].max_hp);
+}
@@ -0,0 +1,136 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use engine_rust::data::moves::{MoveCategory, MoveData};
+use serde_json::json;
+use std::collections::BTreeSet;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn flurry(tags: &[&str]) -> MoveData {
+    MoveData {
+        id: "flurry".to_string(),
+        name: Some("Flurry".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Physical),
+        pp: Some(10),
+        power: Some(10),
+        accuracy: Some(0.7),
+        priority: Some(0),
+        description: None,
+        steps: vec![serde_json::from_value(json!({
+            "type": "repeat",
+            "times": { "min": 3, "max": 5 },
+            "steps": [{ "type": "damage", "power": 10, "accuracy": 0.7 }]
+        }))
+        .unwrap()],
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        crit_rate: None,
+    }
+}
+
+fn flurry_state(ability: &str) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .ability(ability)
+        .moves(&["flurry"])
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(500, 500)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+/// 1ターンで当たった回数。外れたら 0
+fn landed_hits(engine: &BattleEngine, ability: &str, seed: u64) -> usize {
+    let actions = vec![move_action("p1", "flurry", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(engine, &flurry_state(ability), &actions, seed);
+    let summary = next.log.iter().find_map(|line| line.strip_suffix("回 あたった！")?.parse().ok());
+    match summary {
+        Some(hits) => hits,
+        None if next.players[1].team[0].hp < 500 => 1,
+        None => 0,
+    }
+}
+
+#[test]
+fn multiaccuracy_hits_roll_independently() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(flurry(&["multiaccuracy"]));
+    let counts: BTreeSet<usize> = (0..40).map(|seed| landed_hits(&engine, "run_away", seed)).collect();
+    // 途中で外れて 1〜2 回で終わることもある
+    assert!(counts.iter().any(|&hits| (1..3).contains(&hits)), "{counts:?}");
+    assert!(counts.len() >= 3, "{counts:?}");
+}
+
+#[test]
+fn single_accuracy_moves_land_every_hit_once_connected() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(flurry(&[]));
+    for seed in 0..40 {
+        let hits = landed_hits(&engine, "run_away", seed);
+        assert!(hits == 0 || (3..=5).contains(&hits), "seed {seed}: {hits}");
+    }
+}
+
+#[test]
+fn skill_link_still_lands_max_hits() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(flurry(&["multiaccuracy"]));
+    for seed in 0..40 {
+        let hits = landed_hits(&engine, "skill_link", seed);
+        assert!(hits == 0 || hits == 5, "seed {seed}: {hits}");
+    }
+}
+
+#[test]
+fn follow_up_hits_ignore_target_evasion() {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(flurry(&["multiaccuracy"]));
+    let mut state = flurry_state("skill_link");
+    state.players[1].team[0].stages.evasion = 6;
+    let actions = vec![move_action("p1", "flurry", "p2"), move_action("p2", "harden", "p2")];
+    for seed in 0..40 {
+        let next = run_turn_with_seed(&engine, &state, &actions, seed);
+        let connected = next.players[1].team[0].hp < 500;
+        assert!(!connected || next.log.iter().any(|line| line == "5回 あたった！"), "seed {seed}");
+    }
+}
+
+fn population_bomb_hits(item: Option<&str>, seed: u64) -> usize {
+    let mut user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["population_bomb"])
+        .stats(50, 50, 50, 50, 100);
+    if let Some(item) = item {
+        user = user.item(item);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(2000, 2000)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user.build()]), player("p2", "P2", vec![target])]);
+    let actions = vec![move_action("p1", "population_bomb", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&BattleEngine::default(), &state, &actions, seed);
+    let summary = next.log.iter().find_map(|line| line.strip_suffix("回 あたった！")?.parse().ok());
+    match summary {
+        Some(hits) => hits,
+        None if next.players[1].team[0].hp < 2000 => 1,
+        None => 0,
+    }
+}
+
+#[test]
+fn loaded_dice_guarantees_four_to_ten_population_bomb_hits() {
+    for seed in 0..40 {
+        let hits = population_bomb_hits(Some("loaded_dice"), seed);
+        assert!(hits == 0 || (4..=10).contains(&hits), "seed {seed}: {hits}");
+    }
+}
+
+#[test]
+fn population_bomb_stops_at_the_first_miss_without_loaded_dice() {
+    let counts: BTreeSet<usize> = (0..40).map(|seed| population_bomb_hits(None, seed)).collect();
+    assert!(counts.iter().any(|&hits| (1..4).contains(&hits)), "{counts:?}");
+    assert!(counts.iter().all(|&hits| hits <= 10), "{counts:?}");
+}
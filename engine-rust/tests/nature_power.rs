@@ -0,0 +1,56 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, FieldEffect};
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn nature_state(terrain: Option<&str>) -> BattleState {
+    let mut state = battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Caller")
+                .moves(&["nature_power"])
+                .stats(50, 50, 100, 50, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target")
+                .moves(&["splash"])
+                .types(&["water"])
+                .hp(500, 500)
+                .build()],
+        ),
+    ]);
+    if let Some(terrain) = terrain {
+        state.field.global.push(FieldEffect {
+            id: terrain.to_string(),
+            remaining_turns: Some(5),
+            data: HashMap::new(),
+        });
+    }
+    state
+}
+
+fn use_nature_power(state: &BattleState) -> BattleState {
+    let actions = vec![move_action("p1", "nature_power", "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&BattleEngine::default(), state, &actions, 1)
+}
+
+#[test]
+fn nature_power_becomes_thunderbolt_on_electric_terrain() {
+    let next = use_nature_power(&nature_state(Some("electric_terrain")));
+    assert!(next.log.iter().any(|line| line == "Callerの １０まんボルト！"));
+    assert!(next.players[1].team[0].hp < 500);
+    // 呼び出した技の PP は減らない
+    assert!(!next.players[0].team[0].move_pp.contains_key("thunderbolt"));
+}
+
+#[test]
+fn nature_power_falls_back_to_earthquake_without_terrain() {
+    let next = use_nature_power(&nature_state(None));
+    assert!(next.log.iter().any(|line| line == "Callerの じしん！"));
+}
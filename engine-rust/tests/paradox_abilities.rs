@@ -0,0 +1,92 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, FieldEffect};
+use std::collections::HashMap;
+use serde_json::Value;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn paradox_state(ability: &str, item: Option<&str>, field: Option<(&str, i32)>) -> BattleState {
+    let mut ancient = CreatureBuilder::new("p1-mon", "Ancient")
+        .types(&["normal"])
+        .ability(ability)
+        .moves(&["tackle", "harden"])
+        .stats(120, 60, 50, 60, 80);
+    if let Some(item) = item {
+        ancient = ancient.item(item);
+    }
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .types(&["normal"])
+        .moves(&["harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 30)
+        .build();
+    let mut state = battle_state(vec![player("p1", "P1", vec![ancient.build()]), player("p2", "P2", vec![foe])]);
+    if let Some((id, turns)) = field {
+        state.field.global.push(FieldEffect {
+            id: id.to_string(),
+            remaining_turns: Some(turns),
+            data: HashMap::new(),
+        });
+    }
+    state
+}
+
+fn tackle_damage(state: &BattleState) -> (BattleState, i32) {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, state, &actions, 1);
+    let dealt = state.players[1].team[0].hp - next.players[1].team[0].hp;
+    (next, dealt)
+}
+
+#[test]
+fn protosynthesis_boosts_attack_in_sun_and_ends_with_it() {
+    let (_, plain) = tackle_damage(&paradox_state("protosynthesis", None, None));
+    let (sunny, boosted) = tackle_damage(&paradox_state("protosynthesis", None, Some(("sun", 1))));
+
+    assert!(boosted > plain);
+    assert!(sunny.log.iter().any(|l| l.contains("Ancientの こだいかっせいで 攻撃が 高まった！")));
+
+    // 晴れは このターンで終わり、次のターンの始めに効果が切れる
+    assert!(!sunny.field.global.iter().any(|e| e.id == "sun"));
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+    let after = run_turn_with_seed(&engine, &sunny, &actions, 2);
+    assert!(!after.players[0].team[0].ability_data.contains_key("paradoxBoost"));
+    assert!(after.log.iter().any(|l| l.contains("Ancientの こだいかっせいの 効果が 切れた！")));
+}
+
+#[test]
+fn quark_drive_boosts_attack_in_electric_terrain() {
+    let (_, plain) = tackle_damage(&paradox_state("quark_drive", None, None));
+    let (_, boosted) = tackle_damage(&paradox_state("quark_drive", None, Some(("electric_terrain", 5))));
+
+    assert!(boosted > plain);
+}
+
+#[test]
+fn booster_energy_keeps_protosynthesis_active_without_sun() {
+    let (next, boosted) = tackle_damage(&paradox_state("protosynthesis", Some("booster_energy"), None));
+    let (_, plain) = tackle_damage(&paradox_state("protosynthesis", None, None));
+
+    assert!(boosted > plain);
+    let ancient = &next.players[0].team[0];
+    assert_eq!(ancient.item, None);
+    assert_eq!(ancient.ability_data.get("paradoxBoost").and_then(|v| v.as_str()), Some("atk"));
+}
+
+#[test]
+fn booster_energy_held_as_an_item_status_is_used() {
+    let mut state = paradox_state("protosynthesis", None, None);
+    let mut held = status("item", None);
+    held.data.insert("itemId".to_string(), Value::String("booster_energy".to_string()));
+    state.players[0].team[0].statuses.push(held);
+    let (next, boosted) = tackle_damage(&state);
+    let (_, plain) = tackle_damage(&paradox_state("protosynthesis", None, None));
+
+    assert!(boosted > plain);
+    let ancient = &next.players[0].team[0];
+    assert!(!ancient.statuses.iter().any(|s| s.id == "item"));
+    assert_eq!(ancient.ability_data.get("paradoxBoost").and_then(|v| v.as_str()), Some("atk"));
+}
@@ -0,0 +1,47 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::state::BattleState;
+use serde_json::Value;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn protect_state() -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Guard").moves(&["protect"]).hp(200, 200).build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Dummy").moves(&["harden"]).hp(200, 200).build()],
+        ),
+    ])
+}
+
+fn protect_count(state: &BattleState) -> Option<i64> {
+    state.players[0].team[0]
+        .volatile_data
+        .get("protectSuccessCount")
+        .and_then(Value::as_i64)
+}
+
+#[test]
+fn flinch_resets_the_protect_chain() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "protect", "p1"), move_action("p2", "harden", "p2")];
+    let mut state = run_turn_with_seed(&engine, &protect_state(), &actions, 1);
+    assert_eq!(protect_count(&state), Some(1));
+
+    state.players[0].team[0].statuses.push(status("flinch", Some(1)));
+    state = run_turn_with_seed(&engine, &state, &actions, 2);
+    assert!(state.log.iter().any(|line| line == "Guardは ひるんで 動けない！"));
+    assert_eq!(protect_count(&state), Some(0));
+
+    // 連続成功が途切れたので 1/3 なら外れる乱数でも 成功する
+    let mut rng = || 0.9;
+    let next = engine.step_battle(&state, &actions, &mut rng, BattleOptions::default());
+    assert!(!next.log.iter().any(|line| line == "Guardの まもりは 失敗した！"));
+    assert_eq!(protect_count(&next), Some(1));
+}
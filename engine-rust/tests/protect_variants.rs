@@ -0,0 +1,64 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn shield_state(shield: &str) -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Guard")
+                .moves(&[shield])
+                .hp(200, 200)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Attacker")
+                .moves(&["tackle", "swift"])
+                .hp(160, 160)
+                .stats(100, 100, 100, 100, 100)
+                .build()],
+        ),
+    ])
+}
+
+fn blocked(shield: &str, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", shield, "p1"), move_action("p2", move_id, "p1")];
+    run_turn_with_seed(&engine, &shield_state(shield), &actions, 1)
+}
+
+#[test]
+fn spiky_shield_hurts_contact_attackers() {
+    let next = blocked("spiky_shield", "tackle");
+    assert_eq!(next.players[0].team[0].hp, 200);
+    assert_eq!(next.players[1].team[0].hp, 140);
+    assert!(next.log.iter().any(|line| line == "Attackerは 傷ついた！"));
+}
+
+#[test]
+fn spiky_shield_leaves_non_contact_attackers_alone() {
+    let next = blocked("spiky_shield", "swift");
+    assert_eq!(next.players[0].team[0].hp, 200);
+    assert_eq!(next.players[1].team[0].hp, 160);
+}
+
+#[test]
+fn kings_shield_lowers_contact_attackers_attack() {
+    let next = blocked("kings_shield", "tackle");
+    assert_eq!(next.players[0].team[0].hp, 200);
+    assert_eq!(next.players[1].team[0].stages.atk, -1);
+    assert_eq!(blocked("kings_shield", "swift").players[1].team[0].stages.atk, 0);
+}
+
+#[test]
+fn plain_protect_has_no_reaction() {
+    let next = blocked("protect", "tackle");
+    assert_eq!(next.players[1].team[0].hp, 160);
+    assert_eq!(next.players[1].team[0].stages.atk, 0);
+}
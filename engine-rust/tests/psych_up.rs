@@ -0,0 +1,62 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn psych_state(user_ability: Option<&str>) -> BattleState {
+    let mut user = CreatureBuilder::new("p1-mon", "Copier")
+        .moves(&["psych_up"])
+        .stats(50, 50, 50, 50, 100);
+    if let Some(ability) = user_ability {
+        user = user.ability(ability);
+    }
+    let mut user = user.build();
+    user.stages.def = 1;
+    let mut target = CreatureBuilder::new("p2-mon", "Boosted")
+        .moves(&["splash"])
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    target.stages.atk = 2;
+    target.stages.spe = -1;
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn psych_up(state: &BattleState) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "psych_up", "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn psych_up_copies_the_targets_stage_spread_exactly() {
+    let next = psych_up(&psych_state(None));
+    let stages = &next.players[0].team[0].stages;
+    assert_eq!((stages.atk, stages.def, stages.spe), (2, 0, -1));
+    assert_eq!(next.players[1].team[0].stages.atk, 2);
+    assert!(next.log.iter().any(|line| line == "Copierは Boostedの 能力変化を コピーした！"));
+}
+
+#[test]
+fn psych_up_is_not_altered_by_simple_or_contrary() {
+    for ability in ["simple", "contrary"] {
+        let stages = psych_up(&psych_state(Some(ability))).players[0].team[0].stages.clone();
+        assert_eq!((stages.atk, stages.def, stages.spe), (2, 0, -1), "{ability}");
+    }
+}
+
+#[test]
+fn psych_up_lowering_a_stage_does_not_trigger_competitive() {
+    let stages = psych_up(&psych_state(Some("competitive"))).players[0].team[0].stages.clone();
+    assert_eq!((stages.atk, stages.def, stages.spa, stages.spe), (2, 0, 0, -1));
+}
+
+#[test]
+fn self_suggestion_copies_the_targets_stages() {
+    let mut state = psych_state(None);
+    state.players[0].team[0].moves = vec!["self_suggestion".to_string()];
+    let actions = vec![move_action("p1", "self_suggestion", "p2"), move_action("p2", "splash", "p2")];
+    let next = run_turn_with_seed(&BattleEngine::default(), &state, &actions, 1);
+    let stages = &next.players[0].team[0].stages;
+    assert_eq!((stages.atk, stages.def, stages.spe), (2, 0, -1));
+}
@@ -1,6 +1,6 @@
 use engine_rust::core::battle::{BattleEngine, BattleOptions};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages};
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -30,6 +30,11 @@ fn make_creature(id: &str, name: &str, moves: Vec<String>, speed: i32) -> Creatu
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -45,7 +50,7 @@ fn random_move_uses_self_moves_and_consumes_pp() {
         id: "metronome".to_string(),
         name: Some("Metronome".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(1),
         power: None,
         accuracy: None,
@@ -59,7 +64,7 @@ fn random_move_uses_self_moves_and_consumes_pp() {
         id: "tackle".to_string(),
         name: Some("Tackle".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(1),
         power: None,
         accuracy: None,
@@ -70,29 +75,27 @@ fn random_move_uses_self_moves_and_consumes_pp() {
         crit_rate: None,
     });
 
-    let p1 = PlayerState {
-        id: "p1".to_string(),
-        name: "P1".to_string(),
-        team: vec![make_creature("c1", "Alpha", vec!["tackle".to_string()], 100)],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
-    let p2 = PlayerState {
-        id: "p2".to_string(),
-        name: "P2".to_string(),
-        team: vec![make_creature("c2", "Beta", vec!["tackle".to_string()], 10)],
-        active_slot: 0,
-        last_fainted_ability: None,
-    };
+    let p1 = PlayerState::new(
+        "p1",
+        "P1",
+        vec![make_creature("c1", "Alpha", vec!["tackle".to_string()], 100)],
+    );
+    let p2 = PlayerState::new(
+        "p2",
+        "P2",
+        vec![make_creature("c2", "Beta", vec!["tackle".to_string()], 10)],
+    );
 
     let state = BattleState {
         players: vec![p1, p2],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     };
 
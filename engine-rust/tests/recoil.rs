@@ -0,0 +1,51 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn recoil_state(move_id: &str, ability: Option<&str>) -> BattleState {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Rammer")
+        .moves(&[move_id])
+        .hp(200, 200)
+        .stats(100, 100, 100, 100, 100);
+    if let Some(ability) = ability {
+        attacker = attacker.ability(ability);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(300, 300)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![attacker.build()]), player("p2", "P2", vec![target])])
+}
+
+fn run(state: &BattleState, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn double_edge_recoil_is_a_share_of_damage_dealt() {
+    let next = run(&recoil_state("double_edge", None), "double_edge");
+    let dealt = 300 - next.players[1].team[0].hp;
+    assert!(dealt > 0);
+    assert_eq!(200 - next.players[0].team[0].hp, (dealt as f64 * 0.33).floor() as i32);
+    assert!(next.log.iter().any(|line| line == "Rammerは 反動を 受けた！"));
+}
+
+#[test]
+fn steel_beam_recoil_is_half_of_max_hp() {
+    let next = run(&recoil_state("steel_beam", None), "steel_beam");
+    assert_eq!(next.players[0].team[0].hp, 100);
+}
+
+#[test]
+fn rock_head_and_magic_guard_take_no_recoil() {
+    for ability in ["rock_head", "magic_guard"] {
+        let next = run(&recoil_state("double_edge", Some(ability)), "double_edge");
+        assert!(next.players[1].team[0].hp < 300, "{ability}");
+        assert_eq!(next.players[0].team[0].hp, 200, "{ability}");
+    }
+}
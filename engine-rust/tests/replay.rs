@@ -0,0 +1,149 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::replay::{battle_timeline, replay_battle, rewind_one_turn};
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::{state_key, BattleHistory};
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+#[test]
+fn rewinding_after_three_turns_matches_state_after_two() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .moves(&["tackle"])
+        .build();
+    let initial = battle_state(vec![
+        player("p1", "P1", vec![attacker]),
+        player("p2", "P2", vec![defender]),
+    ]);
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+
+    let mut rng = SeededRng::new(5);
+    let mut rng_fn = || rng.next_f64();
+    let mut state = initial.clone();
+    let mut after_two = None;
+    for turn in 0..3 {
+        state = engine.step_battle(&state, &actions, &mut rng_fn, BattleOptions::default());
+        if turn == 1 {
+            after_two = Some(state.clone());
+        }
+    }
+    let after_two = after_two.unwrap();
+
+    let rewound = rewind_one_turn(&engine, &initial, state.history.as_ref().unwrap(), BattleOptions::default()).expect("has turns to rewind");
+    assert_eq!(state_key(&rewound), state_key(&after_two));
+    assert_eq!(rewound.log, after_two.log);
+    assert_eq!(rewound.history.as_ref().unwrap().turns.len(), 2);
+}
+
+#[test]
+fn replay_uses_the_battles_own_engine_rules() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker").moves(&["tackle"]).hp(300, 300).build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender").moves(&["tackle"]).hp(300, 300).build();
+    let initial = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender])]);
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![1.0]));
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+
+    let mut rng = SeededRng::new(3);
+    let mut rng_fn = || rng.next_f64();
+    let mut state = initial.clone();
+    let mut after_one = None;
+    for turn in 0..2 {
+        state = engine.step_battle(&state, &actions, &mut rng_fn, BattleOptions::default());
+        if turn == 0 {
+            after_one = Some(state.clone());
+        }
+    }
+    let history = state.history.as_ref().unwrap();
+
+    let replayed = replay_battle(&engine, &initial, history, BattleOptions::default());
+    assert_eq!(state_key(&replayed), state_key(&state));
+    let rewound = rewind_one_turn(&engine, &initial, history, BattleOptions::default()).unwrap();
+    assert_eq!(state_key(&rewound), state_key(after_one.as_ref().unwrap()));
+}
+
+#[test]
+fn rewinding_empty_history_returns_none() {
+    let lead = CreatureBuilder::new("p1-mon", "Lead").moves(&["harden"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    let initial = battle_state(vec![player("p1", "P1", vec![lead]), player("p2", "P2", vec![foe])]);
+    let history = BattleHistory { turns: Vec::new() };
+    assert!(rewind_one_turn(&BattleEngine::default(), &initial, &history, BattleOptions::default()).is_none());
+}
+
+#[test]
+fn timeline_final_hp_matches_the_recorded_battle() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .hp(200, 200)
+        .build();
+    let healer = CreatureBuilder::new("p2-mon", "Healer")
+        .moves(&["recover"])
+        .hp(120, 200)
+        .build();
+    let initial = battle_state(vec![
+        player("p1", "P1", vec![attacker]),
+        player("p2", "P2", vec![healer]),
+    ]);
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "recover", "p2")];
+
+    let mut rng = SeededRng::new(9);
+    let mut rng_fn = || rng.next_f64();
+    let mut state = initial.clone();
+    for _ in 0..3 {
+        state = engine.step_battle(&state, &actions, &mut rng_fn, BattleOptions::default());
+    }
+
+    let timeline = battle_timeline(&engine, &initial, state.history.as_ref().unwrap(), BattleOptions::default());
+    assert_eq!(timeline.len(), 3);
+    assert_eq!(timeline.iter().map(|t| t.turn).collect::<Vec<_>>(), vec![1, 2, 3]);
+    let last = timeline.last().unwrap();
+    for tally in &last.creatures {
+        let player = state.players.iter().find(|p| p.id == tally.player_id).unwrap();
+        assert_eq!(tally.hp_after, player.team[tally.slot].hp);
+    }
+    let healer_first = timeline[0].creatures.iter().find(|c| c.creature_id == "p2-mon").unwrap();
+    assert!(healer_first.damage_taken > 0);
+    assert!(healer_first.healed > 0);
+    assert_eq!(
+        healer_first.hp_after,
+        healer_first.hp_before - healer_first.damage_taken + healer_first.healed
+    );
+}
+
+#[test]
+fn timeline_matches_a_doubles_battle_on_a_custom_engine() {
+    let mon = |id: &str| CreatureBuilder::new(id, id).moves(&["tackle"]).hp(300, 300).build();
+    let initial = battle_state(vec![
+        player("p1", "P1", vec![mon("p1-a"), mon("p1-b")]),
+        player("p2", "P2", vec![mon("p2-a"), mon("p2-b")]),
+    ]);
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_crit_chances(vec![1.0]));
+    let options = BattleOptions { doubles: true, ..Default::default() };
+    let actions = vec![
+        move_action("p1", "tackle", "p2"),
+        move_action("p1:1", "tackle", "p2:1"),
+        move_action("p2", "tackle", "p1"),
+        move_action("p2:1", "tackle", "p1:1"),
+    ];
+
+    let mut rng = SeededRng::new(4);
+    let mut rng_fn = || rng.next_f64();
+    let mut state = initial.clone();
+    for _ in 0..2 {
+        state = engine.step_battle(&state, &actions, &mut rng_fn, options.clone());
+    }
+
+    let timeline = battle_timeline(&engine, &initial, state.history.as_ref().unwrap(), options);
+    let last = timeline.last().unwrap();
+    for tally in &last.creatures {
+        let player = state.players.iter().find(|p| p.id == tally.player_id).unwrap();
+        assert_eq!(tally.hp_after, player.team[tally.slot].hp, "{}", tally.creature_id);
+    }
+    let partner = last.creatures.iter().find(|c| c.creature_id == "p2-b").unwrap();
+    assert!(partner.hp_after < 300);
+}
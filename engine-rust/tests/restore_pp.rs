@@ -0,0 +1,93 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData};
+use serde_json::{json, Map};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn restore_move(amount: i32) -> MoveData {
+    let data: Map<String, serde_json::Value> = json!({ "moveId": "tackle", "amount": amount, "target": "self" })
+        .as_object()
+        .cloned()
+        .unwrap();
+    MoveData {
+        id: "restore".to_string(),
+        name: Some("Restore".to_string()),
+        move_type: Some("normal".to_string()),
+        category: Some(MoveCategory::Status),
+        pp: Some(10),
+        power: None,
+        accuracy: None,
+        priority: Some(0),
+        description: None,
+        steps: vec![Effect {
+            effect_type: "restore_pp".to_string(),
+            data,
+        }],
+        tags: Vec::new(),
+        crit_rate: None,
+    }
+}
+
+fn engine(amount: i32) -> BattleEngine {
+    let mut engine = BattleEngine::default();
+    engine.move_db.insert(restore_move(amount));
+    engine
+}
+
+fn pp_state(tackle_pp: Option<i32>) -> BattleState {
+    let mut user = CreatureBuilder::new("p1-mon", "User").moves(&["tackle", "restore"]).build();
+    if let Some(pp) = tackle_pp {
+        user.move_pp.insert("tackle".to_string(), pp);
+    }
+    battle_state(vec![
+        player("p1", "P1", vec![user]),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Target").moves(&["splash"]).hp(300, 300).build()],
+        ),
+    ])
+}
+
+fn use_move(engine: &BattleEngine, state: &BattleState, move_id: &str) -> BattleState {
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(engine, state, &actions, 1)
+}
+
+fn tackle_pp(state: &BattleState) -> Option<i32> {
+    state.players[0].team[0].move_pp.get("tackle").copied()
+}
+
+#[test]
+fn restored_pp_lets_a_depleted_move_be_used_again() {
+    let engine = engine(10);
+    let depleted = use_move(&engine, &pp_state(Some(0)), "tackle");
+    assert_eq!(depleted.players[1].team[0].hp, 300);
+    assert!(depleted.log.iter().any(|line| line == "P1の たいあたりは PPが 足りない！"));
+
+    let restored = use_move(&engine, &depleted, "restore");
+    assert_eq!(tackle_pp(&restored), Some(10));
+    assert!(restored.log.iter().any(|line| line == "Userは たいあたりの PPを 10回復した！"));
+
+    let next = use_move(&engine, &restored, "tackle");
+    assert!(next.players[1].team[0].hp < 300);
+    assert_eq!(tackle_pp(&next), Some(9));
+}
+
+#[test]
+fn restore_pp_is_clamped_to_max_pp() {
+    let engine = engine(10);
+    let next = use_move(&engine, &pp_state(Some(30)), "restore");
+    assert_eq!(tackle_pp(&next), Some(35));
+    assert!(next.log.iter().any(|line| line == "Userは たいあたりの PPを 5回復した！"));
+}
+
+#[test]
+fn restore_pp_fails_when_the_move_is_already_full() {
+    let engine = engine(10);
+    let next = use_move(&engine, &pp_state(None), "restore");
+    assert_eq!(tackle_pp(&next), None);
+    assert!(next.log.iter().any(|line| line == "Userの たいあたりの PPは 満タンだ！"));
+}
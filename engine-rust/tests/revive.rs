@@ -0,0 +1,40 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn blessing_state(bench_hp: i32) -> BattleState {
+    let user = CreatureBuilder::new("p1-lead", "Blesser").moves(&["revival_blessing"]).build();
+    let bench = CreatureBuilder::new("p1-bench", "Fallen")
+        .moves(&["harden"])
+        .hp(bench_hp, 120)
+        .with_status(status("burn", None))
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    battle_state(vec![player("p1", "P1", vec![user, bench]), player("p2", "P2", vec![foe])])
+}
+
+#[test]
+fn revival_blessing_revives_fainted_teammate_at_half_hp() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "revival_blessing", "p1"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &blessing_state(0), &actions, 1);
+
+    let revived = &next.players[0].team[1];
+    assert_eq!(revived.hp, 60);
+    assert!(revived.statuses.is_empty());
+    assert!(next.log.iter().any(|line| line == "Fallenは 元気を 取り戻した！"));
+}
+
+#[test]
+fn revival_blessing_fails_when_team_is_healthy() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "revival_blessing", "p1"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &blessing_state(80), &actions, 1);
+
+    assert_eq!(next.players[0].team[1].hp, 80);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+}
@@ -0,0 +1,72 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+#[test]
+fn rototiller_boosts_only_grass_type_actives() {
+    let user = CreatureBuilder::new("p1-mon", "Tiller")
+        .types(&["ground"])
+        .moves(&["rototiller"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let grass = CreatureBuilder::new("p2-mon", "Sprout")
+        .types(&["grass"])
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![grass])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "rototiller", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    let tiller = &next.players[0].team[0];
+    assert_eq!((tiller.stages.atk, tiller.stages.spa), (0, 0));
+    let sprout = &next.players[1].team[0];
+    assert_eq!((sprout.stages.atk, sprout.stages.spa), (1, 1));
+}
+
+#[test]
+fn rototiller_fails_without_grounded_grass_types() {
+    let user = CreatureBuilder::new("p1-mon", "Tiller")
+        .types(&["grass"])
+        .ability("levitate")
+        .moves(&["rototiller"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .types(&["normal"])
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![foe])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "rototiller", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(next.players[0].team[0].stages.atk, 0);
+    assert_eq!(next.players[1].team[0].stages.atk, 0);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+}
+
+#[test]
+fn flower_shield_raises_defense_of_every_grass_type() {
+    let user = CreatureBuilder::new("p1-mon", "Shield")
+        .types(&["grass", "fairy"])
+        .moves(&["flower_shield"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let grass = CreatureBuilder::new("p2-mon", "Sprout")
+        .types(&["grass"])
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![grass])]);
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "flower_shield", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(next.players[0].team[0].stages.def, 1);
+    // フラワーガード +1 とかたくなる +1
+    assert_eq!(next.players[1].team[0].stages.def, 2);
+}
@@ -0,0 +1,99 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::factory::{create_creature, CreateCreatureOptions};
+use engine_rust::core::state::{BattleState, CreatureState};
+use engine_rust::data::learnsets::LearnsetDatabase;
+use engine_rust::data::moves::MoveDatabase;
+use engine_rust::data::species::{SpeciesData, SpeciesDatabase};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn sky_drop_state(target: CreatureState) -> BattleState {
+    let lifter = CreatureBuilder::new("p1-mon", "Lifter")
+        .moves(&["sky_drop", "harden"])
+        .hp(200, 200)
+        .stats(80, 50, 50, 50, 100)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![lifter]), player("p2", "P2", vec![target])])
+}
+
+/// 図鑑データから作った相手。重さは種族のものがそのまま入る
+fn species_target(species: &SpeciesData) -> CreatureState {
+    let learnsets = LearnsetDatabase::load_default().expect("load learnsets");
+    let move_db = MoveDatabase::load_default().expect("load moves");
+    let mut target = create_creature(
+        species,
+        CreateCreatureOptions {
+            name: Some("Target".to_string()),
+            ..Default::default()
+        },
+        &learnsets,
+        &move_db,
+    )
+    .expect("create creature");
+    target.id = "p2-mon".to_string();
+    target.moves = vec!["tackle".to_string()];
+    target.hp = 200;
+    target.max_hp = 200;
+    target.speed = 50;
+    target
+}
+
+fn has_status(state: &BattleState, side: usize, status_id: &str) -> bool {
+    state.players[side].team[0].statuses.iter().any(|s| s.id == status_id)
+}
+
+#[test]
+fn sky_dropped_target_cannot_act_until_dropped() {
+    let engine = BattleEngine::default();
+    let species_db = SpeciesDatabase::load_default().expect("load species");
+    let lifted = run_turn_with_seed(
+        &engine,
+        &sky_drop_state(species_target(species_db.get("eiraku").expect("species exists"))),
+        &[move_action("p1", "sky_drop", "p2"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert!(has_status(&lifted, 1, "sky_dropped"));
+    assert!(lifted.log.iter().any(|line| line == "Targetは 上空で 身動きが とれない！"));
+    assert_eq!(lifted.players[0].team[0].hp, 200, "the lifted target's tackle must be skipped");
+    assert_eq!(lifted.players[1].team[0].hp, 200, "no damage on the lift turn");
+
+    // 2ターン目は別の技を選んでも落としにいく
+    let dropped = run_turn_with_seed(
+        &engine,
+        &lifted,
+        &[move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")],
+        2,
+    );
+    assert!(dropped.players[1].team[0].hp < 200, "damage lands on the drop turn");
+    assert!(!has_status(&dropped, 0, "sky_drop"));
+    assert!(!has_status(&dropped, 1, "sky_dropped"));
+    // 落とされた後は動ける
+    assert!(dropped.players[0].team[0].hp < 200);
+}
+
+#[test]
+fn sky_drop_fails_on_heavy_targets() {
+    let engine = BattleEngine::default();
+    let species_db = SpeciesDatabase::load_from_yaml_str(
+        r#"
+heavy:
+  id: heavy
+  name: おもたい
+  type: [normal]
+  baseStats: { hp: 100, atk: 80, def: 80, spa: 80, spd: 80, spe: 50 }
+  weight: 250.0
+"#,
+    )
+    .expect("load species");
+    let next = run_turn_with_seed(
+        &engine,
+        &sky_drop_state(species_target(species_db.get("heavy").expect("species exists"))),
+        &[move_action("p1", "sky_drop", "p2"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+    assert!(!has_status(&next, 0, "sky_drop"));
+    assert!(!has_status(&next, 1, "sky_dropped"));
+    assert!(next.players[0].team[0].hp < 200, "the target acts normally");
+}
@@ -0,0 +1,110 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::BattleState;
+use serde_json::Value;
+use support::harness::{
+    battle_state, move_action, player, run_turn_with_seed, status, switch_action, CreatureBuilder,
+};
+
+fn clause_state(bench_asleep: bool) -> BattleState {
+    let mon = |id: &str, name: &str| {
+        CreatureBuilder::new(id, name)
+            .moves(&["splash"])
+            .stats(50, 50, 50, 50, 50)
+            .build()
+    };
+    let mut bench = mon("p2-b", "Bench");
+    if bench_asleep {
+        // 相手の p1 に眠らされた
+        let mut sleep = status("sleep", Some(2));
+        sleep.data.insert("sourceId".to_string(), Value::String("p1".to_string()));
+        bench.statuses.push(sleep);
+    }
+    let user = CreatureBuilder::new("p1-mon", "Sleeper")
+        .moves(&["sleep_powder"])
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![mon("p2-a", "Lead"), bench])])
+}
+
+/// sleep_powder が当たって Lead が眠ったシード数 (0..20)
+fn slept_seeds(state: &BattleState, sleep_clause: bool) -> usize {
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_sleep_clause(sleep_clause));
+    let actions = vec![move_action("p1", "sleep_powder", "p2"), move_action("p2", "splash", "p2")];
+    (0..20)
+        .filter(|&seed| {
+            run_turn_with_seed(&engine, state, &actions, seed).players[1].team[0]
+                .statuses
+                .iter()
+                .any(|s| s.id == "sleep")
+        })
+        .count()
+}
+
+#[test]
+fn sleep_clause_blocks_a_second_sleeper_on_the_same_team() {
+    assert_eq!(slept_seeds(&clause_state(true), true), 0);
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_sleep_clause(true));
+    let actions = vec![move_action("p1", "sleep_powder", "p2"), move_action("p2", "splash", "p2")];
+    let next = run_turn_with_seed(&engine, &clause_state(true), &actions, 1);
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+}
+
+#[test]
+fn sleep_clause_still_allows_the_first_sleeper() {
+    assert!(slept_seeds(&clause_state(false), true) > 0);
+}
+
+#[test]
+fn sleep_from_a_foe_remembers_who_caused_it() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "sleep_powder", "p2"), move_action("p2", "splash", "p2")];
+    let sleep = (0..20)
+        .filter_map(|seed| {
+            let next = run_turn_with_seed(&engine, &clause_state(false), &actions, seed);
+            next.players[1].team[0].statuses.iter().find(|s| s.id == "sleep").cloned()
+        })
+        .next()
+        .expect("sleep_powder lands on some seed");
+    assert_eq!(sleep.data.get("sourceId"), Some(&Value::String("p1".to_string())));
+}
+
+#[test]
+fn sleep_clause_is_off_by_default() {
+    assert!(!BattleRules::default().sleep_clause);
+    assert!(slept_seeds(&clause_state(true), false) > 0);
+}
+
+#[test]
+fn sleep_clause_ignores_a_teammate_that_used_rest() {
+    let engine = BattleEngine::default().with_rules(BattleRules::default().with_sleep_clause(true));
+    let resting = CreatureBuilder::new("p2-a", "Rester")
+        .moves(&["rest"])
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let bench = CreatureBuilder::new("p2-b", "Bench")
+        .moves(&["splash"])
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let user = CreatureBuilder::new("p1-mon", "Sleeper")
+        .moves(&["sleep_powder", "splash"])
+        .stats(50, 50, 50, 50, 10)
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![resting, bench])]);
+
+    let rest = vec![move_action("p1", "splash", "p1"), move_action("p2", "rest", "p2")];
+    let rested = run_turn_with_seed(&engine, &state, &rest, 1);
+    assert!(rested.players[1].team[0].statuses.iter().any(|s| s.id == "sleep"));
+
+    // 自分で眠った手持ちしかいないので、交代先は眠らせられる
+    let powder = vec![move_action("p1", "sleep_powder", "p2"), switch_action("p2", 1)];
+    let slept = (0..20).any(|seed| {
+        run_turn_with_seed(&engine, &rested, &powder, seed).players[1].team[1]
+            .statuses
+            .iter()
+            .any(|s| s.id == "sleep")
+    });
+    assert!(slept);
+}
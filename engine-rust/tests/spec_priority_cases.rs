@@ -5,7 +5,8 @@ use engine_rust::core::effects::{apply_effects, EffectContext};
 use engine_rust::core::events::BattleEvent;
 use engine_rust::core::state::{BattleState, FieldEffect};
 use engine_rust::data::learnsets::LearnsetDatabase;
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::{HashMap, HashSet};
@@ -170,7 +171,7 @@ fn wait_move() -> MoveData {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -182,12 +183,12 @@ fn wait_move() -> MoveData {
     }
 }
 
-fn damage_move(id: &str, category: &str, power: i32, crit_rate: Option<i32>) -> MoveData {
+fn damage_move(id: &str, category: MoveCategory, power: i32, crit_rate: Option<i32>) -> MoveData {
     MoveData {
         id: id.to_string(),
         name: Some(id.to_string()),
         move_type: Some("normal".to_string()),
-        category: Some(category.to_string()),
+        category: Some(category),
         pp: Some(10),
         power: Some(power),
         accuracy: Some(1.0),
@@ -204,7 +205,7 @@ fn field_status_move(id: &str, status_id: &str) -> MoveData {
         id: id.to_string(),
         name: Some(id.to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -275,7 +276,7 @@ where
 }
 
 fn is_allowed_target_literal(value: &str) -> bool {
-    if matches!(value, "self" | "target" | "all") {
+    if matches!(value, "self" | "target" | "all" | "ally" | "all_adjacent" | "all_adjacent_foes") {
         return true;
     }
     if let Some(rest) = value.strip_prefix('p') {
@@ -303,6 +304,7 @@ fn is_supported_status_id(status_id: &str) -> bool {
             | "taunt"
             | "leech_seed"
             | "curse"
+            | "grudge"
             | "yawn"
             | "bind"
             | "wish"
@@ -351,7 +353,7 @@ fn first_damage_amount(events: &[BattleEvent]) -> i32 {
 #[test]
 fn p0_crit_ignores_positive_def_stage() {
     let engine = make_engine(vec![
-        damage_move("always_crit", "physical", 90, Some(3)),
+        damage_move("always_crit", MoveCategory::Physical, 90, Some(3)),
         wait_move(),
     ]);
 
@@ -395,7 +397,7 @@ fn p0_crit_ignores_positive_def_stage() {
 #[test]
 fn p0_spec_crit_ignores_negative_attack_stage() {
     let engine = make_engine(vec![
-        damage_move("always_crit", "physical", 90, Some(3)),
+        damage_move("always_crit", MoveCategory::Physical, 90, Some(3)),
         wait_move(),
     ]);
 
@@ -438,8 +440,8 @@ fn p0_spec_crit_ignores_negative_attack_stage() {
 
 #[test]
 fn p0_spec_crit_bypasses_walls_while_non_crit_does_not() {
-    let non_crit_engine = make_engine(vec![damage_move("strike", "physical", 80, None), wait_move()]);
-    let crit_engine = make_engine(vec![damage_move("always_crit", "physical", 80, Some(3)), wait_move()]);
+    let non_crit_engine = make_engine(vec![damage_move("strike", MoveCategory::Physical, 80, None), wait_move()]);
+    let crit_engine = make_engine(vec![damage_move("always_crit", MoveCategory::Physical, 80, Some(3)), wait_move()]);
 
     let base_state = battle_state(vec![
         player(
@@ -539,7 +541,7 @@ fn p0_spec_damage_roll_matches_golden_fixture() {
         ),
     ]);
 
-    let move_data = damage_move("raw_damage", "physical", 100, None);
+    let move_data = damage_move("raw_damage", MoveCategory::Physical, 100, None);
     let damage_step = effect("damage", json!({ "power": 100, "accuracy": 1.0 }));
     let type_chart = TypeChart::new();
 
@@ -554,6 +556,7 @@ fn p0_spec_damage_roll_matches_golden_fixture() {
         rng: &mut low_roll_rng,
         turn: 1,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -574,6 +577,7 @@ fn p0_spec_damage_roll_matches_golden_fixture() {
         rng: &mut high_roll_rng,
         turn: 1,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -594,7 +598,7 @@ fn p0_spec_damage_roll_matches_golden_fixture() {
 
 #[test]
 fn p0_spec_trick_room_reverses_action_order() {
-    let engine = make_engine(vec![damage_move("one_shot", "physical", 400, None)]);
+    let engine = make_engine(vec![damage_move("one_shot", MoveCategory::Physical, 400, None)]);
 
     let mut state = battle_state(vec![
         player(
@@ -632,7 +636,7 @@ fn p0_spec_trick_room_reverses_action_order() {
 
 #[test]
 fn p0_spec_reflect_reduces_physical_damage() {
-    let engine = make_engine(vec![damage_move("strike", "physical", 80, None), wait_move()]);
+    let engine = make_engine(vec![damage_move("strike", MoveCategory::Physical, 80, None), wait_move()]);
 
     let base_state = battle_state(vec![
         player(
@@ -676,7 +680,7 @@ fn p0_spec_reflect_reduces_physical_damage() {
 
 #[test]
 fn p0_spec_light_screen_reduces_special_damage() {
-    let engine = make_engine(vec![damage_move("beam", "special", 80, None), wait_move()]);
+    let engine = make_engine(vec![damage_move("beam", MoveCategory::Special, 80, None), wait_move()]);
 
     let base_state = battle_state(vec![
         player(
@@ -719,7 +723,7 @@ fn p0_spec_light_screen_reduces_special_damage() {
 
 #[test]
 fn p0_spec_tailwind_changes_action_order_by_speed() {
-    let engine = make_engine(vec![damage_move("one_shot", "physical", 400, None)]);
+    let engine = make_engine(vec![damage_move("one_shot", MoveCategory::Physical, 400, None)]);
 
     let mut state = battle_state(vec![
         player(
@@ -869,6 +873,7 @@ fn p0_spec_protect_chain_probability_is_one_third_then_one_ninth() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -965,6 +970,7 @@ fn p0_manual_effects_must_not_be_silent_noop() {
                 rng: &mut rng,
                 turn: 1,
                 type_chart: &type_chart,
+                rules: &BattleRules::default(),
                 bypass_protect: false,
                 ignore_immunity: false,
                 bypass_substitute: false,
@@ -1006,7 +1012,7 @@ fn p0_spec_simultaneous_faint_resolution_rule() {
                 .build()],
         ),
     ]);
-    let winner = determine_winner(&state);
+    let winner = determine_winner(&state, &BattleRules::default());
     assert_eq!(
         winner.as_deref(),
         Some("p2"),
@@ -1019,7 +1025,7 @@ fn p0_spec_simultaneous_faint_resolution_rule() {
         remaining_turns: Some(5),
         data: HashMap::new(),
     });
-    let trick_room_winner = determine_winner(&trick_room_state);
+    let trick_room_winner = determine_winner(&trick_room_state, &BattleRules::default());
     assert_eq!(
         trick_room_winner.as_deref(),
         Some("p1"),
@@ -1027,6 +1033,32 @@ fn p0_spec_simultaneous_faint_resolution_rule() {
     );
 }
 
+#[test]
+fn simultaneous_faint_uses_the_given_rules_for_speed() {
+    let state = battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("c1", "Paralyzed")
+                .hp(0, 100)
+                .stats(50, 50, 50, 50, 120)
+                .with_status(status("paralysis", None))
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("c2", "Slow")
+                .hp(0, 100)
+                .stats(50, 50, 50, 50, 40)
+                .build()],
+        ),
+    ]);
+    // 第7世代以降のまひは半分 (60) で まだ速く、第6世代は 1/4 (30) で遅い
+    assert_eq!(determine_winner(&state, &BattleRules::gen(9)).as_deref(), Some("p2"));
+    assert_eq!(determine_winner(&state, &BattleRules::gen(6)).as_deref(), Some("p1"));
+}
+
 #[test]
 fn p0_spec_timeout_resolution_rule() {
     let alive_count_state = battle_state(vec![
@@ -1152,7 +1184,7 @@ fn p1_spec_ability_status_field_interaction_matrix() {
         id: "poison_touch".to_string(),
         name: Some("Poison Touch".to_string()),
         move_type: Some("poison".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: Some(1.0),
@@ -1172,7 +1204,7 @@ fn p1_spec_ability_status_field_interaction_matrix() {
     let engine = make_engine(vec![
         wait_move(),
         poison_move,
-        damage_move("strike", "physical", 80, None),
+        damage_move("strike", MoveCategory::Physical, 80, None),
     ]);
 
     let mut immunity_state = battle_state(vec![
@@ -1479,7 +1511,7 @@ fn p2_spec_double_battle_model_smoke() {
         id: "chip".to_string(),
         name: Some("Chip".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: None,
         accuracy: Some(1.0),
@@ -0,0 +1,64 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn swap_state(user_ability: Option<&str>) -> BattleState {
+    let mut user = CreatureBuilder::new("p1-mon", "Swapper")
+        .moves(&["power_swap", "guard_swap", "heart_swap"])
+        .stats(50, 50, 50, 50, 100);
+    if let Some(ability) = user_ability {
+        user = user.ability(ability);
+    }
+    let mut user = user.build();
+    user.stages.atk = -1;
+    user.stages.def = 2;
+    let mut target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["splash"])
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    target.stages.atk = 2;
+    target.stages.spa = 1;
+    target.stages.spd = -1;
+    target.stages.spe = 3;
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn swap(state: &BattleState, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn power_swap_exchanges_only_offensive_stages() {
+    let next = swap(&swap_state(None), "power_swap");
+    let (user, target) = (&next.players[0].team[0].stages, &next.players[1].team[0].stages);
+    assert_eq!((user.atk, user.spa, user.def, user.spd, user.spe), (2, 1, 2, 0, 0));
+    assert_eq!((target.atk, target.spa, target.def, target.spd, target.spe), (-1, 0, 0, -1, 3));
+    assert!(next.log.iter().any(|line| line == "Swapperは Targetと 能力変化を 入れ替えた！"));
+}
+
+#[test]
+fn guard_swap_exchanges_only_defensive_stages() {
+    let next = swap(&swap_state(None), "guard_swap");
+    let (user, target) = (&next.players[0].team[0].stages, &next.players[1].team[0].stages);
+    assert_eq!((user.atk, user.def, user.spd), (-1, 0, -1));
+    assert_eq!((target.atk, target.def, target.spd), (2, 2, 0));
+}
+
+#[test]
+fn heart_swap_exchanges_every_stage_ignoring_contrary() {
+    let next = swap(&swap_state(Some("contrary")), "heart_swap");
+    let (user, target) = (&next.players[0].team[0].stages, &next.players[1].team[0].stages);
+    assert_eq!((user.atk, user.def, user.spa, user.spd, user.spe), (2, 0, 1, -1, 3));
+    assert_eq!((target.atk, target.def, target.spa, target.spd, target.spe), (-1, 2, 0, 0, 0));
+}
+
+#[test]
+fn swapping_away_a_boost_does_not_trigger_competitive() {
+    let next = swap(&swap_state(Some("competitive")), "guard_swap");
+    let user = &next.players[0].team[0].stages;
+    assert_eq!((user.def, user.spa), (0, 0));
+}
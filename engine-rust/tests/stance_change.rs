@@ -0,0 +1,51 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn stance_state(ability: &str) -> BattleState {
+    let blade = CreatureBuilder::new("p1-mon", "Blade")
+        .types(&["normal"])
+        .ability(ability)
+        .moves(&["tackle", "kings_shield"])
+        .stats(50, 140, 50, 140, 80)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .types(&["normal"])
+        .moves(&["harden"])
+        .stats(50, 50, 50, 50, 30)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![blade]), player("p2", "P2", vec![foe])])
+}
+
+#[test]
+fn attacking_move_switches_to_blade_forme() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+
+    let control = run_turn_with_seed(&engine, &stance_state("run_away"), &actions, 1);
+    let bladed = run_turn_with_seed(&engine, &stance_state("stance_change"), &actions, 1);
+
+    let user = &bladed.players[0].team[0];
+    assert_eq!((user.attack, user.defense), (140, 50));
+    assert_eq!((user.sp_attack, user.sp_defense), (140, 50));
+    assert!(bladed.log.iter().any(|l| l.contains("Bladeは ブレードフォルムに チェンジ！")));
+    // 入れ替わった攻撃で殴るので ダメージが大きい
+    assert!(bladed.players[1].team[0].hp < control.players[1].team[0].hp);
+}
+
+#[test]
+fn kings_shield_returns_to_shield_forme() {
+    let engine = BattleEngine::default();
+    let attack = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+    let bladed = run_turn_with_seed(&engine, &stance_state("stance_change"), &attack, 1);
+
+    let shield = vec![move_action("p1", "kings_shield", "p1"), move_action("p2", "harden", "p2")];
+    let shielded = run_turn_with_seed(&engine, &bladed, &shield, 2);
+
+    let user = &shielded.players[0].team[0];
+    assert_eq!((user.attack, user.defense), (50, 140));
+    assert_eq!((user.sp_attack, user.sp_defense), (50, 140));
+    assert!(shielded.log.iter().any(|l| l.contains("Bladeは シールドフォルムに チェンジ！")));
+}
@@ -0,0 +1,65 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::rng::SplitMix64Rng;
+use engine_rust::core::state::BattleState;
+use serde_json::{json, Value};
+use support::harness::{battle_state, move_action, player, status, CreatureBuilder};
+
+fn mid_battle() -> BattleState {
+    let engine = BattleEngine::default();
+    let state = battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Saver")
+                .moves(&["tackle"])
+                .hp(200, 200)
+                .with_status(status("burn", None))
+                .build()],
+        ),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "Loader").moves(&["tackle"]).build()]),
+    ]);
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+    let mut rng = SplitMix64Rng::new(7);
+    let mut rng_fn = rng.as_fn();
+    let options = BattleOptions {
+        record_history: true,
+        ..Default::default()
+    };
+    let mut next = engine.step_battle(&state, &actions, &mut rng_fn, options);
+    let mon = &mut next.players[0].team[0];
+    mon.volatile_data.insert("protectSuccessCount".to_string(), json!(2));
+    mon.ability_data.insert("originalAbility".to_string(), json!("intimidate"));
+    next
+}
+
+#[test]
+fn battle_state_round_trips_through_json() {
+    let state = mid_battle();
+    let json = state.to_json().unwrap();
+    let restored = BattleState::from_json(&json).unwrap();
+
+    let mon = &restored.players[0].team[0];
+    assert_eq!(mon.volatile_data.get("protectSuccessCount"), Some(&json!(2)));
+    assert_eq!(mon.ability_data.get("originalAbility"), Some(&json!("intimidate")));
+    assert_eq!(mon.hp, state.players[0].team[0].hp);
+    assert_eq!(restored.history.as_ref().map(|h| h.turns.len()), Some(1));
+    assert_eq!(restored.log, state.log);
+    let original: Value = serde_json::from_str(&json).unwrap();
+    let again: Value = serde_json::from_str(&restored.to_json().unwrap()).unwrap();
+    assert_eq!(original, again);
+}
+
+#[test]
+fn battle_state_json_uses_wire_camel_case() {
+    let value: Value = serde_json::from_str(&mid_battle().to_json().unwrap()).unwrap();
+    let mon = &value["players"][0]["team"][0];
+    assert_eq!(mon["maxHp"], json!(200));
+    assert!(mon["volatileData"].is_object());
+    assert_eq!(mon["statuses"][0]["remainingTurns"], Value::Null);
+    assert_eq!(value["players"][0]["activeSlot"], json!(0));
+    let action = &value["history"]["turns"][0]["actions"][0];
+    assert_eq!(action["type"], json!("move"));
+    assert!(action["playerId"].is_string());
+}
@@ -0,0 +1,165 @@
+mod support;
+
+use engine_rust::core::abilities::{run_ability_value_hook, AbilityValueContext};
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::damage::calc_damage_range;
+use engine_rust::core::events::has_major_status;
+use engine_rust::core::state::BattleState;
+use engine_rust::data::moves::MoveCategory;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn marvel_state(defender_status: Option<&str>) -> BattleState {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle"])
+        .stats(100, 50, 50, 50, 80)
+        .build();
+    let mut defender = CreatureBuilder::new("p2-mon", "Scales")
+        .ability("marvel_scale")
+        .moves(&["harden"])
+        .hp(320, 320)
+        .stats(50, 50, 50, 50, 30);
+    if let Some(id) = defender_status {
+        defender = defender.with_status(status(id, None));
+    }
+    battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![defender.build()])])
+}
+
+fn physical_ctx() -> AbilityValueContext<'static> {
+    AbilityValueContext {
+        move_data: None,
+        category: Some(MoveCategory::Physical),
+        target: None,
+        weather: None,
+        turn: 1,
+        stages: None,
+    }
+}
+
+#[test]
+fn marvel_scale_raises_defense_when_burned() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+
+    let healthy = marvel_state(None);
+    let healthy_next = run_turn_with_seed(&engine, &healthy, &actions, 1);
+    let healthy_taken = healthy.players[1].team[0].hp - healthy_next.players[1].team[0].hp;
+
+    let burned = marvel_state(Some("burn"));
+    let burned_next = run_turn_with_seed(&engine, &burned, &actions, 1);
+    // やけどの定数ダメージ (最大HPの1/16) を除いた 技のダメージで比べる
+    let burned_taken = burned.players[1].team[0].hp - burned_next.players[1].team[0].hp - 320 / 16;
+
+    assert!(burned_taken < healthy_taken, "burned {} vs healthy {}", burned_taken, healthy_taken);
+}
+
+#[test]
+fn volatile_status_does_not_trigger_guts_or_marvel_scale() {
+    let for_ability = |ability: &str, status_id: &str| {
+        let creature = CreatureBuilder::new("p1-mon", "Mon")
+            .ability(ability)
+            .moves(&["tackle"])
+            .with_status(status(status_id, None))
+            .build();
+        let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["tackle"]).build();
+        battle_state(vec![player("p1", "P1", vec![creature]), player("p2", "P2", vec![foe])])
+    };
+
+    let confused_guts = for_ability("guts", "confusion");
+    assert_eq!(run_ability_value_hook(&confused_guts, "p1", "onModifyPower", 100.0, physical_ctx()), 100.0);
+    let confused_scales = for_ability("marvel_scale", "confusion");
+    assert_eq!(run_ability_value_hook(&confused_scales, "p1", "onModifyDefense", 100.0, physical_ctx()), 100.0);
+
+    let burned_guts = for_ability("guts", "burn");
+    assert_eq!(run_ability_value_hook(&burned_guts, "p1", "onModifyPower", 100.0, physical_ctx()), 150.0);
+}
+
+#[test]
+fn has_major_status_ignores_volatiles() {
+    let with = |ids: &[&str]| {
+        let mut builder = CreatureBuilder::new("p1-mon", "Mon");
+        for id in ids {
+            builder = builder.with_status(status(id, None));
+        }
+        builder.build()
+    };
+
+    assert!(!has_major_status(&with(&[])));
+    assert!(!has_major_status(&with(&["confusion", "protect", "pending_switch"])));
+    assert!(has_major_status(&with(&["burn"])));
+    assert!(has_major_status(&with(&["confusion", "sleep"])));
+}
+
+#[test]
+fn guts_ignores_confusion_but_boosts_when_burned() {
+    let engine = BattleEngine::default();
+    let guts_state = |status_id: &str| {
+        let attacker = CreatureBuilder::new("p1-mon", "Gutsy")
+            .ability("guts")
+            .moves(&["tackle"])
+            .stats(100, 50, 50, 50, 80)
+            .with_status(status(status_id, Some(3)))
+            .build();
+        let target = CreatureBuilder::new("p2-mon", "Target")
+            .moves(&["harden"])
+            .hp(400, 400)
+            .stats(50, 50, 50, 50, 30)
+            .build();
+        battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![target])])
+    };
+    let confused = guts_state("confusion");
+    let mut plain = confused.clone();
+    plain.players[0].team[0].ability = None;
+    let normal_rolls = calc_damage_range(&plain, "p1", "p2", "tackle", &engine).rolls;
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p2")];
+
+    // 混乱で自分を攻撃せず、急所にも当たらなかったターンだけを見る
+    let confused_hit = (1..50)
+        .map(|seed| run_turn_with_seed(&engine, &confused, &actions, seed))
+        .find(|next| {
+            next.players[1].team[0].hp < 400 && !next.log.iter().any(|l| l == "急所に あたった！")
+        })
+        .expect("a clean confused hit");
+    let dealt = 400 - confused_hit.players[1].team[0].hp;
+    assert!(normal_rolls.contains(&dealt), "dealt {} outside {:?}", dealt, normal_rolls);
+
+    let burned = guts_state("burn");
+    let burned_hit = run_turn_with_seed(&engine, &burned, &actions, 1);
+    assert!(!burned_hit.log.iter().any(|l| l == "急所に あたった！"));
+    let dealt = 400 - burned_hit.players[1].team[0].hp;
+    assert!(dealt > *normal_rolls.iter().max().unwrap(), "burned dealt {}", dealt);
+}
+
+fn synchronize_turn(attacker_types: &[&str]) -> BattleState {
+    let engine = BattleEngine::default();
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(attacker_types)
+        .moves(&["poison_gas"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let holder = CreatureBuilder::new("p2-mon", "Holder")
+        .ability("synchronize")
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![attacker]), player("p2", "P2", vec![holder])]);
+    let actions = vec![move_action("p1", "poison_gas", "p2"), move_action("p2", "harden", "p2")];
+    (1..20)
+        .map(|seed| run_turn_with_seed(&engine, &state, &actions, seed))
+        .find(|next| has_major_status(&next.players[1].team[0]))
+        .expect("poison gas lands")
+}
+
+#[test]
+fn synchronize_passes_poison_back_to_the_attacker() {
+    let next = synchronize_turn(&["normal"]);
+    assert!(next.players[0].team[0].statuses.iter().any(|s| s.id == "poison"));
+    assert!(next.log.iter().any(|line| line == "Holderの シンクロ！"));
+    // 返した どくは もう一度 返ってこない
+    assert_eq!(next.players[1].team[0].statuses.iter().filter(|s| s.id == "poison").count(), 1);
+}
+
+#[test]
+fn synchronize_respects_the_attackers_immunity() {
+    let next = synchronize_turn(&["poison"]);
+    assert!(next.players[0].team[0].statuses.is_empty());
+    assert!(next.log.iter().any(|line| line == "Attackerには poisonは 効かない！"));
+}
@@ -0,0 +1,240 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::events::{apply_event, BattleEvent};
+use engine_rust::core::state::BattleState;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn scald_state(target: CreatureBuilder) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["scald"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = target.moves(&["harden"]).hp(400, 400).build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn scald_burns(state: &BattleState, seeds: u64) -> usize {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "scald", "p2"), move_action("p2", "harden", "p2")];
+    (0..seeds)
+        .filter(|seed| {
+            let next = run_turn_with_seed(&engine, state, &actions, *seed);
+            next.players[1].team[0].statuses.iter().any(|s| s.id == "burn")
+        })
+        .count()
+}
+
+#[test]
+fn scald_burns_ordinary_targets_sometimes() {
+    let state = scald_state(CreatureBuilder::new("p2-mon", "Target").types(&["normal"]));
+    assert!(scald_burns(&state, 40) > 0);
+}
+
+#[test]
+fn scald_never_burns_fire_types() {
+    let state = scald_state(CreatureBuilder::new("p2-mon", "Target").types(&["fire"]));
+    assert_eq!(scald_burns(&state, 40), 0);
+}
+
+#[test]
+fn water_veil_and_comatose_block_scald_burn() {
+    for ability in ["water_veil", "comatose"] {
+        let state = scald_state(CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).ability(ability));
+        assert_eq!(scald_burns(&state, 40), 0, "{ability}");
+    }
+}
+
+fn status_lands(target_types: &[&str], status_id: &str, source_ability: Option<&str>) -> bool {
+    let mut source = CreatureBuilder::new("p1-mon", "Source").moves(&["harden"]);
+    if let Some(ability) = source_ability {
+        source = source.ability(ability);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .types(target_types)
+        .moves(&["harden"])
+        .build();
+    let state = battle_state(vec![player("p1", "P1", vec![source.build()]), player("p2", "P2", vec![target])]);
+    let mut meta = Map::new();
+    meta.insert("source".to_string(), Value::String("p1".to_string()));
+    let next = apply_event(
+        &state,
+        &BattleEvent::ApplyStatus {
+            target_id: "p2".to_string(),
+            status_id: status_id.to_string(),
+            duration: None,
+            stack: false,
+            data: HashMap::new(),
+            meta,
+        },
+    );
+    next.players[1].team[0].statuses.iter().any(|s| s.id == status_id)
+}
+
+#[test]
+fn types_block_matching_statuses() {
+    assert!(!status_lands(&["electric"], "paralysis", None));
+    assert!(!status_lands(&["fire"], "burn", None));
+    assert!(!status_lands(&["poison"], "poison", None));
+    assert!(!status_lands(&["steel"], "poison", None));
+    assert!(!status_lands(&["ice"], "freeze", None));
+
+    assert!(status_lands(&["normal"], "paralysis", None));
+    assert!(status_lands(&["electric"], "burn", None));
+    assert!(status_lands(&["water"], "freeze", None));
+}
+
+fn apply_status_with_ability(ability: &str, status_id: &str) -> BattleState {
+    let target = CreatureBuilder::new("p2-mon", "Target").ability(ability).moves(&["harden"]).build();
+    let source = CreatureBuilder::new("p1-mon", "Source").moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![source]), player("p2", "P2", vec![target])]);
+    apply_event(
+        &state,
+        &BattleEvent::ApplyStatus {
+            target_id: "p2".to_string(),
+            status_id: status_id.to_string(),
+            duration: None,
+            stack: false,
+            data: HashMap::new(),
+            meta: Map::new(),
+        },
+    )
+}
+
+#[test]
+fn status_blocking_abilities_reject_their_status() {
+    for (ability, status_id) in [
+        ("limber", "paralysis"),
+        ("water_veil", "burn"),
+        ("magma_armor", "freeze"),
+        ("vital_spirit", "sleep"),
+    ] {
+        let next = apply_status_with_ability(ability, status_id);
+        assert!(next.players[1].team[0].statuses.is_empty(), "{ability} should block {status_id}");
+        assert!(
+            next.log.iter().any(|line| line == &format!("Targetには {}は 効かない！", status_id)),
+            "{ability}"
+        );
+        // 他の状態異常は防がない
+        let other = if status_id == "burn" { "paralysis" } else { "burn" };
+        let next = apply_status_with_ability(ability, other);
+        assert!(next.players[1].team[0].statuses.iter().any(|s| s.id == other), "{ability} vs {other}");
+    }
+}
+
+#[test]
+fn corrosion_poisons_poison_and_steel_types() {
+    assert!(status_lands(&["poison"], "poison", Some("corrosion")));
+    assert!(status_lands(&["steel"], "poison", Some("corrosion")));
+    assert!(!status_lands(&["electric"], "paralysis", Some("corrosion")));
+}
+
+#[test]
+fn grass_types_ignore_powder_moves() {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["spore"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "spore", "p2"), move_action("p2", "harden", "p2")];
+
+    let grass = CreatureBuilder::new("p2-mon", "Target").types(&["grass"]).moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![user.clone()]), player("p2", "P2", vec![grass])]);
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    assert!(!next.players[1].team[0].statuses.iter().any(|s| s.id == "sleep"));
+    assert!(next.log.iter().any(|line| line == "Targetには 効果が ないようだ……"));
+
+    let normal = CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![normal])]);
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    assert!(next.players[1].team[0].statuses.iter().any(|s| s.id == "sleep"));
+}
+
+fn toxic_vs_steel(user_ability: &str) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .ability(user_ability)
+        .moves(&["toxic"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .types(&["steel"])
+        .moves(&["harden"])
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+#[test]
+fn corrosion_user_poisons_steel_type_in_battle() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "toxic", "p2"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &toxic_vs_steel("corrosion"), &actions, 1);
+    assert!(next.players[1].team[0].statuses.iter().any(|s| s.id == "toxic"));
+
+    let next = run_turn_with_seed(&engine, &toxic_vs_steel("run_away"), &actions, 1);
+    assert!(!next.players[1].team[0].statuses.iter().any(|s| s.id == "toxic"));
+}
+
+fn sleep_powder_lands(target: CreatureBuilder) -> bool {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["sleep_powder"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = target.moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])]);
+    let actions = vec![move_action("p1", "sleep_powder", "p2"), move_action("p2", "harden", "p2")];
+    let next = run_turn_with_seed(&BattleEngine::default(), &state, &actions, 1);
+    next.players[1].team[0].statuses.iter().any(|s| s.id == "sleep")
+}
+
+#[test]
+fn sleep_powder_blocked_by_grass_overcoat_and_safety_goggles() {
+    assert!(sleep_powder_lands(CreatureBuilder::new("p2-mon", "Target").types(&["normal"])));
+    assert!(!sleep_powder_lands(CreatureBuilder::new("p2-mon", "Target").types(&["grass"])));
+    assert!(!sleep_powder_lands(
+        CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).ability("overcoat")
+    ));
+    assert!(!sleep_powder_lands(
+        CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).item("safety_goggles")
+    ));
+    assert!(sleep_powder_lands(
+        CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).item("safety_goggles").ability("klutz")
+    ));
+}
+
+fn leech_seed_turn(target: CreatureBuilder) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["leech_seed"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = target.moves(&["harden"]).hp(160, 160).build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])]);
+    let actions = vec![move_action("p1", "leech_seed", "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(&BattleEngine::default(), &state, &actions, 1)
+}
+
+fn seed_count(state: &BattleState) -> usize {
+    state.players[1].team[0].statuses.iter().filter(|s| s.id == "leech_seed").count()
+}
+
+#[test]
+fn leech_seed_fails_on_grass_types() {
+    let next = leech_seed_turn(CreatureBuilder::new("p2-mon", "Target").types(&["normal"]));
+    assert_eq!(seed_count(&next), 1);
+    assert_eq!(next.players[1].team[0].hp, 140);
+
+    let next = leech_seed_turn(CreatureBuilder::new("p2-mon", "Target").types(&["grass"]));
+    assert_eq!(seed_count(&next), 0);
+    assert_eq!(next.players[1].team[0].hp, 160);
+}
+
+#[test]
+fn leech_seed_does_not_stack() {
+    let mut seeded = status("leech_seed", None);
+    seeded.data.insert("sourceId".to_string(), Value::String("p1".to_string()));
+    let next = leech_seed_turn(CreatureBuilder::new("p2-mon", "Target").types(&["normal"]).with_status(seeded));
+    assert_eq!(seed_count(&next), 1);
+    assert_eq!(next.players[1].team[0].hp, 140);
+}
@@ -0,0 +1,55 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::rules::BattleRules;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn struggle_state(target_types: &[&str]) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .types(&["normal"])
+        .moves(&["struggle"])
+        .hp(200, 200)
+        .stats(60, 60, 60, 60, 100)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .types(target_types)
+        .moves(&["harden"])
+        .hp(200, 200)
+        .stats(60, 60, 60, 60, 50)
+        .build();
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn struggle_once(engine: &BattleEngine, target_types: &[&str]) -> BattleState {
+    let actions = vec![move_action("p1", "struggle", "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(engine, &struggle_state(target_types), &actions, 11)
+}
+
+#[test]
+fn struggle_hits_ghost_types_for_neutral_damage() {
+    let engine = BattleEngine::default();
+    let ghost = struggle_once(&engine, &["ghost"]);
+    let normal = struggle_once(&engine, &["normal"]);
+    let rock = struggle_once(&engine, &["rock"]);
+
+    let damage = |state: &BattleState| 200 - state.players[1].team[0].hp;
+    assert!(damage(&ghost) > 0);
+    assert_eq!(damage(&ghost), damage(&normal));
+    // いわタイプにも今ひとつにならない
+    assert_eq!(damage(&rock), damage(&normal));
+    assert!(!ghost.log.iter().any(|line| line.contains("効果が ないようだ")));
+}
+
+#[test]
+fn struggle_recoil_follows_configured_fraction() {
+    let modern = struggle_once(&BattleEngine::default(), &["normal"]);
+    assert_eq!(modern.players[0].team[0].hp, 150);
+    assert!(modern.log.iter().any(|line| line == "Userは 反動を 受けた！"));
+
+    let gen3 = struggle_once(&BattleEngine::default().with_rules(BattleRules::gen(3)), &["normal"]);
+    assert_eq!(gen3.players[0].team[0].hp, 100);
+
+    let custom = BattleEngine::default().with_rules(BattleRules::default().with_struggle_recoil(0.1));
+    assert_eq!(struggle_once(&custom, &["normal"]).players[0].team[0].hp, 180);
+}
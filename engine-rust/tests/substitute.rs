@@ -1,7 +1,8 @@
 use engine_rust::core::battle::{BattleEngine, BattleOptions};
 use engine_rust::core::effects::{apply_effects, apply_events, EffectContext};
 use engine_rust::core::state::{Action, ActionType, BattleState, CreatureState, FieldState, PlayerState, StatStages, Status};
-use engine_rust::data::moves::{Effect, MoveData, MoveDatabase};
+use engine_rust::data::moves::{Effect, MoveCategory, MoveData, MoveDatabase};
+use engine_rust::core::rules::BattleRules;
 use engine_rust::data::type_chart::TypeChart;
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -31,6 +32,11 @@ fn make_creature(id: &str, name: &str, moves: Vec<String>) -> CreatureState {
         move_pp: HashMap::new(),
         ability_data: HashMap::new(),
         volatile_data: HashMap::new(),
+        revealed_moves: Vec::new(),
+        species_revealed: false,
+        weight: None,
+        last_damage_dealt: 0,
+        last_damage_taken: 0,
         attack: 50,
         defense: 50,
         sp_attack: 50,
@@ -42,27 +48,17 @@ fn make_creature(id: &str, name: &str, moves: Vec<String>) -> CreatureState {
 fn make_state(p1: CreatureState, p2: CreatureState) -> BattleState {
     BattleState {
         players: vec![
-            PlayerState {
-                id: "p1".to_string(),
-                name: "P1".to_string(),
-                team: vec![p1],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
-            PlayerState {
-                id: "p2".to_string(),
-                name: "P2".to_string(),
-                team: vec![p2],
-                active_slot: 0,
-                last_fainted_ability: None,
-            },
+            PlayerState::new("p1", "P1", vec![p1]),
+            PlayerState::new("p2", "P2", vec![p2]),
         ],
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -82,6 +78,7 @@ fn substitute_initializes_hp_on_apply() {
         rng: &mut rng,
         turn: 0,
         type_chart: &type_chart,
+        rules: &BattleRules::default(),
         bypass_protect: false,
         ignore_immunity: false,
         bypass_substitute: false,
@@ -114,7 +111,7 @@ fn substitute_takes_damage_and_loses_hp() {
         id: "tap".to_string(),
         name: Some("Tap".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -128,7 +125,7 @@ fn substitute_takes_damage_and_loses_hp() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -191,7 +188,7 @@ fn substitute_breaks_when_hp_depleted() {
         id: "tap".to_string(),
         name: Some("Tap".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("physical".to_string()),
+        category: Some(MoveCategory::Physical),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -205,7 +202,7 @@ fn substitute_breaks_when_hp_depleted() {
         id: "wait".to_string(),
         name: Some("Wait".to_string()),
         move_type: Some("normal".to_string()),
-        category: Some("status".to_string()),
+        category: Some(MoveCategory::Status),
         pp: Some(10),
         power: None,
         accuracy: None,
@@ -53,6 +53,8 @@ pub struct CreatureBuilder {
     sp_defense: i32,
     speed: i32,
     statuses: Vec<Status>,
+    revealed_moves: Vec<String>,
+    weight: Option<f32>,
 }
 
 impl CreatureBuilder {
@@ -74,6 +76,8 @@ impl CreatureBuilder {
             sp_defense: 50,
             speed: 50,
             statuses: Vec::new(),
+            revealed_moves: Vec::new(),
+            weight: None,
         }
     }
 
@@ -134,6 +138,16 @@ impl CreatureBuilder {
         self
     }
 
+    pub fn revealed_moves(mut self, moves: &[&str]) -> Self {
+        self.revealed_moves = moves.iter().map(|v| (*v).to_string()).collect();
+        self
+    }
+
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
     pub fn build(self) -> CreatureState {
         CreatureState {
             id: self.id,
@@ -151,6 +165,11 @@ impl CreatureBuilder {
             move_pp: HashMap::new(),
             ability_data: HashMap::new(),
             volatile_data: HashMap::new(),
+            revealed_moves: self.revealed_moves,
+            species_revealed: false,
+            weight: self.weight,
+            last_damage_dealt: 0,
+            last_damage_taken: 0,
             attack: self.attack,
             defense: self.defense,
             sp_attack: self.sp_attack,
@@ -169,13 +188,7 @@ pub fn status(status_id: &str, remaining_turns: Option<i32>) -> Status {
 }
 
 pub fn player(id: &str, name: &str, team: Vec<CreatureState>) -> PlayerState {
-    PlayerState {
-        id: id.to_string(),
-        name: name.to_string(),
-        team,
-        active_slot: 0,
-        last_fainted_ability: None,
-    }
+    PlayerState::new(id, name, team)
 }
 
 pub fn player_with_active(
@@ -185,11 +198,8 @@ pub fn player_with_active(
     active_slot: usize,
 ) -> PlayerState {
     PlayerState {
-        id: id.to_string(),
-        name: name.to_string(),
-        team,
         active_slot,
-        last_fainted_ability: None,
+        ..PlayerState::new(id, name, team)
     }
 }
 
@@ -199,9 +209,11 @@ pub fn battle_state(players: Vec<PlayerState>) -> BattleState {
         field: FieldState {
             global: Vec::new(),
             sides: HashMap::new(),
+            last_move: None,
         },
         turn: 0,
         log: Vec::new(),
+        log_categories: Vec::new(),
         history: None,
     }
 }
@@ -228,6 +240,17 @@ pub fn switch_action(player_id: &str, slot: usize) -> Action {
     }
 }
 
+pub fn item_action(player_id: &str, item_id: &str, slot: usize) -> Action {
+    Action {
+        player_id: player_id.to_string(),
+        action_type: ActionType::UseItem,
+        move_id: Some(item_id.to_string()),
+        target_id: None,
+        slot: Some(slot),
+        priority: None,
+    }
+}
+
 pub fn run_turn_with_seed(
     engine: &BattleEngine,
     state: &BattleState,
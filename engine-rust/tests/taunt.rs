@@ -0,0 +1,76 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{ActionType, BattleState};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn taunt_state() -> BattleState {
+    let taunter = CreatureBuilder::new("p1-mon", "Taunter")
+        .moves(&["taunt", "harden"])
+        .hp(200, 200)
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["tackle", "harden"])
+        .hp(200, 200)
+        .stats(50, 50, 50, 50, 50)
+        .build();
+    let bench = CreatureBuilder::new("p2-bench", "Bench").moves(&["tackle"]).build();
+    battle_state(vec![
+        player("p1", "P1", vec![taunter]),
+        player("p2", "P2", vec![target, bench]),
+    ])
+}
+
+fn move_ids(engine: &BattleEngine, state: &BattleState, player_id: &str) -> Vec<String> {
+    engine
+        .legal_actions(state, player_id)
+        .into_iter()
+        .filter(|a| a.action_type == ActionType::Move)
+        .filter_map(|a| a.move_id)
+        .collect()
+}
+
+#[test]
+fn taunted_creature_has_no_status_moves_in_legal_actions() {
+    let engine = BattleEngine::default();
+    let mut state = taunt_state();
+    assert_eq!(move_ids(&engine, &state, "p2"), vec!["tackle", "harden"]);
+
+    state.players[1].team[0].statuses.push(status("taunt", Some(3)));
+    let actions = engine.legal_actions(&state, "p2");
+    assert_eq!(move_ids(&engine, &state, "p2"), vec!["tackle"]);
+    // 交代はちょうはつの影響を受けない
+    assert!(actions
+        .iter()
+        .any(|a| a.action_type == ActionType::Switch && a.slot == Some(1)));
+}
+
+#[test]
+fn taunt_expires_after_three_turns() {
+    let engine = BattleEngine::default();
+    let idle = || vec![move_action("p1", "harden", "p1"), move_action("p2", "tackle", "p1")];
+    let mut state = run_turn_with_seed(
+        &engine,
+        &taunt_state(),
+        &[move_action("p1", "taunt", "p2"), move_action("p2", "tackle", "p1")],
+        1,
+    );
+    let taunt_turns = |state: &BattleState| {
+        state.players[1].team[0]
+            .statuses
+            .iter()
+            .find(|s| s.id == "taunt")
+            .and_then(|s| s.remaining_turns)
+    };
+    assert_eq!(taunt_turns(&state), Some(2));
+
+    state = run_turn_with_seed(&engine, &state, &idle(), 2);
+    assert_eq!(taunt_turns(&state), Some(1));
+    assert_eq!(move_ids(&engine, &state, "p2"), vec!["tackle"]);
+
+    state = run_turn_with_seed(&engine, &state, &idle(), 3);
+    assert_eq!(taunt_turns(&state), None);
+    assert!(state.log.iter().any(|line| line == "Targetの ちょうはつの 効果が 解けた！"));
+    assert_eq!(move_ids(&engine, &state, "p2"), vec!["tackle", "harden"]);
+}
@@ -0,0 +1,82 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn team_cure_state(move_id: &str, bench_ability: &str) -> BattleState {
+    let user = CreatureBuilder::new("p1-lead", "Healer")
+        .moves(&[move_id])
+        .with_status(status("paralysis", None))
+        .stats(50, 50, 50, 50, 200)
+        .build();
+    let bench = CreatureBuilder::new("p1-bench", "Benched")
+        .ability(bench_ability)
+        .moves(&["harden"])
+        .with_status(status("poison", None))
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    battle_state(vec![player("p1", "P1", vec![user, bench]), player("p2", "P2", vec![foe])])
+}
+
+fn has_status(state: &BattleState, slot: usize, status_id: &str) -> bool {
+    state.players[0].team[slot].statuses.iter().any(|s| s.id == status_id)
+}
+
+#[test]
+fn aromatherapy_cures_benched_teammate() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "aromatherapy", "p1"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &team_cure_state("aromatherapy", "soundproof"), &actions, 1);
+
+    assert!(!has_status(&next, 0, "paralysis"));
+    assert!(!has_status(&next, 1, "poison"));
+}
+
+#[test]
+fn heal_bell_skips_soundproof_teammate() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "heal_bell", "p1"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &team_cure_state("heal_bell", "soundproof"), &actions, 1);
+    assert!(!has_status(&next, 0, "paralysis"));
+    assert!(has_status(&next, 1, "poison"));
+
+    let next = run_turn_with_seed(&engine, &team_cure_state("heal_bell", "run_away"), &actions, 1);
+    assert!(!has_status(&next, 1, "poison"));
+}
+
+#[test]
+fn heal_bell_still_cures_soundproof_user() {
+    let engine = BattleEngine::default();
+    let mut state = team_cure_state("heal_bell", "run_away");
+    state.players[0].team[0].ability = Some("soundproof".to_string());
+    let actions = vec![move_action("p1", "heal_bell", "p1"), move_action("p2", "harden", "p2")];
+
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+    assert!(!has_status(&next, 0, "paralysis"));
+}
+
+#[test]
+fn refresh_cures_burn_but_not_confusion() {
+    let user = CreatureBuilder::new("p1-mon", "User")
+        .moves(&["refresh"])
+        .with_status(status("burn", None))
+        .with_status(status("confusion", Some(4)))
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["harden"]).build();
+    let state = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![foe])]);
+    let engine = BattleEngine::default();
+
+    // こんらんで自分を攻撃しない乱数
+    let actions = vec![move_action("p1", "refresh", "p1"), move_action("p2", "harden", "p2")];
+    let next = (1..20)
+        .map(|seed| run_turn_with_seed(&engine, &state, &actions, seed))
+        .find(|next| next.log.iter().any(|line| line == "P1の リフレッシュ！"))
+        .expect("refresh used");
+
+    assert!(!has_status(&next, 0, "burn"));
+    assert!(has_status(&next, 0, "confusion"));
+}
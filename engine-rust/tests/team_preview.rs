@@ -0,0 +1,66 @@
+mod support;
+
+use engine_rust::core::battle::{team_preview, BattleEngine, BattleOptions};
+use engine_rust::core::rng::SplitMix64Rng;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, switch_action, CreatureBuilder};
+
+fn preview_state() -> BattleState {
+    let mon = |id: &str, species: &str| {
+        CreatureBuilder::new(id, species)
+            .species_id(species)
+            .moves(&["splash"])
+            .build()
+    };
+    battle_state(vec![
+        player("p1", "P1", vec![mon("p1-a", "lead_one"), mon("p1-b", "bench_one")]),
+        player("p2", "P2", vec![mon("p2-a", "lead_two"), mon("p2-b", "bench_two"), mon("p2-c", "bench_three")]),
+    ])
+}
+
+fn step(state: &BattleState, team_preview: bool, p1_switch: bool) -> BattleState {
+    let engine = BattleEngine::default();
+    let p1_action = if p1_switch { switch_action("p1", 1) } else { move_action("p1", "splash", "p1") };
+    let actions = vec![p1_action, move_action("p2", "splash", "p2")];
+    let mut rng = SplitMix64Rng::new(1);
+    let mut rng_fn = rng.as_fn();
+    let options = BattleOptions {
+        team_preview,
+        ..Default::default()
+    };
+    engine.step_battle(state, &actions, &mut rng_fn, options)
+}
+
+fn revealed(state: &BattleState, player: usize) -> Vec<bool> {
+    state.players[player].team.iter().map(|c| c.species_revealed).collect()
+}
+
+#[test]
+fn team_preview_lists_every_species_for_both_players() {
+    let preview = team_preview(&preview_state());
+    assert_eq!(
+        preview,
+        vec![
+            ("p1".to_string(), vec!["lead_one".to_string(), "bench_one".to_string()]),
+            (
+                "p2".to_string(),
+                vec!["lead_two".to_string(), "bench_two".to_string(), "bench_three".to_string()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn team_preview_option_reveals_the_whole_team() {
+    let next = step(&preview_state(), true, false);
+    assert_eq!(revealed(&next, 0), vec![true, true]);
+    assert_eq!(revealed(&next, 1), vec![true, true, true]);
+}
+
+#[test]
+fn without_preview_only_creatures_that_took_the_field_are_revealed() {
+    let next = step(&preview_state(), false, false);
+    assert_eq!(revealed(&next, 1), vec![true, false, false]);
+    let switched = step(&next, false, true);
+    assert_eq!(revealed(&switched, 0), vec![true, true]);
+}
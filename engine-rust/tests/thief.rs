@@ -0,0 +1,51 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn thief_state(attacker_item: Option<&str>, target_ability: &str) -> BattleState {
+    let mut thief = CreatureBuilder::new("p1-mon", "Thief")
+        .moves(&["thief"])
+        .stats(50, 50, 50, 50, 80);
+    if let Some(item) = attacker_item {
+        thief = thief.item(item);
+    }
+    let holder = CreatureBuilder::new("p2-mon", "Holder")
+        .ability(target_ability)
+        .item("leftovers")
+        .moves(&["harden"])
+        .build();
+    battle_state(vec![player("p1", "P1", vec![thief.build()]), player("p2", "P2", vec![holder])])
+}
+
+fn run_thief(state: &BattleState) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "thief", "p2"), move_action("p2", "harden", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn thief_takes_item_when_attacker_is_itemless() {
+    let next = run_thief(&thief_state(None, "run_away"));
+
+    assert_eq!(next.players[0].team[0].item.as_deref(), Some("leftovers"));
+    assert_eq!(next.players[1].team[0].item, None);
+    assert!(next.log.iter().any(|l| l.contains("Thiefは Holderから leftoversを 奪い取った！")));
+}
+
+#[test]
+fn thief_does_nothing_when_attacker_holds_item() {
+    let next = run_thief(&thief_state(Some("scope_lens"), "run_away"));
+
+    assert_eq!(next.players[0].team[0].item.as_deref(), Some("scope_lens"));
+    assert_eq!(next.players[1].team[0].item.as_deref(), Some("leftovers"));
+}
+
+#[test]
+fn sticky_hold_keeps_item_from_thief() {
+    let next = run_thief(&thief_state(None, "sticky_hold"));
+
+    assert_eq!(next.players[0].team[0].item, None);
+    assert_eq!(next.players[1].team[0].item.as_deref(), Some("leftovers"));
+}
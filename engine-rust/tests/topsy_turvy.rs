@@ -0,0 +1,57 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn topsy_state(target_ability: Option<&str>, atk: i32, def: i32) -> BattleState {
+    let user = CreatureBuilder::new("p1-mon", "Flipper")
+        .moves(&["topsy_turvy"])
+        .stats(50, 50, 50, 50, 100)
+        .build();
+    let mut target = CreatureBuilder::new("p2-mon", "Boosted")
+        .moves(&["splash"])
+        .stats(50, 50, 50, 50, 50);
+    if let Some(ability) = target_ability {
+        target = target.ability(ability);
+    }
+    let mut target = target.build();
+    target.stages.atk = atk;
+    target.stages.def = def;
+    battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![target])])
+}
+
+fn flip(state: &BattleState) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "topsy_turvy", "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn topsy_turvy_negates_the_targets_stages() {
+    let next = flip(&topsy_state(None, 2, 1));
+    let stages = &next.players[1].team[0].stages;
+    assert_eq!((stages.atk, stages.def, stages.spe), (-2, -1, 0));
+    assert!(next.log.iter().any(|line| line == "Boostedの 能力変化が ひっくり返った！"));
+}
+
+#[test]
+fn topsy_turvy_sets_stages_directly_past_simple_and_contrary() {
+    for ability in ["simple", "contrary"] {
+        let stages = flip(&topsy_state(Some(ability), 2, -3)).players[1].team[0].stages.clone();
+        assert_eq!((stages.atk, stages.def), (-2, 3), "{ability}");
+    }
+}
+
+#[test]
+fn topsy_turvy_fails_without_stage_changes() {
+    let next = flip(&topsy_state(None, 0, 0));
+    assert!(next.log.iter().any(|line| line == "しかし うまく 決まらなかった！"));
+}
+
+#[test]
+fn topsy_turvy_does_not_trigger_competitive() {
+    let next = flip(&topsy_state(Some("competitive"), 2, 0));
+    let stages = &next.players[1].team[0].stages;
+    assert_eq!((stages.atk, stages.spa), (-2, 0));
+}
@@ -0,0 +1,61 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::events::TOXIC_COUNTER_KEY;
+use engine_rust::core::state::BattleState;
+use serde_json::Value;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn toxic_counter(state: &BattleState) -> Option<i64> {
+    state.players[0].team[0]
+        .statuses
+        .iter()
+        .find(|s| s.id == "toxic")
+        .and_then(|s| s.data.get(TOXIC_COUNTER_KEY))
+        .and_then(Value::as_i64)
+}
+
+#[test]
+fn toxic_damage_ramps_by_one_sixteenth_each_turn() {
+    let engine = BattleEngine::default();
+    let mut state = battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Poisoned")
+                .moves(&["harden"])
+                .hp(160, 160)
+                .with_status(status("toxic", None))
+                .build()],
+        ),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "Idle").moves(&["harden"]).build()]),
+    ]);
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+
+    for turn in 1..=5 {
+        let before = state.players[0].team[0].hp;
+        state = run_turn_with_seed(&engine, &state, &actions, turn as u64);
+        assert_eq!(before - state.players[0].team[0].hp, 160 * turn / 16, "turn {turn}");
+        assert_eq!(toxic_counter(&state), Some(turn as i64 + 1));
+    }
+    assert_eq!(state.players[0].team[0].hp, 160 - 150);
+}
+
+#[test]
+fn toxic_counter_starts_at_one_when_applied() {
+    let engine = BattleEngine::default();
+    let state = battle_state(vec![
+        player("p1", "P1", vec![CreatureBuilder::new("p1-mon", "Target").moves(&["harden"]).build()]),
+        player("p2", "P2", vec![CreatureBuilder::new("p2-mon", "User").moves(&["toxic"]).build()]),
+    ]);
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "toxic", "p1")];
+    let mut next = state.clone();
+    for seed in 0..20 {
+        next = run_turn_with_seed(&engine, &state, &actions, seed);
+        if toxic_counter(&next).is_some() {
+            break;
+        }
+    }
+    // 付けたターンの終わりに 1/16 を受けて 2 段目に進む
+    assert_eq!(toxic_counter(&next), Some(2));
+}
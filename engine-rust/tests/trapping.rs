@@ -0,0 +1,158 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use serde_json::Value;
+use support::harness::{
+    assert_active_has_status, battle_state, move_action, player, run_turn_with_seed, status, switch_action,
+    CreatureBuilder,
+};
+
+fn binding_state() -> BattleState {
+    let binder = CreatureBuilder::new("p1-mon", "Binder")
+        .moves(&["bind", "harden"])
+        .stats(50, 50, 50, 50, 80)
+        .build();
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .moves(&["harden"])
+        .hp(400, 400)
+        .build();
+    let bench = CreatureBuilder::new("p2-bench", "Bench").moves(&["harden"]).build();
+    battle_state(vec![
+        player("p1", "P1", vec![binder]),
+        player("p2", "P2", vec![target, bench]),
+    ])
+}
+
+fn active_id(state: &BattleState, player_id: &str) -> String {
+    let player = state.players.iter().find(|p| p.id == player_id).unwrap();
+    player.team[player.active_slot].id.clone()
+}
+
+#[test]
+fn bound_creature_cannot_switch() {
+    let mut state = binding_state();
+    state.players[1].team[0].statuses.push(status("bind", Some(4)));
+    let engine = BattleEngine::default();
+
+    let actions = vec![move_action("p1", "harden", "p2"), switch_action("p2", 1)];
+    let next = run_turn_with_seed(&engine, &state, &actions, 1);
+
+    assert_eq!(active_id(&next, "p2"), "p2-mon");
+    assert!(next.log.iter().any(|line| line == "P2は 交代できなかった！"));
+}
+
+#[test]
+fn bind_chips_for_four_to_five_turns() {
+    let engine = BattleEngine::default();
+    let mut state = binding_state();
+    let bind = vec![move_action("p1", "bind", "p2"), move_action("p2", "harden", "p1")];
+    state = run_turn_with_seed(&engine, &state, &bind, 2);
+    assert_active_has_status(&state, "p2", "bind");
+
+    let idle = vec![move_action("p1", "harden", "p2"), move_action("p2", "harden", "p1")];
+    for seed in 3..10 {
+        state = run_turn_with_seed(&engine, &state, &idle, seed);
+    }
+    let chips = state
+        .log
+        .iter()
+        .filter(|line| line.starts_with("Targetは しめつけるの ダメージを受けている"))
+        .count();
+    assert!((4..=5).contains(&chips), "expected 4-5 bind chips, got {chips}");
+    let target = &state.players[1].team[0];
+    assert!(!target.statuses.iter().any(|s| s.id == "bind"));
+}
+
+#[test]
+fn shed_shell_escapes_bind_and_shadow_tag() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p2"), switch_action("p2", 1)];
+
+    let mut bound = binding_state();
+    bound.players[1].team[0].statuses.push(status("bind", Some(4)));
+    bound.players[1].team[0].item = Some("shed_shell".to_string());
+    let next = run_turn_with_seed(&engine, &bound, &actions, 1);
+    assert_eq!(active_id(&next, "p2"), "p2-bench");
+
+    let mut tagged = binding_state();
+    tagged.players[0].team[0].ability = Some("shadow_tag".to_string());
+    let next = run_turn_with_seed(&engine, &tagged, &actions, 1);
+    assert_eq!(active_id(&next, "p2"), "p2-mon");
+
+    tagged.players[1].team[0].item = Some("shed_shell".to_string());
+    let next = run_turn_with_seed(&engine, &tagged, &actions, 1);
+    assert_eq!(active_id(&next, "p2"), "p2-bench");
+}
+
+#[test]
+fn shed_shell_held_as_an_item_status_escapes_bind() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p2"), switch_action("p2", 1)];
+
+    let mut bound = binding_state();
+    bound.players[1].team[0].statuses.push(status("bind", Some(4)));
+    let mut held = status("item", None);
+    held.data.insert("itemId".to_string(), Value::String("shed_shell".to_string()));
+    bound.players[1].team[0].statuses.push(held);
+    let next = run_turn_with_seed(&engine, &bound, &actions, 1);
+    assert_eq!(active_id(&next, "p2"), "p2-bench");
+}
+
+#[test]
+fn mean_look_traps_until_the_user_switches_out() {
+    let engine = BattleEngine::default();
+    let looker = CreatureBuilder::new("p1-mon", "Looker").moves(&["mean_look", "harden"]).build();
+    let looker_bench = CreatureBuilder::new("p1-bench", "Relief").moves(&["harden"]).build();
+    let target = CreatureBuilder::new("p2-mon", "Target").moves(&["harden"]).build();
+    let bench = CreatureBuilder::new("p2-bench", "Bench").moves(&["harden"]).build();
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![looker, looker_bench]),
+        player("p2", "P2", vec![target, bench]),
+    ]);
+
+    let look = vec![move_action("p1", "mean_look", "p2"), move_action("p2", "harden", "p1")];
+    state = run_turn_with_seed(&engine, &state, &look, 1);
+    assert_active_has_status(&state, "p2", "trapped");
+
+    let flee = vec![move_action("p1", "harden", "p2"), switch_action("p2", 1)];
+    for seed in 2..8 {
+        state = run_turn_with_seed(&engine, &state, &flee, seed);
+        assert_eq!(active_id(&state, "p2"), "p2-mon");
+    }
+
+    let release = vec![switch_action("p1", 1), move_action("p2", "harden", "p1")];
+    state = run_turn_with_seed(&engine, &state, &release, 8);
+    let target = &state.players[1].team[0];
+    assert!(!target.statuses.iter().any(|s| s.id == "trapped"));
+
+    let flee = vec![move_action("p1", "harden", "p2"), switch_action("p2", 1)];
+    state = run_turn_with_seed(&engine, &state, &flee, 9);
+    assert_eq!(active_id(&state, "p2"), "p2-bench");
+}
+
+fn fire_spin_residual(binder_item: Option<&str>, target_ability: Option<&str>) -> i32 {
+    let engine = BattleEngine::default();
+    let mut state = binding_state();
+    state.players[0].team[0].moves = vec!["fire_spin".to_string(), "harden".to_string()];
+    state.players[0].team[0].item = binder_item.map(str::to_string);
+    state.players[1].team[0].ability = target_ability.map(str::to_string);
+    state.players[1].team[0].hp = 480;
+    state.players[1].team[0].max_hp = 480;
+
+    let spin = vec![move_action("p1", "fire_spin", "p2"), move_action("p2", "harden", "p1")];
+    state = run_turn_with_seed(&engine, &state, &spin, 2);
+    assert_active_has_status(&state, "p2", "bind");
+
+    let before = state.players[1].team[0].hp;
+    let idle = vec![move_action("p1", "harden", "p2"), move_action("p2", "harden", "p1")];
+    state = run_turn_with_seed(&engine, &state, &idle, 3);
+    before - state.players[1].team[0].hp
+}
+
+#[test]
+fn binding_band_boosts_residual_and_magic_guard_blocks_it() {
+    assert_eq!(fire_spin_residual(None, None), 60);
+    assert_eq!(fire_spin_residual(Some("binding_band"), None), 80);
+    assert_eq!(fire_spin_residual(Some("binding_band"), Some("magic_guard")), 0);
+}
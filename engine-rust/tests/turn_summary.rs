@@ -0,0 +1,43 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::summary::{turn_summary, FieldChange, StageChange};
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+#[test]
+fn summary_reports_damage_and_new_status() {
+    let user = CreatureBuilder::new("p1-mon", "User").moves(&["tackle"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["tackle"]).hp(100, 100).build();
+    let before = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![foe])]);
+
+    let mut after = before.clone();
+    let foe = &mut after.players[1].team[0];
+    foe.hp -= 30;
+    foe.statuses.push(status("paralysis", None));
+
+    let summary = turn_summary(&before, &after);
+    assert_eq!(summary.creatures.len(), 1);
+    let change = &summary.creatures[0];
+    assert_eq!(change.player_id, "p2");
+    assert_eq!(change.hp_delta, -30);
+    assert_eq!(change.hp_after, 70);
+    assert_eq!(change.statuses_gained, vec!["paralysis".to_string()]);
+    assert!(change.statuses_lost.is_empty());
+    assert!(!change.fainted);
+}
+
+#[test]
+fn summary_reports_stage_and_field_changes_from_a_turn() {
+    let user = CreatureBuilder::new("p1-mon", "User").moves(&["harden"]).build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe").moves(&["rain_dance"]).build();
+    let before = battle_state(vec![player("p1", "P1", vec![user]), player("p2", "P2", vec![foe])]);
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "rain_dance", "p2")];
+    let after = run_turn_with_seed(&engine, &before, &actions, 1);
+
+    let summary = turn_summary(&before, &after);
+    let user = summary.creatures.iter().find(|c| c.player_id == "p1").expect("user change");
+    assert_eq!(user.stage_changes, vec![StageChange { stat: "def".to_string(), delta: 1 }]);
+    assert!(summary.field_started.contains(&FieldChange { side: None, effect_id: "rain".to_string() }));
+    assert!(summary.field_ended.is_empty());
+}
@@ -0,0 +1,82 @@
+use engine_rust::data::type_chart::TypeChart;
+use serde_json::Value;
+
+#[test]
+fn effectiveness_table_matches_effectiveness() {
+    let chart = TypeChart::new();
+    let table = chart.effectiveness_table();
+
+    assert_eq!(table.len(), 18 * 18);
+    for (attacking, defending, multiplier) in &table {
+        assert_eq!(*multiplier, chart.effectiveness(attacking, std::slice::from_ref(defending)));
+    }
+    assert!(table.contains(&("water".to_string(), "fire".to_string(), 2.0)));
+    assert!(table.contains(&("electric".to_string(), "ground".to_string(), 0.0)));
+}
+
+#[test]
+fn to_json_nests_attacking_then_defending_type() {
+    let chart = TypeChart::new();
+    let json: Value = serde_json::from_str(&chart.to_json()).expect("valid json");
+
+    assert_eq!(json["water"]["fire"].as_f64(), Some(2.0));
+    assert_eq!(json["fire"]["water"].as_f64(), Some(0.5));
+    assert_eq!(json["normal"]["ghost"].as_f64(), Some(0.0));
+}
+
+#[test]
+fn inverted_chart_flips_effectiveness_and_immunities() {
+    let inverted = TypeChart::new().inverted();
+    let types = |t: &str| vec![t.to_string()];
+
+    assert_eq!(inverted.effectiveness("water", &types("fire")), 0.5);
+    assert_eq!(inverted.effectiveness("fire", &types("water")), 2.0);
+    assert_eq!(inverted.effectiveness("electric", &types("ground")), 2.0);
+    assert_eq!(inverted.effectiveness("normal", &types("normal")), 1.0);
+}
+
+#[test]
+fn dual_types_multiply_their_individual_multipliers() {
+    let chart = TypeChart::new();
+    let types = |ts: &[&str]| ts.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(chart.effectiveness("ice", &types(&["dragon", "flying"])), 4.0);
+    assert_eq!(chart.effectiveness("fire", &types(&["water", "rock"])), 0.25);
+    assert_eq!(chart.effectiveness("fire", &types(&["grass", "water"])), 1.0);
+    assert_eq!(chart.effectiveness("ground", &types(&["fire", "flying"])), 0.0);
+    assert_eq!(
+        chart.effectiveness_breakdown("fire", &types(&["grass", "water"])),
+        vec![("grass".to_string(), 2.0), ("water".to_string(), 0.5)]
+    );
+    assert_eq!(
+        chart.effectiveness_breakdown("ground", &types(&["fire", "flying"])),
+        vec![("fire".to_string(), 2.0), ("flying".to_string(), 0.0)]
+    );
+}
+
+#[test]
+fn chart_loads_from_json() {
+    let json = r#"{"fire": {"weakTo": ["water"], "resists": ["fire"]}, "ghost": {"immuneTo": ["normal"]}}"#;
+    let chart = TypeChart::load_from_json_str(json).expect("valid chart");
+    let types = |t: &str| vec![t.to_string()];
+
+    assert_eq!(chart.effectiveness("water", &types("fire")), 2.0);
+    assert_eq!(chart.effectiveness("fire", &types("fire")), 0.5);
+    assert_eq!(chart.effectiveness("normal", &types("ghost")), 0.0);
+    // 表にないタイプは等倍
+    assert_eq!(chart.effectiveness("water", &types("steel")), 1.0);
+}
+
+/// 旧来の直書きの表から直した5つの相性
+#[test]
+fn corrected_matchups_follow_the_games() {
+    let chart = TypeChart::new();
+    let types = |t: &str| vec![t.to_string()];
+
+    assert_eq!(chart.effectiveness("fire", &types("fire")), 0.5);
+    assert_eq!(chart.effectiveness("ice", &types("water")), 0.5);
+    assert_eq!(chart.effectiveness("electric", &types("grass")), 0.5);
+    assert_eq!(chart.effectiveness("fairy", &types("poison")), 0.5);
+    assert_eq!(chart.effectiveness("fighting", &types("steel")), 2.0);
+    assert_eq!(chart.effectiveness("water", &types("steel")), 1.0);
+}
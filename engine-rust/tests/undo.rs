@@ -0,0 +1,59 @@
+mod support;
+
+use engine_rust::core::battle::{BattleEngine, BattleOptions};
+use engine_rust::core::state::state_key;
+use engine_rust::core::undo::undo;
+use support::harness::{battle_state, move_action, player, CreatureBuilder, SeededRng};
+
+#[test]
+fn apply_turn_then_undo_restores_state() {
+    let attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .moves(&["tackle", "harden"])
+        .build();
+    let defender = CreatureBuilder::new("p2-mon", "Defender")
+        .moves(&["tackle", "harden"])
+        .build();
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![attacker]),
+        player("p2", "P2", vec![defender]),
+    ]);
+    let engine = BattleEngine::default();
+    let before_key = state_key(&state);
+    let before_log = state.log.clone();
+
+    let mut rng = SeededRng::new(11);
+    let mut rng_fn = || rng.next_f64();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "harden", "p1")];
+    let token = engine.apply_turn(&mut state, &actions, &mut rng_fn, BattleOptions::default());
+    assert_ne!(state_key(&state), before_key);
+    assert!(state.history.is_some());
+
+    undo(&mut state, token);
+    assert_eq!(state_key(&state), before_key);
+    assert_eq!(state.log, before_log);
+    assert!(state.history.is_none());
+}
+
+#[test]
+fn stacked_turns_undo_in_reverse_order() {
+    let mon = |id: &str, name: &str| CreatureBuilder::new(id, name).moves(&["tackle", "harden"]).build();
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![mon("p1-mon", "Attacker")]),
+        player("p2", "P2", vec![mon("p2-mon", "Defender")]),
+    ]);
+    let engine = BattleEngine::default();
+    let mut rng = SeededRng::new(5);
+    let mut rng_fn = || rng.next_f64();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+
+    let start = (state_key(&state), state.log.clone());
+    let first = engine.apply_turn(&mut state, &actions, &mut rng_fn, BattleOptions::default());
+    let middle = (state_key(&state), state.log.clone());
+    let second = engine.apply_turn(&mut state, &actions, &mut rng_fn, BattleOptions::default());
+    assert_eq!(state.turn, 2);
+
+    undo(&mut state, second);
+    assert_eq!((state_key(&state), state.log.clone()), middle);
+    undo(&mut state, first);
+    assert_eq!((state_key(&state), state.log.clone()), start);
+}
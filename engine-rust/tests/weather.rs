@@ -0,0 +1,132 @@
+mod support;
+
+use engine_rust::core::abilities::{get_weather, WeatherKind};
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, FieldEffect};
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn weather_state(weather: &str, p1: CreatureBuilder, p2: CreatureBuilder) -> BattleState {
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![p1.moves(&["harden"]).hp(160, 160).build()]),
+        player("p2", "P2", vec![p2.moves(&["harden"]).hp(160, 160).build()]),
+    ]);
+    state.field.global.push(FieldEffect {
+        id: weather.to_string(),
+        remaining_turns: Some(5),
+        data: HashMap::new(),
+    });
+    state
+}
+
+fn run_idle_turns(state: BattleState, turns: u64) -> Vec<BattleState> {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+    let mut states = Vec::new();
+    let mut current = state;
+    for seed in 0..turns {
+        current = run_turn_with_seed(&engine, &current, &actions, seed);
+        states.push(current.clone());
+    }
+    states
+}
+
+#[test]
+fn get_weather_recognizes_sand_and_hail() {
+    let sand = weather_state("sandstorm", CreatureBuilder::new("a", "A"), CreatureBuilder::new("b", "B"));
+    assert!(matches!(get_weather(&sand), Some(WeatherKind::Sand)));
+    let hail = weather_state("hail", CreatureBuilder::new("a", "A"), CreatureBuilder::new("b", "B"));
+    assert!(matches!(get_weather(&hail), Some(WeatherKind::Hail)));
+}
+
+#[test]
+fn sandstorm_spares_rock_types_and_chips_normal_types_each_turn() {
+    let state = weather_state(
+        "sandstorm",
+        CreatureBuilder::new("p1-rock", "Rock").types(&["rock"]),
+        CreatureBuilder::new("p2-normal", "Normal").types(&["normal"]),
+    );
+    for (turn, state) in run_idle_turns(state, 3).iter().enumerate() {
+        assert_eq!(state.players[0].team[0].hp, 160);
+        assert_eq!(state.players[1].team[0].hp, 160 - 10 * (turn as i32 + 1));
+    }
+}
+
+#[test]
+fn hail_spares_ice_types_and_overcoat() {
+    let state = weather_state(
+        "hail",
+        CreatureBuilder::new("p1-ice", "Ice").types(&["ice"]),
+        CreatureBuilder::new("p2-normal", "Normal").types(&["normal"]),
+    );
+    let next = &run_idle_turns(state, 1)[0];
+    assert_eq!(next.players[0].team[0].hp, 160);
+    assert_eq!(next.players[1].team[0].hp, 150);
+    assert!(next.log.iter().any(|line| line == "Normalは あられに 襲われている！"));
+
+    let state = weather_state(
+        "hail",
+        CreatureBuilder::new("p1-coat", "Coat").types(&["normal"]).ability("overcoat"),
+        CreatureBuilder::new("p2-guard", "Guard").types(&["normal"]).ability("magic_guard"),
+    );
+    let next = &run_idle_turns(state, 1)[0];
+    assert_eq!(next.players[0].team[0].hp, 160);
+    assert_eq!(next.players[1].team[0].hp, 160);
+}
+
+fn attack_damage(weather: Option<&str>, move_id: &str, attacker_ability: Option<&str>) -> i32 {
+    let mut attacker = CreatureBuilder::new("p1-mon", "Attacker")
+        .types(&["normal"])
+        .moves(&[move_id])
+        .hp(300, 300)
+        .stats(100, 100, 100, 100, 100);
+    if let Some(ability) = attacker_ability {
+        attacker = attacker.ability(ability);
+    }
+    let target = CreatureBuilder::new("p2-mon", "Target")
+        .types(&["normal"])
+        .moves(&["harden"])
+        .hp(1000, 1000)
+        .stats(50, 50, 50, 50, 50);
+    let mut state = battle_state(vec![
+        player("p1", "P1", vec![attacker.build()]),
+        player("p2", "P2", vec![target.build()]),
+    ]);
+    if let Some(weather) = weather {
+        state.field.global.push(FieldEffect {
+            id: weather.to_string(),
+            remaining_turns: Some(5),
+            data: HashMap::new(),
+        });
+    }
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "harden", "p2")];
+    1000 - run_turn_with_seed(&engine, &state, &actions, 5).players[1].team[0].hp
+}
+
+fn assert_scaled(actual: i32, base: i32, factor: f64) {
+    let expected = base as f64 * factor;
+    assert!((actual as f64 - expected).abs() <= 2.0, "{actual} vs {base} x {factor}");
+}
+
+#[test]
+fn sun_boosts_fire_and_weakens_water() {
+    let fire = attack_damage(None, "flamethrower", None);
+    assert_scaled(attack_damage(Some("sun"), "flamethrower", None), fire, 1.5);
+    let water = attack_damage(None, "surf", None);
+    assert_scaled(attack_damage(Some("sun"), "surf", None), water, 0.5);
+}
+
+#[test]
+fn rain_boosts_water_and_weakens_fire() {
+    let water = attack_damage(None, "surf", None);
+    assert_scaled(attack_damage(Some("rain"), "surf", None), water, 1.5);
+    let fire = attack_damage(None, "flamethrower", None);
+    assert_scaled(attack_damage(Some("rain"), "flamethrower", None), fire, 0.5);
+}
+
+#[test]
+fn weather_damage_modifier_is_off_under_cloud_nine() {
+    let fire = attack_damage(None, "flamethrower", Some("cloud_nine"));
+    assert_eq!(attack_damage(Some("sun"), "flamethrower", Some("cloud_nine")), fire);
+}
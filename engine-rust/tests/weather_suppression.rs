@@ -0,0 +1,79 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::{BattleState, FieldEffect};
+use std::collections::HashMap;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, CreatureBuilder};
+
+fn with_weather(mut state: BattleState, weather: &str) -> BattleState {
+    state.field.global.push(FieldEffect {
+        id: weather.to_string(),
+        remaining_turns: Some(5),
+        data: HashMap::new(),
+    });
+    state
+}
+
+fn swift_swim_race(foe_ability: &str) -> BattleState {
+    let swimmer = CreatureBuilder::new("p1-mon", "Swimmer")
+        .ability("swift_swim")
+        .moves(&["tackle"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 40)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .ability(foe_ability)
+        .moves(&["tackle"])
+        .hp(1, 100)
+        .stats(50, 50, 50, 50, 60)
+        .build();
+    with_weather(
+        battle_state(vec![player("p1", "P1", vec![swimmer]), player("p2", "P2", vec![foe])]),
+        "rain",
+    )
+}
+
+#[test]
+fn cloud_nine_cancels_swift_swim_speed_boost() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "tackle", "p2"), move_action("p2", "tackle", "p1")];
+
+    // 雨で素早さ2倍 (80 > 60) なので すいすいが先に倒す
+    let boosted = run_turn_with_seed(&engine, &swift_swim_race("run_away"), &actions, 1);
+    assert!(boosted.players[0].team[0].hp > 0);
+    assert_eq!(boosted.players[1].team[0].hp, 0);
+
+    let negated = run_turn_with_seed(&engine, &swift_swim_race("cloud_nine"), &actions, 1);
+    assert_eq!(negated.players[0].team[0].hp, 0);
+    assert!(negated.players[1].team[0].hp > 0);
+}
+
+fn sandstorm_state(ability: &str) -> BattleState {
+    let mon = CreatureBuilder::new("p1-mon", "Mon")
+        .ability(ability)
+        .moves(&["harden"])
+        .hp(160, 160)
+        .build();
+    let foe = CreatureBuilder::new("p2-mon", "Foe")
+        .moves(&["harden"])
+        .hp(160, 160)
+        .build();
+    with_weather(
+        battle_state(vec![player("p1", "P1", vec![mon]), player("p2", "P2", vec![foe])]),
+        "sandstorm",
+    )
+}
+
+#[test]
+fn cloud_nine_stops_sandstorm_chip() {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", "harden", "p1"), move_action("p2", "harden", "p2")];
+
+    let control = run_turn_with_seed(&engine, &sandstorm_state("run_away"), &actions, 1);
+    assert_eq!(control.players[0].team[0].hp, 150);
+    assert_eq!(control.players[1].team[0].hp, 150);
+
+    let negated = run_turn_with_seed(&engine, &sandstorm_state("cloud_nine"), &actions, 1);
+    assert_eq!(negated.players[0].team[0].hp, 160);
+    assert_eq!(negated.players[1].team[0].hp, 160);
+}
@@ -0,0 +1,57 @@
+mod support;
+
+use engine_rust::core::battle::BattleEngine;
+use engine_rust::core::state::BattleState;
+use support::harness::{battle_state, move_action, player, run_turn_with_seed, status, CreatureBuilder};
+
+fn guard_state() -> BattleState {
+    battle_state(vec![
+        player(
+            "p1",
+            "P1",
+            vec![CreatureBuilder::new("p1-mon", "Attacker")
+                .moves(&["tackle", "flamethrower"])
+                .hp(200, 200)
+                .stats(100, 100, 100, 100, 100)
+                .build()],
+        ),
+        player(
+            "p2",
+            "P2",
+            vec![CreatureBuilder::new("p2-mon", "Guard")
+                .moves(&["splash"])
+                .types(&["bug"])
+                .ability("wonder_guard")
+                .hp(160, 160)
+                .stats(50, 50, 50, 50, 50)
+                .build()],
+        ),
+    ])
+}
+
+fn attack(state: &BattleState, move_id: &str) -> BattleState {
+    let engine = BattleEngine::default();
+    let actions = vec![move_action("p1", move_id, "p2"), move_action("p2", "splash", "p2")];
+    run_turn_with_seed(&engine, state, &actions, 1)
+}
+
+#[test]
+fn wonder_guard_blocks_neutral_moves() {
+    let next = attack(&guard_state(), "tackle");
+    assert_eq!(next.players[1].team[0].hp, 160);
+    assert!(next.log.iter().any(|line| line == "Guardには 効果が ないようだ……"));
+}
+
+#[test]
+fn wonder_guard_lets_super_effective_moves_through() {
+    let next = attack(&guard_state(), "flamethrower");
+    assert!(next.players[1].team[0].hp < 160);
+}
+
+#[test]
+fn wonder_guard_does_not_stop_status_damage() {
+    let mut state = guard_state();
+    state.players[1].team[0].statuses.push(status("burn", None));
+    let next = attack(&state, "tackle");
+    assert_eq!(next.players[1].team[0].hp, 150);
+}